@@ -0,0 +1,76 @@
+import { Command } from 'commander';
+import { PrimordynDB } from '../database/index.js';
+import { Indexer } from '../indexer/index.js';
+import chalk from 'chalk';
+
+export const doctorCommand = new Command('doctor')
+  .description('Check the index for problems, upgrade outdated schemas and rebuild broken indexes')
+  .argument('[path]', 'Project path (defaults to current directory)', '.')
+  .option('--rebuild', 'Delete the index and rebuild it from scratch')
+  .option('--json', 'Output JSON for AI agents')
+  .action(async (path: string, options) => {
+    try {
+      const projectPath = path === '.' ? process.cwd() : path;
+      const health = PrimordynDB.inspect(projectPath);
+      let action: 'none' | 'migrated' | 'rebuilt' = 'none';
+
+      if (options.rebuild) {
+        if (!options.json) {
+          console.log(chalk.blue('🔨 Rebuilding index...'));
+        }
+        PrimordynDB.remove(projectPath);
+        const db = new PrimordynDB(projectPath);
+        await new Indexer(db).index({ projectRoot: projectPath, verbose: !options.json });
+        db.close();
+        action = 'rebuilt';
+      } else if (health.status === 'outdated') {
+        // Opening the database applies pending migrations
+        new PrimordynDB(projectPath).close();
+        action = 'migrated';
+      }
+
+      if (options.json) {
+        console.log(JSON.stringify({
+          status: health.status,
+          schema_version: health.schemaVersion,
+          latest_version: health.latestVersion,
+          problems: health.problems,
+          action
+        }, null, 2));
+      } else {
+        console.log(chalk.blue('🩺 Primordyn Index Health'));
+        console.log(chalk.gray('━'.repeat(50)));
+        console.log(`Database: ${chalk.cyan(health.path)}`);
+        console.log(`Schema: ${chalk.yellow(health.schemaVersion ?? 'n/a')} (latest ${health.latestVersion})`);
+
+        for (const problem of health.problems) {
+          console.log(chalk.red(`  ✗ ${problem}`));
+        }
+
+        if (action === 'rebuilt') {
+          console.log(chalk.green('✅ Index rebuilt'));
+        } else if (action === 'migrated') {
+          console.log(chalk.green(`✅ Upgraded schema v${health.schemaVersion} → v${health.latestVersion}`));
+        } else if (health.status === 'ok') {
+          console.log(chalk.green('✅ Index is healthy'));
+        } else if (health.status === 'missing') {
+          console.log(chalk.yellow('No index found.'));
+          console.log(`  Run ${chalk.cyan('primordyn index')} to create one`);
+        } else {
+          const reason = health.status === 'newer'
+            ? 'The index was created by a newer version of primordyn.'
+            : 'The index is corrupt.';
+          console.log(chalk.red(`❌ ${reason}`));
+          console.log(`  Run ${chalk.cyan('primordyn doctor --rebuild')} to rebuild it`);
+        }
+      }
+
+      if (action === 'none' && (health.status === 'corrupt' || health.status === 'newer')) {
+        process.exit(1);
+      }
+
+    } catch (error) {
+      console.error(chalk.red('❌ Doctor failed:'), error instanceof Error ? error.message : error);
+      process.exit(1);
+    }
+  });
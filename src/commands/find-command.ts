@@ -0,0 +1,94 @@
+import { Command } from 'commander';
+import { PrimordynDB } from '../database/index.js';
+import { ContextRetriever } from '../retriever/index.js';
+import { FindCommandOptions, FindResult } from '../types/index.js';
+import {
+  validateFormat, validateLanguages, validatePositiveInteger, validateSearchTerm, ValidationError
+} from '../utils/validation.js';
+import chalk from 'chalk';
+
+export const findCommand = new Command('find')
+  .description('Find symbols by name, tolerating typos and partial names')
+  .argument('<pattern>', 'Symbol name or qualified name, e.g. getUsr or models.User')
+  .option('--type <type>', 'Only symbols of this type: function, class, method, etc.')
+  .option('--languages <langs>', 'Filter by languages: ts,js,py,go,etc')
+  .option('--limit <n>', 'Maximum number of results (default: 20)', '20')
+  .option('--include-content', 'Include the source of each symbol')
+  .option('--format <type>', 'Output format: ai, json, human (default: ai)', 'ai')
+  .action(async (pattern: string, options: FindCommandOptions) => {
+    try {
+      const validatedPattern = validateSearchTerm(pattern);
+      const format = validateFormat(options.format);
+      const limit = validatePositiveInteger(options.limit, '--limit');
+      const fileTypes = options.languages ? validateLanguages(options.languages) : undefined;
+
+      const db = new PrimordynDB();
+      const retriever = new ContextRetriever(db);
+      const results = retriever.findSymbolsFuzzy(validatedPattern, {
+        types: options.type ? [options.type] : undefined,
+        fileTypes,
+        limit,
+        includeContent: options.includeContent
+      });
+      db.close();
+
+      switch (format) {
+        case 'json':
+          console.log(JSON.stringify(results, null, 2));
+          break;
+
+        case 'ai':
+          outputAIFormat(validatedPattern, results);
+          break;
+
+        default:
+          outputHumanFormat(validatedPattern, results);
+      }
+    } catch (error) {
+      if (error instanceof ValidationError) {
+        console.error(chalk.red('❌ Validation error:'), error.message);
+      } else {
+        console.error(chalk.red('❌ Find failed:'), error instanceof Error ? error.message : error);
+      }
+      process.exit(1);
+    }
+  });
+
+function outputAIFormat(pattern: string, results: FindResult[]) {
+  console.log(`# Symbols matching: ${pattern}\n`);
+
+  if (results.length === 0) {
+    console.log('No matching symbols found.');
+    return;
+  }
+
+  results.forEach(result => {
+    console.log(`- ${result.type} ${result.qualifiedName || result.name} (${result.filePath}:${result.lineStart}) similarity ${result.similarity.toFixed(2)}`);
+    if (result.signature) {
+      console.log(`  \`${result.signature}\``);
+    }
+    if (result.content) {
+      console.log('```');
+      console.log(result.content);
+      console.log('```');
+    }
+  });
+}
+
+function outputHumanFormat(pattern: string, results: FindResult[]) {
+  console.log(chalk.blue(`🔎 Symbols matching "${pattern}"`));
+  console.log(chalk.gray('━'.repeat(50)));
+
+  if (results.length === 0) {
+    console.log(chalk.yellow('No matching symbols found.'));
+    return;
+  }
+
+  results.forEach(result => {
+    const similarity = `${Math.round(result.similarity * 100)}%`;
+    console.log(`  ${chalk.cyan(result.type.padEnd(10))} ${chalk.yellow(result.qualifiedName || result.name)} ${chalk.gray(`${result.filePath}:${result.lineStart}`)} ${chalk.gray(similarity)}`);
+    if (result.content) {
+      console.log(chalk.gray(result.content.split('\n').map(line => `    ${line}`).join('\n')));
+    }
+  });
+}
@@ -0,0 +1,111 @@
+import { Command } from 'commander';
+import { PrimordynDB } from '../database/index.js';
+import { ContextRetriever } from '../retriever/index.js';
+import { HierarchyCommandOptions, TypeHierarchy, TypeHierarchyNode } from '../types/index.js';
+import { validateFormat, validateDepth, validateSearchTerm, ValidationError } from '../utils/validation.js';
+import chalk from 'chalk';
+
+export const hierarchyCommand = new Command('hierarchy')
+  .description('Show supertypes, subtypes and implementors of a type')
+  .argument('<type>', 'Class, interface, struct or trait name')
+  .option('--format <type>', 'Output format: ai, json, human (default: ai)', 'ai')
+  .option('--depth <n>', 'Levels of the hierarchy to follow (default: 3)', '3')
+  .action(async (typeName: string, options: HierarchyCommandOptions) => {
+    try {
+      const validatedTypeName = validateSearchTerm(typeName);
+      const format = validateFormat(options.format);
+      const depth = validateDepth(options.depth);
+
+      const db = new PrimordynDB();
+      const retriever = new ContextRetriever(db);
+      const hierarchy = await retriever.getTypeHierarchy(validatedTypeName, depth);
+      db.close();
+
+      if (!hierarchy) {
+        if (format === 'json') {
+          console.log(JSON.stringify(null));
+        } else {
+          console.log(chalk.yellow(`No type named "${validatedTypeName}" found in the index.`));
+        }
+        return;
+      }
+
+      switch (format) {
+        case 'json':
+          console.log(JSON.stringify(hierarchy, null, 2));
+          break;
+
+        case 'ai':
+          outputAIFormat(hierarchy);
+          break;
+
+        default:
+          outputHumanFormat(hierarchy);
+      }
+    } catch (error) {
+      if (error instanceof ValidationError) {
+        console.error(chalk.red('❌ Validation error:'), error.message);
+      } else {
+        console.error(chalk.red('❌ Hierarchy failed:'), error instanceof Error ? error.message : error);
+      }
+      process.exit(1);
+    }
+  });
+
+function location(node: TypeHierarchyNode): string {
+  if (!node.filePath) return 'external';
+  return node.line ? `${node.filePath}:${node.line}` : node.filePath;
+}
+
+function outputAIFormat(hierarchy: TypeHierarchy) {
+  console.log(`# Type hierarchy: ${hierarchy.name}\n`);
+
+  if (hierarchy.definitions.length > 0) {
+    console.log('## Defined In');
+    hierarchy.definitions.forEach(definition => {
+      console.log(`- ${definition.type} ${definition.qualifiedName || definition.name} (${definition.filePath}:${definition.line})`);
+    });
+    console.log('');
+  }
+
+  const section = (title: string, nodes: TypeHierarchyNode[]) => {
+    if (nodes.length === 0) return;
+    console.log(`## ${title}`);
+    const print = (node: TypeHierarchyNode, indent: number) => {
+      console.log(`${'  '.repeat(indent)}- ${node.kind} ${node.name} (${node.type}, ${location(node)})`);
+      node.children.forEach(child => print(child, indent + 1));
+    };
+    nodes.forEach(node => print(node, 0));
+    console.log('');
+  };
+
+  section('Supertypes', hierarchy.supertypes);
+  section('Subtypes', hierarchy.subtypes);
+  section('Implementors', hierarchy.implementors);
+}
+
+function outputHumanFormat(hierarchy: TypeHierarchy) {
+  console.log(chalk.blue(`🧬 Type hierarchy: ${hierarchy.name}`));
+  console.log(chalk.gray('━'.repeat(50)));
+
+  hierarchy.definitions.forEach(definition => {
+    console.log(`  ${chalk.cyan(definition.type)} ${chalk.yellow(definition.qualifiedName || definition.name)} ${chalk.gray(`${definition.filePath}:${definition.line}`)}`);
+  });
+
+  const section = (title: string, nodes: TypeHierarchyNode[]) => {
+    console.log(chalk.green(`\n${title}:`));
+    if (nodes.length === 0) {
+      console.log(chalk.gray('  (none)'));
+      return;
+    }
+    const print = (node: TypeHierarchyNode, indent: number) => {
+      console.log(`${'  '.repeat(indent + 1)}${chalk.gray(node.kind)} ${chalk.yellow(node.name)} ${chalk.gray(location(node))}`);
+      node.children.forEach(child => print(child, indent + 1));
+    };
+    nodes.forEach(node => print(node, 0));
+  };
+
+  section('⬆️ Supertypes', hierarchy.supertypes);
+  section('⬇️ Subtypes', hierarchy.subtypes);
+  section('🔌 Implementors', hierarchy.implementors);
+}
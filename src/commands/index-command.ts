@@ -1,6 +1,8 @@
 import { Command } from 'commander';
 import { PrimordynDB } from '../database/index.js';
 import { Indexer } from '../indexer/index.js';
+import { IndexWatcher } from '../indexer/watcher.js';
+import { validateJobs } from '../utils/validation.js';
 import chalk from 'chalk';
 
 export const indexCommand = new Command('index')
@@ -11,9 +13,13 @@ export const indexCommand = new Command('index')
   .option('--max-size <kb>', 'Maximum file size in KB (default: 1024)', '1024')
   .option('--update', 'Update only changed files (incremental)')
   .option('--quiet', 'Minimal output')
+  .option('--watch', 'Keep watching for changes and update the index incrementally')
+  .option('--jobs <n>', 'Number of worker threads used for extraction (default: CPU cores - 1)')
+  .option('--embeddings', 'Also build the vector index used by query --semantic')
   .action(async (path: string, options) => {
     try {
       const projectPath = path === '.' ? process.cwd() : path;
+      const jobs = options.jobs ? validateJobs(options.jobs) : undefined;
       
       const db = new PrimordynDB(projectPath);
       const indexer = new Indexer(db);
@@ -48,16 +54,31 @@ export const indexCommand = new Command('index')
         languages,
         maxFileSize,
         updateExisting: options.update,
+        jobs,
+        embeddings: options.embeddings,
         verbose: !options.quiet
       });
+      const filesPerSecond = stats.filesIndexed / Math.max(stats.timeElapsed / 1000, 0.001);
       
       if (!options.quiet) {
         console.log('\n' + chalk.green('✅ Indexing complete!'));
         console.log(chalk.blue('📊 Summary:'));
         console.log(`  • Files indexed: ${chalk.yellow(stats.filesIndexed)}`);
+        if (stats.filesRemoved || stats.filesRenamed) {
+          console.log(`  • Files removed: ${chalk.yellow(stats.filesRemoved || 0)}, renamed: ${chalk.yellow(stats.filesRenamed || 0)}`);
+        }
         console.log(`  • Symbols extracted: ${chalk.yellow(stats.symbolsExtracted)}`);
         console.log(`  • Total tokens: ${chalk.yellow(stats.totalTokens.toLocaleString())}`);
-        console.log(`  • Time elapsed: ${chalk.yellow((stats.timeElapsed / 1000).toFixed(2))}s`);
+        if (stats.callsResolved) {
+          console.log(`  • Calls linked: ${chalk.yellow(stats.callsResolved)} (${stats.callsAmbiguous || 0} ambiguous)`);
+        }
+        if (stats.crates) {
+          console.log(`  • Cargo crates: ${chalk.yellow(stats.crates)}`);
+        }
+        if (stats.chunksEmbedded !== undefined) {
+          console.log(`  • Chunks embedded: ${chalk.yellow(stats.chunksEmbedded)}`);
+        }
+        console.log(`  • Time elapsed: ${chalk.yellow((stats.timeElapsed / 1000).toFixed(2))}s (${chalk.yellow(filesPerSecond.toFixed(1))} files/s)`);
         
         if (stats.errors > 0) {
           console.log(`  • Errors: ${chalk.red(stats.errors)}`);
@@ -68,13 +89,54 @@ export const indexCommand = new Command('index')
         // Minimal output for AI agents
         console.log(JSON.stringify({
           indexed: stats.filesIndexed,
+          removed: stats.filesRemoved || 0,
+          renamed: stats.filesRenamed || 0,
           symbols: stats.symbolsExtracted, 
           tokens: stats.totalTokens,
           time_ms: stats.timeElapsed,
-          errors: stats.errors
+          files_per_sec: Math.round(filesPerSecond * 10) / 10,
+          errors: stats.errors,
+          crates: stats.crates || 0,
+          calls_linked: stats.callsResolved || 0,
+          chunks_embedded: stats.chunksEmbedded || 0
         }));
       }
       
+      if (options.watch) {
+        const watcher = new IndexWatcher(indexer, {
+          projectRoot: projectPath,
+          languages,
+          maxFileSize,
+          onUpdate: (update) => {
+            if (options.quiet) {
+              console.log(JSON.stringify(update));
+              return;
+            }
+            update.indexed.forEach(file => console.log(chalk.green('  ↻'), file));
+            update.removed.forEach(file => console.log(chalk.red('  ✗'), file));
+            if (update.relinked > 0) {
+              console.log(chalk.gray(`    ${update.relinked} calls relinked`));
+            }
+          },
+          onError: (error) => {
+            console.error(chalk.red('❌ Watch error:'), error instanceof Error ? error.message : error);
+          }
+        });
+        watcher.start();
+
+        if (!options.quiet) {
+          console.log('\n' + chalk.blue('👀 Watching for changes...'), chalk.gray('(Ctrl+C to stop)'));
+        }
+
+        process.on('SIGINT', async () => {
+          await watcher.flush();
+          watcher.close();
+          db.close();
+          process.exit(0);
+        });
+        return;
+      }
+      
       db.close();
       
     } catch (error) {
@@ -3,6 +3,12 @@ import { indexCommand } from './index-command.js';
 import { queryCommand } from './query-command.js';
 import { statsCommand } from './stats-command.js';
 import { clearCommand } from './clear-command.js';
+import { lspCommand } from './lsp-command.js';
+import { serveCommand } from './serve-command.js';
+import { doctorCommand } from './doctor-command.js';
+import { migrateCommand } from './migrate-command.js';
+import { hierarchyCommand } from './hierarchy-command.js';
+import { findCommand } from './find-command.js';
 import chalk from 'chalk';
 
 export function createCLI(): Command {
@@ -22,6 +28,12 @@ export function createCLI(): Command {
   program.addCommand(queryCommand);
   program.addCommand(statsCommand);
   program.addCommand(clearCommand);
+  program.addCommand(lspCommand);
+  program.addCommand(serveCommand);
+  program.addCommand(doctorCommand);
+  program.addCommand(migrateCommand);
+  program.addCommand(hierarchyCommand);
+  program.addCommand(findCommand);
 
   // Global error handler
   program.exitOverride((err) => {
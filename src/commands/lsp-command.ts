@@ -0,0 +1,11 @@
+import { Command } from 'commander';
+import { PrimordynLanguageServer } from '../lsp/server.js';
+
+export const lspCommand = new Command('lsp')
+  .description('Start a Language Server Protocol server over stdio backed by the index')
+  .option('--stdio', 'Communicate over stdin/stdout (default)')
+  .action(() => {
+    // stdout carries the protocol, so nothing else may be written to it
+    const server = new PrimordynLanguageServer(process.stdin, process.stdout);
+    server.listen();
+  });
@@ -0,0 +1,35 @@
+import { Command } from 'commander';
+import { PrimordynDB } from '../database/index.js';
+import chalk from 'chalk';
+
+export const migrateCommand = new Command('migrate')
+  .description('Upgrade the index database schema in place')
+  .argument('[path]', 'Project path (defaults to current directory)', '.')
+  .action(async (path: string) => {
+    try {
+      const projectPath = path === '.' ? process.cwd() : path;
+      const health = PrimordynDB.inspect(projectPath);
+
+      if (health.status === 'missing') {
+        console.log(chalk.yellow('No index found.'));
+        console.log(`  Run ${chalk.cyan('primordyn index')} to create one`);
+        return;
+      }
+
+      if (health.status === 'ok') {
+        console.log(chalk.green(`✅ Schema is up to date (v${health.latestVersion})`));
+        return;
+      }
+
+      // Opening the database applies pending migrations
+      const db = new PrimordynDB(projectPath);
+      const version = db.getSchemaVersion();
+      db.close();
+
+      console.log(chalk.green(`✅ Upgraded schema v${health.schemaVersion} → v${version}`));
+
+    } catch (error) {
+      console.error(chalk.red('❌ Migration failed:'), error instanceof Error ? error.message : error);
+      process.exit(1);
+    }
+  });
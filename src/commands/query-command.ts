@@ -2,7 +2,7 @@ import { Command } from 'commander';
 import { PrimordynDB } from '../database/index.js';
 import { ContextRetriever } from '../retriever/index.js';
 import { QueryCommandOptions, QueryCommandResult, FileResult, DependencyGraph, ImpactAnalysis, GitHistory, RecentFileChanges } from '../types/index.js';
-import { validateTokenLimit, validateFormat, validateLanguages, validateDays, validateDepth, validateSearchTerm, ValidationError } from '../utils/validation.js';
+import { validateTokenLimit, validateFormat, validateLanguages, validateCrates, validateDays, validateDepth, validateSearchTerm, validateSortBy, ValidationError } from '../utils/validation.js';
 import chalk from 'chalk';
 
 export const queryCommand = new Command('query')
@@ -18,6 +18,9 @@ export const queryCommand = new Command('query')
   .option('--recent <days>', 'Show commits from last N days (default: 7)')
   .option('--blame', 'Show git blame (who last modified each line)')
   .option('--languages <langs>', 'Filter by languages: ts,js,py,go,etc')
+  .option('--crates <names>', 'Filter by Cargo crates: core,cli,etc')
+  .option('--semantic', 'Blend embedding similarity into the ranking, for natural-language queries')
+  .option('--sort <mode>', 'Order results by: relevance, path, size, modified (default: relevance)', 'relevance')
   .action(async (searchTerm: string, options: QueryCommandOptions) => {
     try {
       // Validate inputs
@@ -26,14 +29,16 @@ export const queryCommand = new Command('query')
       const format = validateFormat(options.format);
       const depth = validateDepth(options.depth);
       const fileTypes = options.languages ? validateLanguages(options.languages) : undefined;
+      const crates = options.crates ? validateCrates(options.crates) : undefined;
       const days = options.recent ? validateDays(options.recent) : undefined;
+      const sortBy = validateSortBy(options.sort || 'relevance');
       
       const db = new PrimordynDB();
       const retriever = new ContextRetriever(db);
       // const depth = parseInt(options.depth); // For future context expansion
       
       // First, try to find as a symbol
-      const symbols = await retriever.findSymbol(validatedSearchTerm, { fileTypes });
+      const symbols = await retriever.findSymbol(validatedSearchTerm, { fileTypes, crates });
       
       // Then get broader context
       const searchResult = await retriever.query(validatedSearchTerm, {
@@ -42,13 +47,15 @@ export const queryCommand = new Command('query')
         includeSymbols: true,
         includeImports: true,
         fileTypes,
-        sortBy: 'relevance'
+        crates,
+        sortBy,
+        semantic: options.semantic
       });
       
       // Find usages if requested
       let usages: FileResult[] = [];
       if (options.includeCallers && symbols.length > 0) {
-        usages = await retriever.findUsages(validatedSearchTerm, { fileTypes, maxTokens: 2000 });
+        usages = await retriever.findUsages(validatedSearchTerm, { fileTypes, crates, maxTokens: 2000 });
       }
       
       // Get dependency graph if requested (using depth for call graph traversal)
@@ -75,18 +82,26 @@ export const queryCommand = new Command('query')
         recentChanges = await retriever.getRecentChanges(days);
       }
       
+      // Nothing matched: offer symbol names one typo away
+      const nothingFound = symbols.length === 0 && searchResult.files.length === 0 && searchResult.symbols.length === 0;
+      const suggestions = nothingFound ? retriever.suggestNames(validatedSearchTerm, { fileTypes, crates }) : [];
+      
       // Combine results intelligently
       const result: QueryCommandResult = {
-        primarySymbol: symbols.length > 0 ? symbols[0] : null,
+        // Natural-language queries rarely name a symbol; fall back to the best semantic match
+        primarySymbol: symbols[0] ?? (options.semantic ? searchResult.symbols[0] ?? null : null),
         allSymbols: symbols,
         files: searchResult.files,
+        symbols: searchResult.symbols,
         usages,
         dependencyGraph,
         impactAnalysis,
         gitHistory,
         recentChanges,
+        suggestions,
         totalTokens: searchResult.totalTokens,
-        truncated: searchResult.truncated
+        truncated: searchResult.truncated,
+        packing: searchResult.packing ?? null
       };
       
       // Handle different output formats
@@ -117,12 +132,24 @@ export const queryCommand = new Command('query')
 
 function outputAIFormat(searchTerm: string, result: QueryCommandResult, options: QueryCommandOptions) {
   console.log(`# Context for: ${searchTerm}\n`);
+
+  if (result.suggestions.length > 0) {
+    console.log(`No matches. Did you mean: ${result.suggestions.join(', ')}?\n`);
+  }
   
   // Primary symbol if found
   if (result.primarySymbol) {
     const sym = result.primarySymbol;
-    console.log(`## ${sym.name} (${sym.type})`);
+    console.log(`## ${sym.qualifiedName || sym.name} (${sym.type})`);
     console.log(`📍 ${sym.filePath}:${sym.lineStart}-${sym.lineEnd}\n`);
+
+    if (sym.members && sym.members.length > 0) {
+      console.log(`### Members`);
+      sym.members.forEach((member) => {
+        console.log(`- **${member.name}** (${member.type}) - line ${member.lineStart}`);
+      });
+      console.log();
+    }
     
     if (sym.signature) {
       console.log(`### Signature`);
@@ -143,7 +170,7 @@ function outputAIFormat(searchTerm: string, result: QueryCommandResult, options:
   if (result.allSymbols.length > 1) {
     console.log(`### Related Symbols`);
     result.allSymbols.slice(1, 6).forEach((sym) => {
-      console.log(`- **${sym.name}** (${sym.type}) - ${sym.filePath}:${sym.lineStart}`);
+      console.log(`- **${sym.qualifiedName || sym.name}** (${sym.type}) - ${sym.filePath}:${sym.lineStart}`);
     });
     console.log();
   }
@@ -211,6 +238,15 @@ function outputAIFormat(searchTerm: string, result: QueryCommandResult, options:
       });
       console.log();
     }
+
+    if (graph.importedBy.length > 0) {
+      console.log(`### Imported By`);
+      graph.importedBy.forEach((edge) => {
+        const location = edge.line !== null ? `${edge.filePath}:${edge.line}` : edge.filePath;
+        console.log(`- ${location} via \`${edge.specifier}\`${edge.reexport ? ' (re-export)' : ''}`);
+      });
+      console.log();
+    }
   }
   
   // Show impact analysis
@@ -315,17 +351,15 @@ function outputAIFormat(searchTerm: string, result: QueryCommandResult, options:
     console.log();
   }
   
-  // Show where it's used (different from graph - this is text search based)
+  // Show where it's used - every reference, not only calls
   if (result.usages && result.usages.length > 0) {
-    console.log(`### Text References`);
+    console.log(`### References`);
     result.usages.slice(0, 10).forEach((file) => {
       console.log(`- **${file.relativePath}**`);
-      if (file.metadata && typeof file.metadata === 'object' && 'usageLines' in file.metadata) {
-        const usageLines = (file.metadata as { usageLines?: number[] }).usageLines;
-        if (Array.isArray(usageLines)) {
-          const lines = usageLines.slice(0, 3);
-          console.log(`  Lines: ${lines.join(', ')}${usageLines.length > 3 ? '...' : ''}`);
-        }
+      const references = (file.metadata as { references?: Array<{ kind: string; line: number }> } | undefined)?.references;
+      if (Array.isArray(references)) {
+        const shown = references.slice(0, 3).map(reference => `${reference.line} (${reference.kind})`);
+        console.log(`  Lines: ${shown.join(', ')}${references.length > 3 ? '...' : ''}`);
       }
     });
     console.log();
@@ -346,6 +380,12 @@ function outputAIFormat(searchTerm: string, result: QueryCommandResult, options:
   // Token usage
   console.log(`### Token Usage`);
   console.log(`- Total tokens: ${result.totalTokens}`);
+  if (result.packing?.reduced.length) {
+    console.log(`- Shortened to fit: ${result.packing.reduced.map(item => `${item.label} (${item.form})`).join(', ')}`);
+  }
+  if (result.packing?.dropped.length) {
+    console.log(`- Left out: ${result.packing.dropped.map(item => item.label).join(', ')}`);
+  }
   if (result.truncated) {
     console.log(`- ⚠️ Results truncated (increase --tokens for more context)`);
   }
@@ -357,6 +397,9 @@ function outputHumanFormat(searchTerm: string, result: QueryCommandResult, _opti
   
   if (!result.primarySymbol && result.files.length === 0) {
     console.log(chalk.yellow('No results found.'));
+    if (result.suggestions.length > 0) {
+      console.log(chalk.blue('\n🤔 Did you mean:'), result.suggestions.map(name => chalk.cyan(name)).join(', '));
+    }
     console.log('\n' + chalk.blue('💡 Try:'));
     console.log('  • Different search terms');
     console.log('  • Check indexed files:', chalk.cyan('primordyn stats'));
@@ -367,12 +410,16 @@ function outputHumanFormat(searchTerm: string, result: QueryCommandResult, _opti
   if (result.primarySymbol) {
     const sym = result.primarySymbol;
     console.log(chalk.green('\n🎯 Primary Match:'));
-    console.log(chalk.blue(`   ${sym.name} (${sym.type})`));
+    console.log(chalk.blue(`   ${sym.qualifiedName || sym.name} (${sym.type})`));
     console.log(chalk.gray(`   📍 ${sym.filePath}:${sym.lineStart}-${sym.lineEnd}`));
     
     if (sym.signature) {
       console.log(chalk.gray(`   Signature: ${sym.signature.substring(0, 100)}${sym.signature.length > 100 ? '...' : ''}`));
     }
+
+    if (sym.members && sym.members.length > 0) {
+      console.log(chalk.gray(`   Members: ${sym.members.map(member => `${member.name} (${member.type})`).join(', ')}`));
+    }
     
     if (sym.content) {
       console.log(chalk.gray('\n   Implementation:'));
@@ -388,7 +435,7 @@ function outputHumanFormat(searchTerm: string, result: QueryCommandResult, _opti
   if (result.allSymbols.length > 1) {
     console.log(chalk.green('\n🏷️ Other Matches:'));
     result.allSymbols.slice(1, 6).forEach((sym, index: number) => {
-      console.log(chalk.blue(`   ${index + 2}. ${sym.name} (${sym.type})`));
+      console.log(chalk.blue(`   ${index + 2}. ${sym.qualifiedName || sym.name} (${sym.type})`));
       console.log(chalk.gray(`      ${sym.filePath}:${sym.lineStart}`));
     });
   }
@@ -410,7 +457,9 @@ function outputHumanFormat(searchTerm: string, result: QueryCommandResult, _opti
   console.log(`  • Total tokens: ${chalk.yellow(result.totalTokens.toLocaleString())}`);
   
   if (result.truncated) {
-    console.log(chalk.yellow('\n  ⚠️ Results truncated due to token limit'));
+    const reduced = result.packing?.reduced.length || 0;
+    const dropped = result.packing?.dropped.length || 0;
+    console.log(chalk.yellow(`\n  ⚠️ Results packed to fit the token limit: ${reduced} shortened, ${dropped} left out`));
   }
   
   console.log(chalk.gray('\n💡 Tips:'));
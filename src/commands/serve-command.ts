@@ -0,0 +1,22 @@
+import { Command } from 'commander';
+import { PrimordynMcpServer } from '../mcp/server.js';
+import chalk from 'chalk';
+
+export const serveCommand = new Command('serve')
+  .description('Serve the index to AI agents')
+  .option('--mcp', 'Run a Model Context Protocol server over stdio')
+  .action((options) => {
+    if (!options.mcp) {
+      console.error(chalk.red('❌ Specify a protocol to serve, e.g.'), chalk.cyan('primordyn serve --mcp'));
+      process.exit(1);
+    }
+
+    try {
+      // stdout carries the protocol, so nothing else may be written to it
+      const server = new PrimordynMcpServer(process.stdin, process.stdout);
+      server.listen();
+    } catch (error) {
+      console.error(chalk.red('❌ Failed to start MCP server:'), error instanceof Error ? error.message : error);
+      process.exit(1);
+    }
+  });
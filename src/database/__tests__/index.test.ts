@@ -1,5 +1,7 @@
-import { PrimordynDB } from '../index.js';
-import { mkdirSync, rmSync, existsSync } from 'fs';
+import { PrimordynDB, DatabaseSchemaError } from '../index.js';
+import { MIGRATIONS, LATEST_SCHEMA_VERSION } from '../migrations.js';
+import Database from 'better-sqlite3';
+import { mkdirSync, rmSync, existsSync, writeFileSync } from 'fs';
 import { join } from 'path';
 
 describe('PrimordynDB', () => {
@@ -117,4 +119,40 @@ describe('PrimordynDB', () => {
       .get() as { query_hash: string };
     expect(remaining.query_hash).toBe('valid_hash');
   });
-});
\ No newline at end of file
+
+  test('should record the latest schema version', () => {
+    expect(db.getSchemaVersion()).toBe(LATEST_SCHEMA_VERSION);
+    expect(PrimordynDB.inspect(testDir).status).toBe('ok');
+  });
+
+  test('should upgrade a database created before schema versioning', () => {
+    db.close();
+    PrimordynDB.remove(testDir);
+
+    // Baseline schema, as written by releases without migrations
+    const legacy = new Database(join(testDir, '.primordyn', 'context.db'));
+    MIGRATIONS[0].up(legacy);
+    legacy.close();
+
+    expect(PrimordynDB.inspect(testDir)).toMatchObject({ status: 'outdated', schemaVersion: 1 });
+
+    db = new PrimordynDB(testDir);
+    const columns = (db.getDatabase().prepare('PRAGMA table_info(call_graph)').all() as { name: string }[])
+      .map(c => c.name);
+    expect(columns).toContain('resolution_confidence');
+    expect(db.getSchemaVersion()).toBe(LATEST_SCHEMA_VERSION);
+  });
+
+  test('should detect a corrupt database', () => {
+    db.close();
+    PrimordynDB.remove(testDir);
+    writeFileSync(join(testDir, '.primordyn', 'context.db'), 'not a database');
+
+    expect(PrimordynDB.inspect(testDir).status).toBe('corrupt');
+    expect(() => new PrimordynDB(testDir)).toThrow(DatabaseSchemaError);
+
+    // Reopen a fresh database so afterEach can close it
+    PrimordynDB.remove(testDir);
+    db = new PrimordynDB(testDir);
+  });
+});
@@ -1,13 +1,41 @@
 import Database from 'better-sqlite3';
-import { join } from 'path';
-import { existsSync, mkdirSync } from 'fs';
-import type { DatabaseInfo } from '../types/index.js';
+import { join, resolve } from 'path';
+import { existsSync, mkdirSync, rmSync } from 'fs';
+import { MIGRATIONS, LATEST_SCHEMA_VERSION } from './migrations.js';
+import type { DatabaseInfo, DatabaseHealth } from '../types/index.js';
+
+const REQUIRED_TABLES = ['files', 'symbols', 'call_graph', 'context_cache', 'files_fts', 'symbols_fts'];
+
+export class DatabaseSchemaError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = 'DatabaseSchemaError';
+  }
+}
+
+/**
+ * Current schema version. Databases created before versioning was added
+ * have no `schema_version` table and are treated as version 1.
+ */
+function readSchemaVersion(db: Database.Database): number {
+  const tables = new Set((db.prepare(
+    "SELECT name FROM sqlite_master WHERE type = 'table' AND name IN ('schema_version', 'files')"
+  ).all() as { name: string }[]).map(row => row.name));
+
+  if (tables.has('schema_version')) {
+    const row = db.prepare('SELECT MAX(version) as version FROM schema_version').get() as { version: number | null };
+    return row.version ?? 0;
+  }
+  return tables.has('files') ? 1 : 0;
+}
 
 export class PrimordynDB {
   private db: Database.Database;
   private dbPath: string;
+  private projectRoot: string;
 
   constructor(projectPath: string = process.cwd()) {
+    this.projectRoot = resolve(projectPath);
     const dbDir = join(projectPath, '.primordyn');
     
     if (!existsSync(dbDir)) {
@@ -16,128 +44,123 @@ export class PrimordynDB {
 
     this.dbPath = join(dbDir, 'context.db');
     this.db = new Database(this.dbPath);
-    this.initializeSchema();
-  }
 
-  private initializeSchema(): void {
-    // Enable foreign keys
-    this.db.pragma('foreign_keys = ON');
-    
-    // Create tables
-    this.db.exec(`
-      CREATE TABLE IF NOT EXISTS files (
-        id INTEGER PRIMARY KEY AUTOINCREMENT,
-        path TEXT UNIQUE NOT NULL,
-        relative_path TEXT NOT NULL,
-        content TEXT NOT NULL,
-        hash TEXT NOT NULL,
-        size INTEGER NOT NULL,
-        language TEXT,
-        last_modified TEXT NOT NULL,
-        indexed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        metadata TEXT
+    try {
+      // Enable foreign keys
+      this.db.pragma('foreign_keys = ON');
+      this.migrate();
+    } catch (error) {
+      this.db.close();
+      if (error instanceof DatabaseSchemaError) {
+        throw error;
+      }
+      throw new DatabaseSchemaError(
+        `Index at ${this.dbPath} is corrupt or unreadable (${error instanceof Error ? error.message : error}). ` +
+        'Run "primordyn doctor --rebuild" to rebuild it.'
       );
+    }
+  }
 
-      CREATE TABLE IF NOT EXISTS symbols (
-        id INTEGER PRIMARY KEY AUTOINCREMENT,
-        file_id INTEGER NOT NULL,
-        name TEXT NOT NULL,
-        type TEXT NOT NULL,
-        line_start INTEGER NOT NULL,
-        line_end INTEGER NOT NULL,
-        signature TEXT,
-        documentation TEXT,
-        metadata TEXT,
-        FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE
+  /**
+   * Bring the schema up to date by applying pending migrations in order,
+   * each in its own transaction.
+   */
+  public migrate(): { from: number; to: number } {
+    const from = readSchemaVersion(this.db);
+    if (from > LATEST_SCHEMA_VERSION) {
+      throw new DatabaseSchemaError(
+        `Index schema v${from} is newer than this version of primordyn supports (v${LATEST_SCHEMA_VERSION}). ` +
+        'Upgrade primordyn or run "primordyn doctor --rebuild".'
       );
+    }
 
-      CREATE TABLE IF NOT EXISTS context_cache (
-        id INTEGER PRIMARY KEY AUTOINCREMENT,
-        query_hash TEXT UNIQUE NOT NULL,
-        result TEXT NOT NULL,
-        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-        expires_at TIMESTAMP NOT NULL
-      );
+    this.db.exec(`
+      CREATE TABLE IF NOT EXISTS schema_version (
+        version INTEGER PRIMARY KEY,
+        description TEXT NOT NULL,
+        applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+      )
+    `);
 
-      -- Call relationships table for dependency graph
-      CREATE TABLE IF NOT EXISTS call_graph (
-        id INTEGER PRIMARY KEY AUTOINCREMENT,
-        caller_symbol_id INTEGER,
-        caller_file_id INTEGER,
-        callee_name TEXT NOT NULL,
-        callee_symbol_id INTEGER,
-        callee_file_id INTEGER,
-        call_type TEXT NOT NULL, -- 'function', 'method', 'constructor', 'import'
-        line_number INTEGER NOT NULL,
-        column_number INTEGER,
-        FOREIGN KEY (caller_symbol_id) REFERENCES symbols (id) ON DELETE CASCADE,
-        FOREIGN KEY (caller_file_id) REFERENCES files (id) ON DELETE CASCADE,
-        FOREIGN KEY (callee_symbol_id) REFERENCES symbols (id) ON DELETE SET NULL,
-        FOREIGN KEY (callee_file_id) REFERENCES files (id) ON DELETE SET NULL
-      );
+    const record = this.db.prepare('INSERT OR REPLACE INTO schema_version (version, description) VALUES (?, ?)');
+    for (const migration of MIGRATIONS) {
+      if (migration.version <= from) {
+        continue;
+      }
+      this.db.transaction(() => {
+        migration.up(this.db);
+        record.run(migration.version, migration.description);
+      })();
+    }
 
-      -- Create indexes for better performance
-      CREATE INDEX IF NOT EXISTS idx_files_path ON files(path);
-      CREATE INDEX IF NOT EXISTS idx_files_relative_path ON files(relative_path);
-      CREATE INDEX IF NOT EXISTS idx_files_language ON files(language);
-      CREATE INDEX IF NOT EXISTS idx_files_hash ON files(hash);
-      CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols(name);
-      CREATE INDEX IF NOT EXISTS idx_symbols_type ON symbols(type);
-      CREATE INDEX IF NOT EXISTS idx_symbols_file_id ON symbols(file_id);
-      CREATE INDEX IF NOT EXISTS idx_context_cache_query_hash ON context_cache(query_hash);
-      CREATE INDEX IF NOT EXISTS idx_context_cache_expires_at ON context_cache(expires_at);
-      
-      -- Indexes for call graph
-      CREATE INDEX IF NOT EXISTS idx_call_graph_caller_symbol ON call_graph(caller_symbol_id);
-      CREATE INDEX IF NOT EXISTS idx_call_graph_callee_symbol ON call_graph(callee_symbol_id);
-      CREATE INDEX IF NOT EXISTS idx_call_graph_callee_name ON call_graph(callee_name);
-      CREATE INDEX IF NOT EXISTS idx_call_graph_caller_file ON call_graph(caller_file_id);
-      CREATE INDEX IF NOT EXISTS idx_call_graph_callee_file ON call_graph(callee_file_id);
-
-      -- Full-text search indexes
-      CREATE VIRTUAL TABLE IF NOT EXISTS files_fts USING fts5(
-        relative_path, content, language,
-        content='files',
-        content_rowid='id'
-      );
+    return { from, to: LATEST_SCHEMA_VERSION };
+  }
 
-      CREATE VIRTUAL TABLE IF NOT EXISTS symbols_fts USING fts5(
-        name, signature, documentation,
-        content='symbols', 
-        content_rowid='id'
-      );
+  public getSchemaVersion(): number {
+    return readSchemaVersion(this.db);
+  }
 
-      -- Triggers to keep FTS in sync
-      CREATE TRIGGER IF NOT EXISTS files_fts_insert AFTER INSERT ON files BEGIN
-        INSERT INTO files_fts(rowid, relative_path, content, language) 
-        VALUES (new.id, new.relative_path, new.content, new.language);
-      END;
-
-      CREATE TRIGGER IF NOT EXISTS files_fts_delete AFTER DELETE ON files BEGIN
-        DELETE FROM files_fts WHERE rowid = old.id;
-      END;
-
-      CREATE TRIGGER IF NOT EXISTS files_fts_update AFTER UPDATE ON files BEGIN
-        DELETE FROM files_fts WHERE rowid = old.id;
-        INSERT INTO files_fts(rowid, relative_path, content, language) 
-        VALUES (new.id, new.relative_path, new.content, new.language);
-      END;
-
-      CREATE TRIGGER IF NOT EXISTS symbols_fts_insert AFTER INSERT ON symbols BEGIN
-        INSERT INTO symbols_fts(rowid, name, signature, documentation) 
-        VALUES (new.id, new.name, new.signature, new.documentation);
-      END;
-
-      CREATE TRIGGER IF NOT EXISTS symbols_fts_delete AFTER DELETE ON symbols BEGIN
-        DELETE FROM symbols_fts WHERE rowid = old.id;
-      END;
-
-      CREATE TRIGGER IF NOT EXISTS symbols_fts_update AFTER UPDATE ON symbols BEGIN
-        DELETE FROM symbols_fts WHERE rowid = old.id;
-        INSERT INTO symbols_fts(rowid, name, signature, documentation) 
-        VALUES (new.id, new.name, new.signature, new.documentation);
-      END;
-    `);
+  /**
+   * Check an index without opening it for writing or migrating it.
+   */
+  public static inspect(projectPath: string = process.cwd()): DatabaseHealth {
+    const dbPath = join(projectPath, '.primordyn', 'context.db');
+    const health: DatabaseHealth = {
+      path: dbPath,
+      status: 'ok',
+      schemaVersion: null,
+      latestVersion: LATEST_SCHEMA_VERSION,
+      problems: []
+    };
+
+    if (!existsSync(dbPath)) {
+      health.status = 'missing';
+      return health;
+    }
+
+    let db: Database.Database | null = null;
+    try {
+      db = new Database(dbPath, { readonly: true, fileMustExist: true });
+
+      const integrity = db.pragma('quick_check', { simple: true });
+      if (integrity !== 'ok') {
+        health.problems.push(`Integrity check failed: ${integrity}`);
+      }
+
+      health.schemaVersion = readSchemaVersion(db);
+      const tables = new Set((db.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table'"
+      ).all() as { name: string }[]).map(row => row.name));
+      for (const table of REQUIRED_TABLES) {
+        if (!tables.has(table)) {
+          health.problems.push(`Missing table: ${table}`);
+        }
+      }
+    } catch (error) {
+      health.problems.push(error instanceof Error ? error.message : String(error));
+    } finally {
+      db?.close();
+    }
+
+    if (health.problems.length > 0) {
+      health.status = 'corrupt';
+    } else if (health.schemaVersion! > LATEST_SCHEMA_VERSION) {
+      health.status = 'newer';
+    } else if (health.schemaVersion! < LATEST_SCHEMA_VERSION) {
+      health.status = 'outdated';
+    }
+
+    return health;
+  }
+
+  /**
+   * Delete the index database so it can be rebuilt from scratch.
+   */
+  public static remove(projectPath: string = process.cwd()): void {
+    const dbPath = join(projectPath, '.primordyn', 'context.db');
+    for (const file of [dbPath, `${dbPath}-wal`, `${dbPath}-shm`, `${dbPath}-journal`]) {
+      rmSync(file, { force: true });
+    }
   }
 
   public getDatabase(): Database.Database {
@@ -148,6 +171,13 @@ export class PrimordynDB {
     return this.dbPath;
   }
 
+  /**
+   * The project this index belongs to
+   */
+  public getProjectRoot(): string {
+    return this.projectRoot;
+  }
+
   public async getDatabaseInfo(): Promise<DatabaseInfo> {
     const fileCount = (this.db.prepare('SELECT COUNT(*) as count FROM files').get() as { count: number }).count;
     const symbolCount = (this.db.prepare('SELECT COUNT(*) as count FROM symbols').get() as { count: number }).count;
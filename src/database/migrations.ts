@@ -0,0 +1,469 @@
+import type Database from 'better-sqlite3';
+
+export interface Migration {
+  version: number;
+  description: string;
+  up: (db: Database.Database) => void;
+}
+
+/**
+ * Schema migrations, applied in order. Never edit a released migration -
+ * append a new one instead. Statements should tolerate databases that were
+ * partially upgraded by builds predating `schema_version`.
+ */
+export const MIGRATIONS: Migration[] = [
+  {
+    version: 1,
+    description: 'Initial schema',
+    up: (db) => {
+      db.exec(`
+        CREATE TABLE IF NOT EXISTS files (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          path TEXT UNIQUE NOT NULL,
+          relative_path TEXT NOT NULL,
+          content TEXT NOT NULL,
+          hash TEXT NOT NULL,
+          size INTEGER NOT NULL,
+          language TEXT,
+          last_modified TEXT NOT NULL,
+          indexed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+          metadata TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS symbols (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          file_id INTEGER NOT NULL,
+          name TEXT NOT NULL,
+          type TEXT NOT NULL,
+          line_start INTEGER NOT NULL,
+          line_end INTEGER NOT NULL,
+          signature TEXT,
+          documentation TEXT,
+          metadata TEXT,
+          FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS context_cache (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          query_hash TEXT UNIQUE NOT NULL,
+          result TEXT NOT NULL,
+          created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+          expires_at TIMESTAMP NOT NULL
+        );
+
+        -- Call relationships table for dependency graph
+        CREATE TABLE IF NOT EXISTS call_graph (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          caller_symbol_id INTEGER,
+          caller_file_id INTEGER,
+          callee_name TEXT NOT NULL,
+          callee_symbol_id INTEGER,
+          callee_file_id INTEGER,
+          call_type TEXT NOT NULL, -- 'function', 'method', 'constructor', 'import'
+          line_number INTEGER NOT NULL,
+          column_number INTEGER,
+          FOREIGN KEY (caller_symbol_id) REFERENCES symbols (id) ON DELETE CASCADE,
+          FOREIGN KEY (caller_file_id) REFERENCES files (id) ON DELETE CASCADE,
+          FOREIGN KEY (callee_symbol_id) REFERENCES symbols (id) ON DELETE SET NULL,
+          FOREIGN KEY (callee_file_id) REFERENCES files (id) ON DELETE SET NULL
+        );
+
+        -- Create indexes for better performance
+        CREATE INDEX IF NOT EXISTS idx_files_path ON files(path);
+        CREATE INDEX IF NOT EXISTS idx_files_relative_path ON files(relative_path);
+        CREATE INDEX IF NOT EXISTS idx_files_language ON files(language);
+        CREATE INDEX IF NOT EXISTS idx_files_hash ON files(hash);
+        CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols(name);
+        CREATE INDEX IF NOT EXISTS idx_symbols_type ON symbols(type);
+        CREATE INDEX IF NOT EXISTS idx_symbols_file_id ON symbols(file_id);
+        CREATE INDEX IF NOT EXISTS idx_context_cache_query_hash ON context_cache(query_hash);
+        CREATE INDEX IF NOT EXISTS idx_context_cache_expires_at ON context_cache(expires_at);
+      
+        -- Indexes for call graph
+        CREATE INDEX IF NOT EXISTS idx_call_graph_caller_symbol ON call_graph(caller_symbol_id);
+        CREATE INDEX IF NOT EXISTS idx_call_graph_callee_symbol ON call_graph(callee_symbol_id);
+        CREATE INDEX IF NOT EXISTS idx_call_graph_callee_name ON call_graph(callee_name);
+        CREATE INDEX IF NOT EXISTS idx_call_graph_caller_file ON call_graph(caller_file_id);
+        CREATE INDEX IF NOT EXISTS idx_call_graph_callee_file ON call_graph(callee_file_id);
+
+        -- Full-text search indexes
+        CREATE VIRTUAL TABLE IF NOT EXISTS files_fts USING fts5(
+          relative_path, content, language,
+          content='files',
+          content_rowid='id'
+        );
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS symbols_fts USING fts5(
+          name, signature, documentation,
+          content='symbols', 
+          content_rowid='id'
+        );
+
+        -- Triggers to keep FTS in sync
+        CREATE TRIGGER IF NOT EXISTS files_fts_insert AFTER INSERT ON files BEGIN
+          INSERT INTO files_fts(rowid, relative_path, content, language) 
+          VALUES (new.id, new.relative_path, new.content, new.language);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS files_fts_delete AFTER DELETE ON files BEGIN
+          DELETE FROM files_fts WHERE rowid = old.id;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS files_fts_update AFTER UPDATE ON files BEGIN
+          DELETE FROM files_fts WHERE rowid = old.id;
+          INSERT INTO files_fts(rowid, relative_path, content, language) 
+          VALUES (new.id, new.relative_path, new.content, new.language);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS symbols_fts_insert AFTER INSERT ON symbols BEGIN
+          INSERT INTO symbols_fts(rowid, name, signature, documentation) 
+          VALUES (new.id, new.name, new.signature, new.documentation);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS symbols_fts_delete AFTER DELETE ON symbols BEGIN
+          DELETE FROM symbols_fts WHERE rowid = old.id;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS symbols_fts_update AFTER UPDATE ON symbols BEGIN
+          DELETE FROM symbols_fts WHERE rowid = old.id;
+          INSERT INTO symbols_fts(rowid, name, signature, documentation) 
+          VALUES (new.id, new.name, new.signature, new.documentation);
+        END;
+      `);
+    }
+  },
+  {
+    version: 2,
+    description: 'Cargo crates',
+    up: (db) => {
+      db.exec(`
+        CREATE TABLE IF NOT EXISTS crates (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          name TEXT NOT NULL,
+          lib_name TEXT NOT NULL,
+          manifest_path TEXT UNIQUE NOT NULL,
+          root_path TEXT NOT NULL,
+          version TEXT,
+          workspace_member INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS crate_dependencies (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          crate_id INTEGER NOT NULL,
+          name TEXT NOT NULL,
+          package TEXT NOT NULL,
+          kind TEXT NOT NULL, -- 'normal', 'dev', 'build'
+          path TEXT,
+          workspace INTEGER NOT NULL DEFAULT 0,
+          dependency_crate_id INTEGER,
+          FOREIGN KEY (crate_id) REFERENCES crates (id) ON DELETE CASCADE,
+          FOREIGN KEY (dependency_crate_id) REFERENCES crates (id) ON DELETE SET NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_crates_name ON crates(name);
+        CREATE INDEX IF NOT EXISTS idx_crate_dependencies_crate ON crate_dependencies(crate_id);
+      `);
+
+      addColumn(db, 'files', 'crate_id', 'INTEGER REFERENCES crates (id) ON DELETE SET NULL');
+      db.exec(`
+        CREATE INDEX IF NOT EXISTS idx_files_crate ON files(crate_id);
+
+        -- Only re-index when searchable columns change (not e.g. crate_id)
+        DROP TRIGGER IF EXISTS files_fts_update;
+        CREATE TRIGGER files_fts_update AFTER UPDATE OF relative_path, content, language ON files BEGIN
+          DELETE FROM files_fts WHERE rowid = old.id;
+          INSERT INTO files_fts(rowid, relative_path, content, language) 
+          VALUES (new.id, new.relative_path, new.content, new.language);
+        END;
+      `);
+    }
+  },
+  {
+    version: 3,
+    description: 'Call resolution confidence',
+    up: (db) => {
+      // 1.0 for a unique, well-supported match; lower when ambiguous
+      addColumn(db, 'call_graph', 'resolution_confidence', 'REAL');
+    }
+  },
+  {
+    version: 4,
+    description: 'Import and export tables',
+    up: (db) => {
+      db.exec(`
+        CREATE TABLE IF NOT EXISTS imports (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          file_id INTEGER NOT NULL,
+          specifier TEXT NOT NULL, -- Module as written in the source
+          names TEXT,              -- JSON array of imported names ('*' for namespace/glob imports)
+          line INTEGER,
+          reexport INTEGER NOT NULL DEFAULT 0,
+          target_file_id INTEGER,  -- Resolved module; NULL when external or unresolved
+          FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+          FOREIGN KEY (target_file_id) REFERENCES files (id) ON DELETE SET NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS exports (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          file_id INTEGER NOT NULL,
+          name TEXT NOT NULL,
+          specifier TEXT,          -- Source module for re-exports
+          target_file_id INTEGER,
+          FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+          FOREIGN KEY (target_file_id) REFERENCES files (id) ON DELETE SET NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_imports_file ON imports(file_id);
+        CREATE INDEX IF NOT EXISTS idx_imports_target ON imports(target_file_id);
+        CREATE INDEX IF NOT EXISTS idx_exports_file ON exports(file_id);
+        CREATE INDEX IF NOT EXISTS idx_exports_name ON exports(name);
+
+        -- Carry over imports/exports previously kept in files.metadata; targets
+        -- are resolved on the next index run
+        INSERT INTO imports (file_id, specifier)
+        SELECT f.id, j.value FROM files f, json_each(f.metadata, '$.imports') j
+        WHERE json_valid(f.metadata) AND json_type(f.metadata, '$.imports') = 'array';
+
+        INSERT INTO exports (file_id, name)
+        SELECT f.id, j.value FROM files f, json_each(f.metadata, '$.exports') j
+        WHERE json_valid(f.metadata) AND json_type(f.metadata, '$.exports') = 'array';
+
+        UPDATE files SET metadata = json_remove(metadata, '$.imports', '$.exports')
+        WHERE json_valid(metadata);
+      `);
+    }
+  },
+  {
+    version: 5,
+    description: 'Symbol qualified names',
+    up: (db) => {
+      // e.g. `pkg.mod.Class.method`; filled in when imports are resolved
+      addColumn(db, 'symbols', 'qualified_name', 'TEXT');
+      db.exec('CREATE INDEX IF NOT EXISTS idx_symbols_qualified_name ON symbols(qualified_name)');
+    }
+  },
+  {
+    version: 6,
+    description: 'Symbol hierarchy',
+    up: (db) => {
+      addColumn(db, 'symbols', 'parent_symbol_id', 'INTEGER REFERENCES symbols (id) ON DELETE SET NULL');
+      db.exec(`
+        CREATE INDEX IF NOT EXISTS idx_symbols_parent ON symbols(parent_symbol_id);
+
+        -- Hierarchy comes from the extractors; clear hashes so the next
+        -- index run re-extracts every file instead of skipping it as unchanged
+        UPDATE files SET hash = '', last_modified = '';
+      `);
+    }
+  },
+  {
+    version: 7,
+    description: 'Type relations table',
+    up: (db) => {
+      db.exec(`
+        CREATE TABLE IF NOT EXISTS type_relations (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          file_id INTEGER NOT NULL,
+          source_symbol_id INTEGER,
+          source_name TEXT NOT NULL,
+          target_name TEXT NOT NULL,      -- As written, e.g. \`base.Model\` or \`fmt::Display\`
+          target_short_name TEXT NOT NULL,
+          target_symbol_id INTEGER,       -- Resolved by the link phase
+          kind TEXT NOT NULL,             -- extends, implements, embeds
+          line INTEGER,
+          FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+          FOREIGN KEY (source_symbol_id) REFERENCES symbols (id) ON DELETE CASCADE,
+          FOREIGN KEY (target_symbol_id) REFERENCES symbols (id) ON DELETE SET NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_type_relations_file ON type_relations(file_id);
+        CREATE INDEX IF NOT EXISTS idx_type_relations_source ON type_relations(source_symbol_id);
+        CREATE INDEX IF NOT EXISTS idx_type_relations_source_name ON type_relations(source_name);
+        CREATE INDEX IF NOT EXISTS idx_type_relations_target ON type_relations(target_symbol_id);
+        CREATE INDEX IF NOT EXISTS idx_type_relations_target_name ON type_relations(target_short_name);
+
+        -- Carry over relationships previously kept in files.metadata; the
+        -- short name is whatever follows the last \`.\` or \`::\`
+        INSERT INTO type_relations (file_id, source_name, target_name, target_short_name, kind, line)
+        SELECT f.id,
+               json_extract(j.value, '$.source'),
+               json_extract(j.value, '$.target'),
+               replace(
+                 replace(json_extract(j.value, '$.target'), '::', '.'),
+                 rtrim(
+                   replace(json_extract(j.value, '$.target'), '::', '.'),
+                   replace(replace(json_extract(j.value, '$.target'), '::', '.'), '.', '')
+                 ),
+                 ''
+               ),
+               json_extract(j.value, '$.kind'),
+               json_extract(j.value, '$.line')
+        FROM files f, json_each(f.metadata, '$.relationships') j
+        WHERE json_valid(f.metadata) AND json_type(f.metadata, '$.relationships') = 'array'
+          AND json_extract(j.value, '$.source') IS NOT NULL
+          AND json_extract(j.value, '$.target') IS NOT NULL;
+
+        UPDATE type_relations SET source_symbol_id = (
+          SELECT s.id FROM symbols s
+          WHERE s.file_id = type_relations.file_id
+            AND (s.name = type_relations.source_name OR s.qualified_name = type_relations.source_name)
+          ORDER BY s.line_start LIMIT 1
+        );
+
+        UPDATE files SET metadata = json_remove(metadata, '$.relationships')
+        WHERE json_valid(metadata);
+      `);
+    }
+  },
+  {
+    version: 8,
+    description: 'Symbol references table',
+    up: (db) => {
+      // `references` is an SQL keyword, hence the prefix
+      db.exec(`
+        CREATE TABLE IF NOT EXISTS symbol_references (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          file_id INTEGER NOT NULL,
+          symbol_id INTEGER,              -- Innermost symbol containing the reference
+          name TEXT NOT NULL,             -- As written, e.g. \`config.port\` or \`models::User\`
+          short_name TEXT NOT NULL,       -- Last path segment
+          kind TEXT NOT NULL,             -- call, type, read, write, import, export
+          line INTEGER NOT NULL,
+          column_number INTEGER,
+          FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+          FOREIGN KEY (symbol_id) REFERENCES symbols (id) ON DELETE SET NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_symbol_references_file ON symbol_references(file_id);
+        CREATE INDEX IF NOT EXISTS idx_symbol_references_short_name ON symbol_references(short_name);
+        CREATE INDEX IF NOT EXISTS idx_symbol_references_symbol ON symbol_references(symbol_id);
+
+        -- References come from the extractors; re-extract every file on the next run
+        UPDATE files SET hash = '', last_modified = '';
+      `);
+    }
+  },
+  {
+    version: 9,
+    description: 'Call scopes',
+    up: (db) => {
+      // local, project or external; only project calls are linked
+      addColumn(db, 'call_graph', 'scope', 'TEXT');
+      // Module the callee's first name is imported from
+      addColumn(db, 'call_graph', 'import_source', 'TEXT');
+      db.exec(`
+        -- Scopes come from the extractors; re-extract every file on the next run
+        UPDATE files SET hash = '', last_modified = '';
+      `);
+    }
+  },
+  {
+    version: 10,
+    description: 'Embeddings table',
+    up: (db) => {
+      db.exec(`
+        CREATE TABLE IF NOT EXISTS embeddings (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          file_id INTEGER NOT NULL,
+          symbol_id INTEGER,              -- NULL for line windows of files without symbols
+          line_start INTEGER NOT NULL,
+          line_end INTEGER NOT NULL,
+          provider TEXT NOT NULL,         -- Provider and model, e.g. hashing-512 or ollama:nomic-embed-text
+          dimensions INTEGER NOT NULL,
+          vector BLOB NOT NULL,           -- Float32 values
+          FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+          FOREIGN KEY (symbol_id) REFERENCES symbols (id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_embeddings_file ON embeddings(file_id);
+        CREATE INDEX IF NOT EXISTS idx_embeddings_symbol ON embeddings(symbol_id);
+      `);
+    }
+  },
+  {
+    version: 11,
+    description: 'Identifier-aware full-text indexes',
+    up: (db) => {
+      // Words of compound identifiers (`getUserById` -> `get user by id`),
+      // computed by the indexer
+      addColumn(db, 'files', 'identifier_parts', 'TEXT');
+      addColumn(db, 'symbols', 'identifier_parts', 'TEXT');
+      db.exec(`
+        DROP TRIGGER IF EXISTS files_fts_insert;
+        DROP TRIGGER IF EXISTS files_fts_delete;
+        DROP TRIGGER IF EXISTS files_fts_update;
+        DROP TRIGGER IF EXISTS symbols_fts_insert;
+        DROP TRIGGER IF EXISTS symbols_fts_delete;
+        DROP TRIGGER IF EXISTS symbols_fts_update;
+        DROP TABLE IF EXISTS files_fts;
+        DROP TABLE IF EXISTS symbols_fts;
+
+        -- '_' is part of a token, so snake_case names stay whole next to their parts
+        CREATE VIRTUAL TABLE files_fts USING fts5(
+          relative_path, content, language, identifier_parts,
+          content='files',
+          content_rowid='id',
+          tokenize="unicode61 tokenchars '_'"
+        );
+
+        CREATE VIRTUAL TABLE symbols_fts USING fts5(
+          name, signature, documentation, identifier_parts,
+          content='symbols',
+          content_rowid='id',
+          tokenize="unicode61 tokenchars '_'"
+        );
+
+        -- External-content tables are told the old values on delete, since
+        -- the row has already changed by the time an update trigger runs
+        CREATE TRIGGER files_fts_insert AFTER INSERT ON files BEGIN
+          INSERT INTO files_fts(rowid, relative_path, content, language, identifier_parts)
+          VALUES (new.id, new.relative_path, new.content, new.language, new.identifier_parts);
+        END;
+
+        CREATE TRIGGER files_fts_delete AFTER DELETE ON files BEGIN
+          INSERT INTO files_fts(files_fts, rowid, relative_path, content, language, identifier_parts)
+          VALUES ('delete', old.id, old.relative_path, old.content, old.language, old.identifier_parts);
+        END;
+
+        CREATE TRIGGER files_fts_update AFTER UPDATE OF relative_path, content, language, identifier_parts ON files BEGIN
+          INSERT INTO files_fts(files_fts, rowid, relative_path, content, language, identifier_parts)
+          VALUES ('delete', old.id, old.relative_path, old.content, old.language, old.identifier_parts);
+          INSERT INTO files_fts(rowid, relative_path, content, language, identifier_parts)
+          VALUES (new.id, new.relative_path, new.content, new.language, new.identifier_parts);
+        END;
+
+        CREATE TRIGGER symbols_fts_insert AFTER INSERT ON symbols BEGIN
+          INSERT INTO symbols_fts(rowid, name, signature, documentation, identifier_parts)
+          VALUES (new.id, new.name, new.signature, new.documentation, new.identifier_parts);
+        END;
+
+        CREATE TRIGGER symbols_fts_delete AFTER DELETE ON symbols BEGIN
+          INSERT INTO symbols_fts(symbols_fts, rowid, name, signature, documentation, identifier_parts)
+          VALUES ('delete', old.id, old.name, old.signature, old.documentation, old.identifier_parts);
+        END;
+
+        CREATE TRIGGER symbols_fts_update AFTER UPDATE OF name, signature, documentation, identifier_parts ON symbols BEGIN
+          INSERT INTO symbols_fts(symbols_fts, rowid, name, signature, documentation, identifier_parts)
+          VALUES ('delete', old.id, old.name, old.signature, old.documentation, old.identifier_parts);
+          INSERT INTO symbols_fts(rowid, name, signature, documentation, identifier_parts)
+          VALUES (new.id, new.name, new.signature, new.documentation, new.identifier_parts);
+        END;
+
+        -- Index what is already stored; parts are filled in when every file
+        -- is re-extracted on the next run
+        INSERT INTO files_fts(files_fts) VALUES ('rebuild');
+        INSERT INTO symbols_fts(symbols_fts) VALUES ('rebuild');
+        UPDATE files SET hash = '', last_modified = '';
+      `);
+    }
+  }
+];
+
+export const LATEST_SCHEMA_VERSION = MIGRATIONS[MIGRATIONS.length - 1].version;
+
+function addColumn(db: Database.Database, table: string, column: string, definition: string): void {
+  const columns = db.prepare(`PRAGMA table_info(${table})`).all() as { name: string }[];
+  if (!columns.some(c => c.name === column)) {
+    db.exec(`ALTER TABLE ${table} ADD COLUMN ${column} ${definition}`);
+  }
+}
@@ -0,0 +1,75 @@
+import { HashingEmbeddingProvider, EmbeddingIndex } from '../index.js';
+import { Indexer } from '../../indexer/index.js';
+import { PrimordynDB } from '../../database/index.js';
+import { ContextRetriever } from '../../retriever/index.js';
+import { mkdirSync, writeFileSync, rmSync, existsSync } from 'fs';
+import { join } from 'path';
+
+const cosine = (a: Float32Array, b: Float32Array) => a.reduce((sum, value, i) => sum + value * b[i], 0);
+
+describe('HashingEmbeddingProvider', () => {
+  test('should be deterministic and match related word forms', async () => {
+    const provider = new HashingEmbeddingProvider();
+    const [query, identifier, unrelated, again] = await provider.embed([
+      'retry failed payments',
+      'retryFailedPayment',
+      'renderUserAvatar',
+      'retry failed payments'
+    ]);
+
+    expect(Array.from(again)).toEqual(Array.from(query));
+    expect(cosine(query, identifier)).toBeGreaterThan(0.8);
+    expect(cosine(query, unrelated)).toBeLessThan(0.2);
+  });
+});
+
+describe('semantic search', () => {
+  const testDir = join(process.cwd(), '.test-embeddings');
+  let db: PrimordynDB;
+
+  beforeEach(async () => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(testDir, { recursive: true });
+    writeFileSync(join(testDir, 'billing.ts'), [
+      '/** Schedule another attempt for invoices whose charge was declined */',
+      'export function scheduleChargeRetries(invoice: { id: string }) {',
+      '  return { invoice: invoice.id, attempts: 3 };',
+      '}',
+      ''
+    ].join('\n'));
+    writeFileSync(join(testDir, 'avatar.ts'), 'export function renderAvatar(url: string) { return `<img src="${url}">`; }\n');
+
+    db = new PrimordynDB(testDir);
+    await new Indexer(db).index({ projectRoot: testDir, verbose: false, embeddings: true });
+  });
+
+  afterEach(() => {
+    db.close();
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should only embed files changed since the last update', async () => {
+    const index = new EmbeddingIndex(db, new HashingEmbeddingProvider());
+    expect(await index.update()).toBe(0);
+
+    const [best] = await index.search('charge retries');
+    const symbol = db.getDatabase().prepare('SELECT name FROM symbols WHERE id = ?').get(best.symbolId) as { name: string };
+    expect(symbol.name).toBe('scheduleChargeRetries');
+  });
+
+  test('should find code by meaning and blend similarity into the ranking', async () => {
+    const retriever = new ContextRetriever(db);
+
+    const lexical = await retriever.query('where do we retry failed payments');
+    expect(lexical.symbols).toEqual([]);
+
+    const semantic = await retriever.query('where do we retry failed payments', { semantic: true });
+    expect(semantic.symbols[0].name).toBe('scheduleChargeRetries');
+    expect(semantic.symbols[0].score?.semantic).toBeGreaterThan(0);
+    expect(semantic.symbols.findIndex(symbol => symbol.name === 'renderAvatar')).not.toBe(0);
+  });
+});
@@ -0,0 +1,161 @@
+import { PrimordynDB } from '../database/index.js';
+import { createEmbeddingProvider } from './providers.js';
+import type { EmbeddingProvider } from './providers.js';
+
+export { createEmbeddingProvider, HashingEmbeddingProvider, OllamaEmbeddingProvider } from './providers.js';
+export type { EmbeddingProvider } from './providers.js';
+
+// Lines of a symbol's body included in its chunk
+const MAX_CHUNK_LINES = 60;
+// Window size for files without symbols (docs, config)
+const FILE_CHUNK_LINES = 40;
+const MAX_FILE_CHUNKS = 20;
+const EMBED_BATCH_SIZE = 64;
+
+export interface SemanticMatch {
+  fileId: number;
+  symbolId: number | null;  // NULL for chunks of files without symbols
+  lineStart: number;
+  lineEnd: number;
+  similarity: number;       // Cosine similarity, -1 to 1
+}
+
+interface Chunk {
+  fileId: number;
+  symbolId: number | null;
+  lineStart: number;
+  lineEnd: number;
+  text: string;
+}
+
+/**
+ * Vector index over symbols, and over line windows of files that have no
+ * symbols. Vectors live in the `embeddings` table as Float32 blobs, tagged
+ * with the provider that produced them; search is a brute-force cosine scan.
+ *
+ * Re-indexing a file deletes its vectors, so `update()` only embeds files
+ * that changed since the last run (or everything, after switching provider).
+ */
+export class EmbeddingIndex {
+  private db: PrimordynDB;
+  private provider: EmbeddingProvider;
+
+  constructor(db: PrimordynDB, provider: EmbeddingProvider = createEmbeddingProvider()) {
+    this.db = db;
+    this.provider = provider;
+  }
+
+  /**
+   * Embed every file without vectors from this provider. Returns the number of chunks embedded.
+   */
+  public async update(): Promise<number> {
+    const database = this.db.getDatabase();
+    database.prepare('DELETE FROM embeddings WHERE provider != ?').run(this.provider.id);
+
+    const files = database.prepare(`
+      SELECT f.id, f.content
+      FROM files f
+      WHERE NOT EXISTS (SELECT 1 FROM embeddings e WHERE e.file_id = f.id)
+    `).all() as Array<{ id: number; content: string }>;
+
+    const chunks = files.flatMap(file => this.chunkFile(file.id, file.content));
+    const insert = database.prepare(`
+      INSERT INTO embeddings (file_id, symbol_id, line_start, line_end, provider, dimensions, vector)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `);
+
+    for (let i = 0; i < chunks.length; i += EMBED_BATCH_SIZE) {
+      const batch = chunks.slice(i, i + EMBED_BATCH_SIZE);
+      const vectors = await this.provider.embed(batch.map(chunk => chunk.text));
+      database.transaction(() => {
+        batch.forEach((chunk, index) => {
+          const vector = vectors[index];
+          insert.run(
+            chunk.fileId,
+            chunk.symbolId,
+            chunk.lineStart,
+            chunk.lineEnd,
+            this.provider.id,
+            vector.length,
+            Buffer.from(vector.buffer, vector.byteOffset, vector.byteLength)
+          );
+        });
+      })();
+    }
+
+    return chunks.length;
+  }
+
+  /**
+   * The chunks most similar to `text`, best first
+   */
+  public async search(text: string, limit: number = 30): Promise<SemanticMatch[]> {
+    const [query] = await this.provider.embed([text]);
+    const rows = this.db.getDatabase().prepare(`
+      SELECT file_id, symbol_id, line_start, line_end, vector
+      FROM embeddings
+      WHERE provider = ? AND dimensions = ?
+    `).all(this.provider.id, query.length) as Array<{
+      file_id: number; symbol_id: number | null; line_start: number; line_end: number; vector: Buffer
+    }>;
+
+    return rows
+      .map(row => {
+        // Copy: blobs are not guaranteed to be 4-byte aligned
+        const vector = new Float32Array(new Uint8Array(row.vector).buffer);
+        let similarity = 0;
+        for (let i = 0; i < query.length; i++) similarity += query[i] * vector[i];
+        return {
+          fileId: row.file_id,
+          symbolId: row.symbol_id,
+          lineStart: row.line_start,
+          lineEnd: row.line_end,
+          similarity
+        };
+      })
+      .sort((a, b) => b.similarity - a.similarity)
+      .slice(0, limit);
+  }
+
+  private chunkFile(fileId: number, content: string): Chunk[] {
+    const lines = content.split('\n');
+    const symbols = this.db.getDatabase().prepare(`
+      SELECT id, name, qualified_name, type, line_start, line_end, signature, documentation
+      FROM symbols
+      WHERE file_id = ?
+      ORDER BY line_start
+    `).all(fileId) as Array<{
+      id: number; name: string; qualified_name: string | null; type: string;
+      line_start: number; line_end: number; signature: string | null; documentation: string | null
+    }>;
+
+    if (symbols.length > 0) {
+      return symbols.map(symbol => ({
+        fileId,
+        symbolId: symbol.id,
+        lineStart: symbol.line_start,
+        lineEnd: symbol.line_end,
+        text: [
+          `${symbol.qualified_name || symbol.name} (${symbol.type})`,
+          symbol.signature || '',
+          symbol.documentation || '',
+          lines.slice(symbol.line_start - 1, Math.min(symbol.line_end, symbol.line_start - 1 + MAX_CHUNK_LINES)).join('\n')
+        ].filter(Boolean).join('\n')
+      }));
+    }
+
+    const chunks: Chunk[] = [];
+    for (let start = 0; start < lines.length && chunks.length < MAX_FILE_CHUNKS; start += FILE_CHUNK_LINES) {
+      const text = lines.slice(start, start + FILE_CHUNK_LINES).join('\n');
+      if (!text.trim()) continue;
+      chunks.push({
+        fileId,
+        symbolId: null,
+        lineStart: start + 1,
+        lineEnd: Math.min(lines.length, start + FILE_CHUNK_LINES),
+        text
+      });
+    }
+    return chunks;
+  }
+}
@@ -0,0 +1,152 @@
+/**
+ * Turns text into vectors for semantic search. `id` names the provider and
+ * model; vectors from different providers are never compared.
+ */
+export interface EmbeddingProvider {
+  readonly id: string;
+  embed(texts: string[]): Promise<Float32Array[]>;
+}
+
+// Words too common in prose and code to say anything about a chunk
+const STOP_WORDS = new Set([
+  'a', 'an', 'and', 'are', 'as', 'at', 'be', 'by', 'do', 'does', 'for', 'from', 'how', 'if', 'in',
+  'is', 'it', 'of', 'on', 'or', 'that', 'the', 'this', 'to', 'we', 'what', 'when', 'where', 'which',
+  'with', 'const', 'let', 'var', 'def', 'fn', 'function', 'return', 'self', 'new', 'import', 'export',
+  'pub', 'use', 'class', 'void', 'null', 'none', 'true', 'false', 'async', 'await'
+]);
+
+/**
+ * Split identifiers and prose into lower-case words:
+ * `retryFailedPayments` and `retry_failed_payments` both give
+ * `retry failed payments`
+ */
+export function splitWords(text: string): string[] {
+  return text
+    .replace(/([a-z0-9])([A-Z])/g, '$1 $2')
+    .replace(/([A-Z]+)([A-Z][a-z])/g, '$1 $2')
+    .toLowerCase()
+    .split(/[^a-z0-9]+/)
+    .filter(word => word.length > 1 && !/^\d+$/.test(word));
+}
+
+/**
+ * Strip common English suffixes so `retries`, `retried` and `retrying`
+ * land on the same feature
+ */
+function stem(word: string): string {
+  if (word.length <= 4) return word;
+  return word
+    .replace(/ies$/, 'y')
+    .replace(/ied$/, 'y')
+    .replace(/(ing|ed|es|s)$/, '')
+    .replace(/(.)\1$/, '$1');
+}
+
+// FNV-1a
+function hash(text: string): number {
+  let h = 0x811c9dc5;
+  for (let i = 0; i < text.length; i++) {
+    h ^= text.charCodeAt(i);
+    h = Math.imul(h, 0x01000193);
+  }
+  return h >>> 0;
+}
+
+export function normalize(vector: Float32Array): Float32Array {
+  let norm = 0;
+  for (const value of vector) norm += value * value;
+  norm = Math.sqrt(norm);
+  if (norm > 0) {
+    for (let i = 0; i < vector.length; i++) vector[i] /= norm;
+  }
+  return vector;
+}
+
+/**
+ * Deterministic, offline stand-in for a real model: stemmed words and their
+ * character trigrams are hashed into a fixed number of signed buckets.
+ * Matches related word forms and identifiers split differently, but knows
+ * nothing about synonyms.
+ */
+export class HashingEmbeddingProvider implements EmbeddingProvider {
+  readonly id: string;
+  private dimensions: number;
+
+  constructor(dimensions: number = 512) {
+    this.dimensions = dimensions;
+    this.id = `hashing-${dimensions}`;
+  }
+
+  async embed(texts: string[]): Promise<Float32Array[]> {
+    return texts.map(text => this.embedOne(text));
+  }
+
+  private embedOne(text: string): Float32Array {
+    const vector = new Float32Array(this.dimensions);
+    const add = (feature: string, weight: number) => {
+      const h = hash(feature);
+      vector[h % this.dimensions] += (h & 0x80000000) ? -weight : weight;
+    };
+
+    for (const word of splitWords(text)) {
+      if (STOP_WORDS.has(word)) continue;
+      const stemmed = stem(word);
+      add(stemmed, 1);
+      const padded = `^${stemmed}$`;
+      for (let i = 0; i + 3 <= padded.length; i++) {
+        add(`#${padded.substring(i, i + 3)}`, 0.3);
+      }
+    }
+
+    return normalize(vector);
+  }
+}
+
+/**
+ * Embeddings from a model served by a local Ollama instance
+ * (`ollama pull nomic-embed-text`), running on the CPU if there is no GPU.
+ */
+export class OllamaEmbeddingProvider implements EmbeddingProvider {
+  readonly id: string;
+  private model: string;
+  private host: string;
+
+  constructor(model: string, host: string = process.env.OLLAMA_HOST || 'http://127.0.0.1:11434') {
+    this.model = model;
+    this.host = host.replace(/\/$/, '');
+    this.id = `ollama:${model}`;
+  }
+
+  async embed(texts: string[]): Promise<Float32Array[]> {
+    if (texts.length === 0) return [];
+
+    const response = await fetch(`${this.host}/api/embed`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ model: this.model, input: texts })
+    });
+    if (!response.ok) {
+      throw new Error(`Ollama embedding request failed: ${response.status} ${response.statusText}`);
+    }
+
+    const body = await response.json() as { embeddings?: number[][] };
+    if (!body.embeddings || body.embeddings.length !== texts.length) {
+      throw new Error('Ollama returned no embeddings');
+    }
+    return body.embeddings.map(values => normalize(Float32Array.from(values)));
+  }
+}
+
+/**
+ * Provider named by `spec`, or by `$PRIMORDYN_EMBEDDINGS`:
+ * `hashing` (default) or `ollama:<model>`
+ */
+export function createEmbeddingProvider(spec: string = process.env.PRIMORDYN_EMBEDDINGS || 'hashing'): EmbeddingProvider {
+  if (spec === 'hashing') {
+    return new HashingEmbeddingProvider();
+  }
+  if (spec.startsWith('ollama:') && spec.length > 'ollama:'.length) {
+    return new OllamaEmbeddingProvider(spec.substring('ollama:'.length));
+  }
+  throw new Error(`Unknown embedding provider "${spec}". Use "hashing" or "ollama:<model>".`);
+}
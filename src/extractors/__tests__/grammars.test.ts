@@ -0,0 +1,73 @@
+import { GrammarLoader } from '../grammars.js';
+import { TreeSitterExtractor } from '../treesitter-extractor.js';
+import { mkdirSync, writeFileSync, rmSync, existsSync } from 'fs';
+import { join } from 'path';
+
+describe('GrammarLoader', () => {
+  const testDir = join(process.cwd(), '.test-grammars');
+  const envDir = join(testDir, 'env');
+  const projectRoot = join(testDir, 'project');
+  const projectGrammars = join(projectRoot, '.primordyn', 'grammars');
+  const savedEnv = process.env.PRIMORDYN_GRAMMARS_DIR;
+
+  beforeEach(() => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(envDir, { recursive: true });
+    mkdirSync(projectGrammars, { recursive: true });
+    delete process.env.PRIMORDYN_GRAMMARS_DIR;
+  });
+
+  afterEach(() => {
+    if (savedEnv === undefined) {
+      delete process.env.PRIMORDYN_GRAMMARS_DIR;
+    } else {
+      process.env.PRIMORDYN_GRAMMARS_DIR = savedEnv;
+    }
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should search the environment, the project, then the bundled grammars', () => {
+    process.env.PRIMORDYN_GRAMMARS_DIR = envDir;
+    const paths = GrammarLoader.getSearchPaths(projectRoot);
+
+    expect(paths.slice(0, 3)).toEqual([envDir, projectGrammars, join(process.cwd(), 'grammars')]);
+    expect(paths[3]).toMatch(/tree-sitter-wasms[/\\]out$/);
+  });
+
+  test('should prefer the first directory holding the grammar', () => {
+    writeFileSync(join(envDir, 'tree-sitter-test.wasm'), '');
+    writeFileSync(join(projectGrammars, 'tree-sitter-test.wasm'), '');
+
+    // Resolved against the project being indexed, not the working directory
+    expect(GrammarLoader.resolveGrammarPath('tree-sitter-test.wasm', projectRoot)).toBe(join(projectGrammars, 'tree-sitter-test.wasm'));
+    expect(GrammarLoader.hasGrammar('tree-sitter-test.wasm', process.cwd())).toBe(false);
+
+    process.env.PRIMORDYN_GRAMMARS_DIR = envDir;
+    expect(GrammarLoader.resolveGrammarPath('tree-sitter-test.wasm', projectRoot)).toBe(join(envDir, 'tree-sitter-test.wasm'));
+  });
+
+  test('should fall back to regex extraction when a grammar is missing or unusable', async () => {
+    // A project grammar shadows the bundled one; an unloadable file means no parser
+    writeFileSync(join(projectGrammars, 'tree-sitter-go.wasm'), 'not a grammar');
+    expect(await GrammarLoader.getParser('tree-sitter-go.wasm', projectRoot)).toBeNull();
+    expect(await GrammarLoader.getParser('tree-sitter-missing.wasm', projectRoot)).toBeNull();
+
+    const content = 'package main\n\nimport "fmt"\n\ntype Server struct {\n}\n\nfunc (s *Server) Start() {\n  fmt.Println("up")\n}\n';
+    const context = await new TreeSitterExtractor(projectRoot).extract({
+      path: join(projectRoot, 'main.go'),
+      relativePath: 'main.go',
+      content,
+      hash: 'test-hash',
+      size: content.length,
+      language: 'go',
+      lastModified: new Date()
+    });
+
+    expect(context.symbols.map(s => [s.name, s.type])).toEqual(expect.arrayContaining([['Start', 'function'], ['Server', 'class']]));
+    expect(context.imports).toEqual(['fmt']);
+  });
+});
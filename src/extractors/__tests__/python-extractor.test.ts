@@ -0,0 +1,107 @@
+import { PythonExtractor } from '../python-extractor.js';
+import { mkdirSync, writeFileSync, rmSync, existsSync } from 'fs';
+import { join } from 'path';
+import type { FileInfo } from '../../types/index.js';
+
+const source = [
+  'from .base import Base, Mixin as M',
+  '',
+  'TEMPLATE = "def fake(): pass"',
+  '',
+  '@register(name="svc", retries=3)',
+  'class Service(Base, M, metaclass=Meta):',
+  '    """Coordinates work.',
+  '',
+  '    Extra detail.',
+  '    """',
+  '    limit = 10',
+  '',
+  '    async def run(',
+  '        self,',
+  '        job: Job,',
+  '        *,',
+  '        timeout: float = 1.0,',
+  '    ) -> Result:',
+  '        """Run a job."""',
+  '        def retry(attempt):',
+  '            return self.queue.push(job)',
+  '        return retry(0)',
+  ''
+].join('\n');
+
+describe('PythonExtractor', () => {
+  const testDir = join(process.cwd(), '.test-python-extractor');
+
+  afterEach(() => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should parse multi-line signatures, decorators, docstrings and nested scopes', async () => {
+    const fileInfo: FileInfo = {
+      path: '/project/pkg/service.py',
+      relativePath: 'pkg/service.py',
+      content: source,
+      hash: 'test-hash',
+      size: source.length,
+      language: 'python',
+      lastModified: new Date()
+    };
+
+    const context = await new PythonExtractor('/project').extract(fileInfo);
+
+    const service = context.symbols.find(s => s.name === 'Service');
+    expect(service?.documentation).toBe('Coordinates work.\n\nExtra detail.');
+    expect(service?.metadata).toMatchObject({
+      bases: ['Base', 'M'],
+      keywords: { metaclass: 'Meta' },
+      decorators: ['@register(name="svc", retries=3)'],
+      methods: ['run'],
+      properties: ['limit']
+    });
+
+    const run = context.symbols.find(s => s.name === 'run');
+    expect(run).toMatchObject({ type: 'method', lineStart: 13, lineEnd: 22, documentation: 'Run a job.' });
+    expect(run?.metadata).toMatchObject({
+      async: true,
+      returnType: 'Result',
+      className: 'Service',
+      scope: 'Service',
+      parameters: [{ name: 'self' }, { name: 'job', type: 'Job' }, { name: 'timeout', type: 'float', default: '1.0' }]
+    });
+
+    const retry = context.symbols.find(s => s.name === 'retry');
+    expect(retry).toMatchObject({ type: 'function', metadata: expect.objectContaining({ scope: 'Service.run' }) });
+
+    // `def` inside a string is not a function
+    expect(context.symbols.some(s => s.name === 'fake')).toBe(false);
+
+    expect(context.calls.map(call => [call.calleeName, call.line])).toEqual([
+      ['register', 5],
+      ['self.queue.push', 21],
+      ['retry', 22]
+    ]);
+    expect(context.importBindings).toEqual([{ source: '.base', names: ['Base', 'Mixin'], line: 1 }]);
+  });
+
+  test('should extract simple functions without a usable grammar', async () => {
+    // A project grammar that fails to load leaves only the regex patterns
+    mkdirSync(join(testDir, '.primordyn', 'grammars'), { recursive: true });
+    writeFileSync(join(testDir, '.primordyn', 'grammars', 'tree-sitter-python.wasm'), 'not a grammar');
+    const content = 'def hello(name):\n    return name\n';
+    const fileInfo: FileInfo = {
+      path: join(testDir, 'pkg', 'simple.py'),
+      relativePath: 'pkg/simple.py',
+      content,
+      hash: 'test-hash',
+      size: content.length,
+      language: 'python',
+      lastModified: new Date()
+    };
+
+    const context = await new PythonExtractor(testDir).extract(fileInfo);
+
+    expect(context.symbols).toContainEqual(expect.objectContaining({ name: 'hello', type: 'function', lineStart: 1 }));
+  });
+});
@@ -0,0 +1,140 @@
+import { RustExtractor } from '../rust-extractor.js';
+import { readFileSync } from 'fs';
+import { join } from 'path';
+import type { FileInfo } from '../../types/index.js';
+
+describe('RustExtractor', () => {
+  const fixture = readFileSync(join(process.cwd(), 'test-files', 'lib.rs'), 'utf-8');
+  const libFile: FileInfo = {
+    path: '/project/src/lib.rs',
+    relativePath: 'src/lib.rs',
+    content: fixture,
+    hash: 'test-hash',
+    size: fixture.length,
+    language: 'rust',
+    lastModified: new Date()
+  };
+
+  test('should link impl methods to their owning type', async () => {
+    const context = await new RustExtractor().extract(libFile);
+
+    const method = context.symbols.find(s => s.name === 'Config::new');
+    expect(method?.type).toBe('method');
+    expect(method?.metadata?.owner).toBe('Config');
+    expect(method?.documentation).toBe('Creates a new configuration with default values');
+
+    const cacheInsert = context.symbols.find(s => s.name === 'Cache::insert');
+    expect(cacheInsert?.metadata?.owner).toBe('Cache');
+
+    // impl blocks are not reported as classes
+    expect(context.symbols.some(s => s.type === 'class')).toBe(false);
+  });
+
+  test('should record trait implementations', async () => {
+    const context = await new RustExtractor().extract(libFile);
+
+    expect(context.relationships).toContainEqual(expect.objectContaining({
+      source: 'StringProcessor',
+      target: 'DataProcessor',
+      kind: 'implements'
+    }));
+
+    const traitMethod = context.symbols.find(s => s.name === 'DataProcessor::process');
+    expect(traitMethod?.metadata?.required).toBe(true);
+
+    const implMethod = context.symbols.find(s => s.name === 'StringProcessor::process');
+    expect(implMethod?.metadata?.trait).toBe('DataProcessor');
+
+    const processor = context.symbols.find(s => s.name === 'StringProcessor' && s.type === 'struct');
+    expect(processor?.metadata?.traits).toEqual(['DataProcessor']);
+  });
+
+  test('should capture doc comments and inline modules', async () => {
+    const context = await new RustExtractor().extract(libFile);
+
+    const config = context.symbols.find(s => s.name === 'Config' && s.type === 'struct');
+    expect(config?.documentation).toBe('Represents a configuration for the application');
+    expect(config?.metadata?.attributes).toEqual(['derive(Debug, Clone)']);
+
+    const utils = context.symbols.find(s => s.name === 'utils' && s.type === 'module');
+    expect(utils?.metadata?.modulePath).toBe('crate::utils');
+
+    const toUppercase = context.symbols.find(s => s.name === 'to_uppercase');
+    expect(toUppercase?.type).toBe('function');
+    expect(toUppercase?.metadata?.module).toBe('crate::utils');
+
+    expect(context.exports).toEqual(expect.arrayContaining(['Config', 'Cache', 'DataProcessor']));
+    expect(context.exports).not.toContain('StringProcessor');
+  });
+
+  test('should build the module tree from mod declarations', async () => {
+    const content = [
+      '//! Network layer',
+      '',
+      'pub mod client;',
+      '#[path = "legacy_server.rs"]',
+      'mod server;',
+      'pub use client::{Client, Options as ClientOptions};'
+    ].join('\n');
+    const fileInfo: FileInfo = {
+      path: '/project/src/net/mod.rs',
+      relativePath: 'src/net/mod.rs',
+      content,
+      hash: 'test-hash',
+      size: content.length,
+      language: 'rust',
+      lastModified: new Date()
+    };
+
+    const context = await new RustExtractor().extract(fileInfo);
+
+    const fileModule = context.symbols.find(s => s.metadata?.file);
+    expect(fileModule?.metadata?.modulePath).toBe('crate::net');
+    expect(fileModule?.documentation).toBe('Network layer');
+
+    const client = context.symbols.find(s => s.name === 'client');
+    expect(client?.metadata?.modulePath).toBe('crate::net::client');
+    expect(client?.metadata?.candidates).toEqual(['src/net/client.rs', 'src/net/client/mod.rs']);
+
+    const server = context.symbols.find(s => s.name === 'server');
+    expect(server?.metadata?.candidates).toEqual(['src/net/legacy_server.rs']);
+
+    expect(context.imports).toEqual(['client::Client', 'client::Options']);
+    expect(context.exports).toEqual(expect.arrayContaining(['client', 'Client', 'ClientOptions']));
+  });
+
+  test('should ignore braces inside strings and comments', async () => {
+    const content = [
+      'fn outer() {',
+      '    let s = "}}}";',
+      '    // }',
+      '    helper();',
+      '}',
+      '',
+      'fn helper() {}'
+    ].join('\n');
+    const fileInfo: FileInfo = {
+      path: '/project/src/util.rs',
+      relativePath: 'src/util.rs',
+      content,
+      hash: 'test-hash',
+      size: content.length,
+      language: 'rust',
+      lastModified: new Date()
+    };
+
+    const context = await new RustExtractor().extract(fileInfo);
+
+    const outer = context.symbols.find(s => s.name === 'outer');
+    expect(outer?.lineEnd).toBe(5);
+    expect(context.calls).toContainEqual(expect.objectContaining({ calleeName: 'helper', line: 4 }));
+  });
+
+  test('should derive module paths from file locations', () => {
+    expect(RustExtractor.moduleForFile('src/lib.rs').modulePath).toBe('crate');
+    expect(RustExtractor.moduleForFile('src/db/mod.rs').modulePath).toBe('crate::db');
+    expect(RustExtractor.moduleForFile('src/db/pool.rs').modulePath).toBe('crate::db::pool');
+    expect(RustExtractor.moduleForFile('crates/core/src/main.rs').isCrateRoot).toBe(true);
+    expect(RustExtractor.moduleForFile('src/db.rs').childDir).toBe('src/db');
+  });
+});
@@ -1,6 +1,7 @@
 import { ILanguageExtractor } from './base.js';
 import { TypeScriptExtractor } from './typescript-extractor.js';
 import { PythonExtractor } from './python-extractor.js';
+import { RustExtractor } from './rust-extractor.js';
 import { TreeSitterExtractor } from './treesitter-extractor.js';
 import { RegexExtractor } from './regex-extractor.js';
 import type { FileInfo, ExtractedContext } from '../types/index.js';
@@ -12,17 +13,22 @@ export class ExtractorManager {
   private extractors: ILanguageExtractor[] = [];
   private languageMap: Map<string, ILanguageExtractor> = new Map();
   
-  constructor() {
-    this.registerExtractors();
+  /**
+   * `projectRoot` is the project being indexed; its `.primordyn/grammars`
+   * directory is searched for tree-sitter grammars
+   */
+  constructor(projectRoot: string) {
+    this.registerExtractors(projectRoot);
   }
   
-  private registerExtractors(): void {
+  private registerExtractors(projectRoot: string): void {
     // Register extractors in priority order
     const extractors = [
-      new TypeScriptExtractor(),  // Priority 10
-      new PythonExtractor(),       // Priority 10
-      new TreeSitterExtractor(),   // Priority 5
-      new RegexExtractor()         // Priority 1 (fallback)
+      new TypeScriptExtractor(),             // Priority 10
+      new PythonExtractor(projectRoot),      // Priority 10
+      new RustExtractor(),                   // Priority 10
+      new TreeSitterExtractor(projectRoot),  // Priority 5
+      new RegexExtractor()                   // Priority 1 (fallback)
     ];
     
     // Sort by priority (higher first)
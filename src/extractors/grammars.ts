@@ -0,0 +1,117 @@
+import { Parser, Language } from 'web-tree-sitter';
+import { existsSync } from 'fs';
+import { createRequire } from 'module';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+const require = createRequire(import.meta.url);
+
+/**
+ * Loads tree-sitter WASM grammars and hands out parsers for them.
+ *
+ * Grammars are looked up, in order, in:
+ * 1. the directory named by PRIMORDYN_GRAMMARS_DIR
+ * 2. `.primordyn/grammars` inside the project being indexed
+ * 3. the `grammars/` directory bundled with the package
+ * 4. the prebuilt grammars of the `tree-sitter-wasms` package
+ */
+export class GrammarLoader {
+  private static initialized: Promise<void> | null = null;
+  private static lookups: Map<string, Promise<Language | null>> = new Map(); // project + grammar -> language
+  private static languages: Map<string, Promise<Language | null>> = new Map(); // grammar path -> language
+  private static parsers: Map<Language, Parser> = new Map();
+
+  /**
+   * Get a parser for the given grammar file (e.g. `tree-sitter-rust.wasm`),
+   * or null when the grammar cannot be found or loaded.
+   */
+  public static async getParser(wasmFile: string, projectRoot: string): Promise<Parser | null> {
+    const language = await this.loadLanguage(wasmFile, projectRoot);
+    if (!language) {
+      return null;
+    }
+
+    let parser = this.parsers.get(language);
+    if (!parser) {
+      parser = new Parser();
+      parser.setLanguage(language);
+      this.parsers.set(language, parser);
+    }
+    return parser;
+  }
+
+  /**
+   * Load a grammar, caching both hits and misses so a missing grammar is only
+   * searched for once per project.
+   */
+  public static loadLanguage(wasmFile: string, projectRoot: string): Promise<Language | null> {
+    const key = join(projectRoot, wasmFile);
+    let lookup = this.lookups.get(key);
+    if (!lookup) {
+      const wasmPath = this.resolveGrammarPath(wasmFile, projectRoot);
+      lookup = wasmPath ? this.load(wasmPath) : Promise.resolve(null);
+      this.lookups.set(key, lookup);
+    }
+    return lookup;
+  }
+
+  /**
+   * Check whether a grammar file is available without loading it.
+   */
+  public static hasGrammar(wasmFile: string, projectRoot: string): boolean {
+    return this.resolveGrammarPath(wasmFile, projectRoot) !== null;
+  }
+
+  public static resolveGrammarPath(wasmFile: string, projectRoot: string): string | null {
+    for (const dir of this.getSearchPaths(projectRoot)) {
+      const candidate = join(dir, wasmFile);
+      if (existsSync(candidate)) {
+        return candidate;
+      }
+    }
+    return null;
+  }
+
+  public static getSearchPaths(projectRoot: string): string[] {
+    const dirs: string[] = [];
+
+    if (process.env.PRIMORDYN_GRAMMARS_DIR) {
+      dirs.push(process.env.PRIMORDYN_GRAMMARS_DIR);
+    }
+
+    dirs.push(join(projectRoot, '.primordyn', 'grammars'));
+
+    // dist/extractors -> <package>/grammars (also works from src/ under tsx)
+    dirs.push(join(__dirname, '..', '..', 'grammars'));
+
+    try {
+      const wasmsPackage = require.resolve('tree-sitter-wasms/package.json');
+      dirs.push(join(dirname(wasmsPackage), 'out'));
+    } catch {
+      // Missing from a partial install - the other directories still apply
+    }
+
+    return dirs;
+  }
+
+  private static load(wasmPath: string): Promise<Language | null> {
+    let language = this.languages.get(wasmPath);
+    if (!language) {
+      language = this.init()
+        .then(() => Language.load(wasmPath))
+        // Incompatible or corrupt grammar - fall back to the regex extractors
+        .catch(() => null);
+      this.languages.set(wasmPath, language);
+    }
+    return language;
+  }
+
+  private static init(): Promise<void> {
+    if (!this.initialized) {
+      this.initialized = Parser.init();
+    }
+    return this.initialized;
+  }
+}
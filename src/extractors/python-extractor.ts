@@ -1,8 +1,52 @@
+import type { Node } from 'web-tree-sitter';
 import { BaseExtractor } from './base.js';
-import type { FileInfo, ExtractedContext, Symbol, CallReference } from '../types/index.js';
+import { GrammarLoader } from './grammars.js';
+import type { FileInfo, ExtractedContext, Symbol, CallReference, ImportBinding, SymbolReference, TypeRelationship } from '../types/index.js';
 import type { StructureCategory, SymbolDetail } from './types.js';
 
+const PYTHON_GRAMMAR = 'tree-sitter-python.wasm';
+
+// Identifiers directly under these nodes name parameters, modules or declarations
+const NON_REFERENCE_PARENTS = new Set([
+  'parameters', 'lambda_parameters', 'typed_parameter', 'list_splat_pattern', 'dictionary_splat_pattern',
+  'dotted_name', 'aliased_import', 'import_statement', 'import_from_statement',
+  'global_statement', 'nonlocal_statement'
+]);
+
+// Builtins that are called often enough to matter for call linking
+const PYTHON_BUILTINS = new Set([
+  'abs', 'all', 'any', 'callable', 'chr', 'delattr', 'dir', 'divmod', 'enumerate', 'eval', 'exec',
+  'filter', 'format', 'frozenset', 'getattr', 'globals', 'hasattr', 'hash', 'hex', 'id', 'input',
+  'iter', 'locals', 'map', 'max', 'min', 'next', 'object', 'oct', 'open', 'ord', 'pow', 'repr',
+  'reversed', 'round', 'setattr', 'slice', 'sorted', 'sum', 'vars', 'zip', 'bytes', 'bytearray',
+  'Exception', 'ValueError', 'TypeError', 'KeyError', 'IndexError', 'RuntimeError', 'AttributeError',
+  'NotImplementedError', 'StopIteration', 'OSError', 'IOError'
+]);
+
+// Receivers that refer to the enclosing class or instance
+const SELF_NAMES = new Set(['self', 'cls', 'super']);
+
+// Enclosing class or function while walking the syntax tree
+interface PythonScope {
+  name: string;
+  kind: 'class' | 'function';
+  locals?: Set<string>;  // Parameters and assigned names of a function
+}
+
+/**
+ * Python extractor. Parses with tree-sitter-python when the grammar is
+ * available (see grammars/README.md) and falls back to regexes and
+ * indentation heuristics otherwise.
+ */
 export class PythonExtractor extends BaseExtractor {
+  private moduleImports: Map<string, string> = new Map(); // bound name -> module
+  private projectRoot: string;
+
+  constructor(projectRoot: string) {
+    super();
+    this.projectRoot = projectRoot;
+  }
+
   getSupportedLanguages(): string[] {
     return ['python'];
   }
@@ -16,15 +60,544 @@ export class PythonExtractor extends BaseExtractor {
   }
   
   async extract(fileInfo: FileInfo): Promise<ExtractedContext> {
+    // Load the parser before setting any per-file state: other files handled
+    // by this extractor can run while we wait for it
+    const parser = await GrammarLoader.getParser(PYTHON_GRAMMAR, this.projectRoot);
     this.initialize(fileInfo);
-    
+
+    const tree = parser?.parse(this.content);
+    if (tree) {
+      try {
+        return this.extractFromTree(tree.rootNode);
+      } finally {
+        tree.delete();
+      }
+    }
+
+    return this.extractWithPatterns();
+  }
+
+  private extractFromTree(root: Node): ExtractedContext {
     const context: ExtractedContext = {
       symbols: [],
       imports: [],
+      importBindings: [],
       exports: [],
       dependencies: [],
       comments: [],
       calls: [],
+      references: [],
+      relationships: [],
+      structure: {}
+    };
+
+    this.moduleImports = this.importedNames(root);
+    this.walk(root, [], context);
+    context.structure = this.buildStructure(context.symbols);
+
+    return context;
+  }
+
+  /**
+   * Visit every node once, tracking the enclosing classes and functions
+   */
+  private walk(node: Node, scopes: PythonScope[], context: ExtractedContext): void {
+    let childScopes = scopes;
+
+    switch (node.type) {
+      case 'function_definition': {
+        const symbol = this.functionSymbol(node, scopes);
+        context.symbols.push(symbol);
+        childScopes = [...scopes, { name: symbol.name, kind: 'function', locals: this.functionLocals(node) }];
+        break;
+      }
+      case 'class_definition': {
+        const symbol = this.classSymbol(node, scopes);
+        context.symbols.push(symbol);
+        this.baseRelationships(symbol, context.relationships!);
+        childScopes = [...scopes, { name: symbol.name, kind: 'class' }];
+        break;
+      }
+      case 'assignment':
+        this.visitAssignment(node, scopes, context);
+        break;
+      case 'augmented_assignment':
+        if (scopes.length === 0 && node.childForFieldName('left')?.text === '__all__') {
+          context.exports.push(...this.stringItems(node.childForFieldName('right')));
+        }
+        break;
+      case 'import_statement':
+      case 'import_from_statement':
+        this.visitImport(node, context);
+        break;
+      case 'call': {
+        const call = this.callReference(node, scopes);
+        if (call) context.calls.push(call);
+        break;
+      }
+      case 'identifier':
+      case 'attribute': {
+        const reference = this.nameReference(node);
+        if (reference) context.references!.push(reference);
+        break;
+      }
+      case 'comment':
+        // Skip shebang
+        if (!(node.startIndex === 0 && node.text.startsWith('#!'))) {
+          context.comments.push(node.text);
+        }
+        break;
+      case 'expression_statement': {
+        const value = node.namedChildren[0];
+        if (value?.type === 'string' && node.namedChildCount === 1) {
+          context.comments.push(value.text);
+        }
+        break;
+      }
+    }
+
+    for (const child of node.namedChildren) {
+      if (child) this.walk(child, childScopes, context);
+    }
+  }
+
+  private functionSymbol(node: Node, scopes: PythonScope[]): Symbol {
+    const name = node.childForFieldName('name')?.text || '';
+    const parametersNode = node.childForFieldName('parameters');
+    const returnType = node.childForFieldName('return_type')?.text || '';
+    const isAsync = node.children.some(child => child?.type === 'async');
+    const parent = scopes[scopes.length - 1];
+    const isMethod = parent?.kind === 'class';
+    const parameters = parametersNode ? this.parameters(parametersNode) : [];
+    const paramsText = (parametersNode?.text || '()').replace(/\s+/g, ' ').replace(/^\(\s*|\s*\)$/g, '');
+
+    return {
+      name,
+      type: isMethod ? 'method' : 'function',
+      lineStart: node.startPosition.row + 1,
+      lineEnd: node.endPosition.row + 1,
+      signature: `${isAsync ? 'async ' : ''}def ${name}(${paramsText})${returnType ? ' -> ' + returnType : ''}`,
+      documentation: this.docstring(node),
+      ...this.hierarchy(scopes, name),
+      metadata: {
+        async: isAsync,
+        params: parameters.map(param => param.name).filter(param => param !== 'self' && param !== 'cls'),
+        parameters,
+        returnType,
+        decorators: this.decorators(node),
+        ...this.scopeMetadata(scopes)
+      }
+    };
+  }
+
+  private classSymbol(node: Node, scopes: PythonScope[]): Symbol {
+    const name = node.childForFieldName('name')?.text || '';
+    const superclasses = node.childForFieldName('superclasses');
+    const bases: string[] = [];
+    const keywords: Record<string, string> = {};
+
+    for (const arg of superclasses?.namedChildren || []) {
+      if (!arg) continue;
+      if (arg.type === 'keyword_argument') {
+        // `metaclass=ABCMeta`
+        const key = arg.childForFieldName('name')?.text;
+        if (key) keywords[key] = arg.childForFieldName('value')?.text || '';
+      } else {
+        bases.push(arg.text);
+      }
+    }
+
+    // Direct members only; nested classes and functions get their own symbols
+    const methods: string[] = [];
+    const properties: string[] = [];
+    for (const statement of node.childForFieldName('body')?.namedChildren || []) {
+      const definition = statement?.type === 'decorated_definition' ? statement.childForFieldName('definition') : statement;
+      if (definition?.type === 'function_definition') {
+        methods.push(definition.childForFieldName('name')?.text || '');
+      } else if (definition?.type === 'expression_statement') {
+        const assignment = definition.namedChildren[0];
+        const left = assignment?.type === 'assignment' ? assignment.childForFieldName('left') : null;
+        if (left?.type === 'identifier') properties.push(left.text);
+      }
+    }
+
+    return {
+      name,
+      type: 'class',
+      lineStart: node.startPosition.row + 1,
+      lineEnd: node.endPosition.row + 1,
+      signature: `class ${name}${superclasses ? superclasses.text.replace(/\s+/g, ' ') : ''}`,
+      documentation: this.docstring(node),
+      ...this.hierarchy(scopes, name),
+      metadata: {
+        bases,
+        ...(Object.keys(keywords).length > 0 ? { keywords } : {}),
+        methods,
+        properties,
+        decorators: this.decorators(node),
+        ...this.scopeMetadata(scopes)
+      }
+    };
+  }
+
+  /**
+   * `scope` is the dotted chain of enclosing classes and functions;
+   * `className` is the class directly enclosing a method
+   */
+  private scopeMetadata(scopes: PythonScope[]): Record<string, unknown> {
+    if (scopes.length === 0) return {};
+    const parent = scopes[scopes.length - 1];
+    return {
+      scope: scopes.map(scope => scope.name).join('.'),
+      ...(parent.kind === 'class' ? { className: parent.name } : {})
+    };
+  }
+
+  /**
+   * Base classes as `extends` relationships; `Generic[T]` is recorded as
+   * `Generic`, and `object` is skipped
+   */
+  private baseRelationships(symbol: Symbol, relationships: TypeRelationship[]): void {
+    const bases = (symbol.metadata?.bases as string[] | undefined) || [];
+    for (const base of bases) {
+      const target = base.replace(/\[[\s\S]*$/, '').replace(/\s+/g, '');
+      if (/^[\w.]+$/.test(target) && target !== 'object') {
+        relationships.push({ source: symbol.qualifiedName || symbol.name, target, kind: 'extends', line: symbol.lineStart });
+      }
+    }
+  }
+
+  private hierarchy(scopes: PythonScope[], name: string): Pick<Symbol, 'qualifiedName' | 'parent'> {
+    const parent = scopes.map(scope => scope.name).join('.');
+    return parent ? { qualifiedName: `${parent}.${name}`, parent } : { qualifiedName: name };
+  }
+
+  private parameters(node: Node): Array<{ name: string; type?: string; default?: string; kind?: string }> {
+    const parameters: Array<{ name: string; type?: string; default?: string; kind?: string }> = [];
+
+    for (const param of node.namedChildren) {
+      if (!param) continue;
+      switch (param.type) {
+        case 'identifier':
+          parameters.push({ name: param.text });
+          break;
+        case 'typed_parameter': {
+          // `x: int`, `*args: str`, `**kwargs: Any`
+          const inner = param.namedChildren[0];
+          const kind = inner?.type === 'list_splat_pattern' ? 'args' : inner?.type === 'dictionary_splat_pattern' ? 'kwargs' : undefined;
+          parameters.push({
+            name: (inner?.type === 'identifier' ? inner.text : inner?.namedChildren[0]?.text) || '',
+            type: param.childForFieldName('type')?.text,
+            ...(kind ? { kind } : {})
+          });
+          break;
+        }
+        case 'default_parameter':
+        case 'typed_default_parameter':
+          parameters.push({
+            name: param.childForFieldName('name')?.text || '',
+            ...(param.childForFieldName('type') ? { type: param.childForFieldName('type')!.text } : {}),
+            default: param.childForFieldName('value')?.text
+          });
+          break;
+        case 'list_splat_pattern':
+          parameters.push({ name: param.namedChildren[0]?.text || '', kind: 'args' });
+          break;
+        case 'dictionary_splat_pattern':
+          parameters.push({ name: param.namedChildren[0]?.text || '', kind: 'kwargs' });
+          break;
+      }
+    }
+
+    return parameters;
+  }
+
+  private decorators(node: Node): string[] {
+    const parent = node.parent;
+    if (parent?.type !== 'decorated_definition') return [];
+    return parent.namedChildren
+      .filter((child): child is Node => child?.type === 'decorator')
+      .map(decorator => decorator.text.replace(/\s+/g, ' '));
+  }
+
+  /**
+   * The docstring of a function or class body, with quotes and common
+   * indentation removed
+   */
+  private docstring(node: Node): string | undefined {
+    const first = node.childForFieldName('body')?.namedChildren[0];
+    const value = first?.type === 'expression_statement' ? first.namedChildren[0] : null;
+    if (!value || value.type !== 'string') return undefined;
+
+    const raw = value.text.replace(/^[rRuUbBfF]*("""|'''|"|')/, '').replace(/("""|'''|"|')$/, '');
+    const lines = raw.split('\n');
+    const indents = lines.slice(1)
+      .filter(line => line.trim())
+      .map(line => this.getIndentation(line));
+    const indent = indents.length > 0 ? Math.min(...indents) : 0;
+    const text = [lines[0].trim(), ...lines.slice(1).map(line => line.substring(indent).trimEnd())]
+      .join('\n')
+      .trim();
+    return text || undefined;
+  }
+
+  private visitAssignment(node: Node, scopes: PythonScope[], context: ExtractedContext): void {
+    const left = node.childForFieldName('left');
+    const right = node.childForFieldName('right');
+    if (left?.type !== 'identifier' || !right) return;
+
+    if (left.text === '__all__' && scopes.length === 0) {
+      context.exports.push(...this.stringItems(right));
+    } else if (right.type === 'lambda') {
+      const params = right.childForFieldName('parameters')?.text || '';
+      context.symbols.push({
+        name: left.text,
+        type: 'function',
+        lineStart: node.startPosition.row + 1,
+        lineEnd: node.endPosition.row + 1,
+        signature: `${left.text} = lambda ${params}`,
+        ...this.hierarchy(scopes, left.text),
+        metadata: {
+          lambda: true,
+          params: this.parseParams(params),
+          ...this.scopeMetadata(scopes)
+        }
+      });
+    }
+  }
+
+  private stringItems(node: Node | null): string[] {
+    if (!node || (node.type !== 'list' && node.type !== 'tuple')) return [];
+    return node.namedChildren
+      .filter((item): item is Node => item?.type === 'string')
+      .map(item => item.text.replace(/^[rRuU]*["']+|["']+$/g, ''))
+      .filter(name => /^\w+$/.test(name));
+  }
+
+  private visitImport(node: Node, context: ExtractedContext): void {
+    const line = node.startPosition.row + 1;
+    const importedName = (child: Node) =>
+      child.type === 'aliased_import' ? child.childForFieldName('name')?.text || '' : child.text;
+
+    if (node.type === 'import_statement') {
+      // `import a.b, c as d` binds modules, not names
+      for (const child of node.childrenForFieldName('name')) {
+        if (!child) continue;
+        const module = importedName(child);
+        context.imports.push(module);
+        context.dependencies.push(module);
+        context.importBindings!.push({ source: module, names: [], line });
+      }
+      return;
+    }
+
+    const module = (node.childForFieldName('module_name')?.text || '').replace(/\s+/g, '');
+    if (!module) return;
+
+    const names = node.children.some(child => child?.type === 'wildcard_import')
+      ? ['*']
+      : node.childrenForFieldName('name')
+        .filter((child): child is Node => child !== null)
+        .map(importedName);
+
+    context.imports.push(module);
+    context.dependencies.push(module);
+    context.importBindings!.push({ source: module, names, line });
+  }
+
+  private callReference(node: Node, scopes: PythonScope[]): CallReference | null {
+    const target = node.childForFieldName('function');
+    if (!target) return null;
+
+    const position = {
+      line: node.startPosition.row + 1,
+      column: node.startPosition.column + 1
+    };
+
+    if (target.type === 'identifier') {
+      const name = target.text;
+      if (this.isPythonKeyword(name)) return null;
+      return {
+        calleeName: name,
+        callType: /^[A-Z]/.test(name) ? 'constructor' : 'function',
+        ...position,
+        ...this.callScope(name, scopes)
+      };
+    }
+
+    if (target.type === 'attribute') {
+      const attribute = target.childForFieldName('attribute')?.text;
+      if (!attribute) return null;
+      // Keep dotted receivers (`self.repo.save`, `os.path.join`) and `super()`;
+      // other receivers (calls, subscripts, literals) only contribute the method name
+      const objectText = target.childForFieldName('object')?.text.replace(/\s+/g, '') ?? '';
+      const receiver = objectText === 'super()' ? 'super' : /^[\w.]+$/.test(objectText) ? objectText : undefined;
+      const [root, ...rest] = receiver?.split('.') ?? [];
+      return {
+        calleeName: `${receiver ?? 'unknown'}.${attribute}`,
+        callType: 'method',
+        ...position,
+        // `self.repo.save()` calls whatever `self.repo` holds
+        ...(!receiver || (SELF_NAMES.has(root) && rest.length > 0)
+          ? { scope: 'unknown' as const }
+          : this.callScope(root, scopes))
+      };
+    }
+
+    return null;
+  }
+
+  /**
+   * Classify a call by what its first name is bound to: a local of an
+   * enclosing function, an import (project or package, settled once imports
+   * are resolved), a builtin, or a definition in the project
+   */
+  private callScope(root: string, scopes: PythonScope[]): Pick<CallReference, 'scope' | 'importSource'> {
+    if (SELF_NAMES.has(root)) {
+      return { scope: 'project' };
+    }
+    if (scopes.some(scope => scope.locals?.has(root))) {
+      return { scope: 'local' };
+    }
+    const source = this.moduleImports.get(root);
+    if (source) {
+      return { scope: 'project', importSource: source };
+    }
+    return { scope: PYTHON_BUILTINS.has(root) ? 'external' : 'project' };
+  }
+
+  /**
+   * Names bound by import statements anywhere in the file, mapped to the
+   * module they come from: `import numpy as np` binds `np`, `import os.path`
+   * binds `os`
+   */
+  private importedNames(root: Node): Map<string, string> {
+    const names = new Map<string, string>();
+    for (const node of root.descendantsOfType(['import_statement', 'import_from_statement'])) {
+      if (!node) continue;
+      if (node.type === 'import_statement') {
+        for (const child of node.childrenForFieldName('name')) {
+          if (!child) continue;
+          const module = (child.type === 'aliased_import' ? child.childForFieldName('name')?.text : child.text)?.replace(/\s+/g, '');
+          const alias = child.type === 'aliased_import' ? child.childForFieldName('alias')?.text : module?.split('.')[0];
+          if (module && alias) names.set(alias, module);
+        }
+        continue;
+      }
+
+      const module = (node.childForFieldName('module_name')?.text || '').replace(/\s+/g, '');
+      for (const child of node.childrenForFieldName('name')) {
+        if (!child) continue;
+        const bound = child.type === 'aliased_import' ? child.childForFieldName('alias')?.text : child.text;
+        if (module && bound) names.set(bound, module);
+      }
+    }
+    return names;
+  }
+
+  /**
+   * Names bound inside a function: parameters and assignment, loop, `with`
+   * and walrus targets, minus `global`/`nonlocal` names. Nested functions
+   * and classes are left out; they keep their own.
+   */
+  private functionLocals(node: Node): Set<string> {
+    const parametersNode = node.childForFieldName('parameters');
+    const locals = new Set((parametersNode ? this.parameters(parametersNode) : []).map(param => param.name).filter(Boolean));
+    const shared = new Set<string>();
+
+    const bind = (target: Node | null) => {
+      if (!target) return;
+      if (target.type === 'identifier') {
+        locals.add(target.text);
+      } else if (['pattern_list', 'tuple_pattern', 'list_pattern', 'as_pattern_target'].includes(target.type)) {
+        target.namedChildren.forEach(bind);
+      }
+    };
+    const visit = (current: Node) => {
+      for (const child of current.namedChildren) {
+        if (!child || ['function_definition', 'class_definition', 'lambda'].includes(child.type)) continue;
+        switch (child.type) {
+          case 'assignment':
+          case 'augmented_assignment':
+          case 'for_statement':
+          case 'for_in_clause':
+            bind(child.childForFieldName('left'));
+            break;
+          case 'named_expression':
+            bind(child.childForFieldName('name'));
+            break;
+          case 'as_pattern':
+            bind(child.childForFieldName('alias'));
+            break;
+          case 'global_statement':
+          case 'nonlocal_statement':
+            child.namedChildren.forEach(name => name && shared.add(name.text));
+            break;
+        }
+        visit(child);
+      }
+    };
+
+    const body = node.childForFieldName('body');
+    if (body) visit(body);
+    shared.forEach(name => locals.delete(name));
+    return locals;
+  }
+
+  /**
+   * A read, write or type annotation naming `node`. Definitions, parameters,
+   * keyword names and import clauses are not references; callees are
+   * recorded as calls.
+   */
+  private nameReference(node: Node): SymbolReference | null {
+    const parent = node.parent;
+    if (!parent) return null;
+    const is = (field: string) => parent.childForFieldName(field)?.equals(node) ?? false;
+
+    if (
+      (is('function') && parent.type === 'call') ||
+      (is('name') && ['function_definition', 'class_definition', 'keyword_argument', 'default_parameter', 'typed_default_parameter'].includes(parent.type)) ||
+      (is('attribute') && parent.type === 'attribute') ||
+      NON_REFERENCE_PARENTS.has(parent.type)
+    ) {
+      return null;
+    }
+
+    const text = node.text.replace(/\s+/g, '');
+    let name: string | undefined = text;
+    if (node.type === 'attribute' && !/^[\w.]+$/.test(text)) {
+      // `get_user().name` - only the attribute is known
+      name = node.childForFieldName('attribute')?.text;
+    }
+    if (!name || name === 'self' || name === 'cls' || this.isPythonKeyword(name)) return null;
+
+    let kind: SymbolReference['kind'] = 'read';
+    if (parent.type === 'argument_list' && parent.parent?.type === 'class_definition') {
+      kind = 'type'; // Base class
+    } else if (is('left') && ['assignment', 'augmented_assignment', 'for_statement', 'for_in_clause'].includes(parent.type)) {
+      kind = 'write';
+    } else {
+      for (let ancestor: Node | null = parent; ancestor && !ancestor.type.endsWith('statement'); ancestor = ancestor.parent) {
+        if (ancestor.type === 'type') {
+          kind = 'type';
+          break;
+        }
+      }
+    }
+
+    return { name, kind, line: node.startPosition.row + 1, column: node.startPosition.column + 1 };
+  }
+
+  private extractWithPatterns(): ExtractedContext {
+    const context: ExtractedContext = {
+      symbols: [],
+      imports: [],
+      importBindings: [],
+      exports: [],
+      dependencies: [],
+      comments: [],
+      calls: [],
+      relationships: [],
       structure: {}
     };
     
@@ -33,9 +606,15 @@ export class PythonExtractor extends BaseExtractor {
     
     // Extract classes
     this.extractClasses(context.symbols);
+
+    // Record enclosing classes/functions for qualified names
+    this.assignScopes(context.symbols);
+    context.symbols
+      .filter(symbol => symbol.type === 'class')
+      .forEach(symbol => this.baseRelationships(symbol, context.relationships!));
     
     // Extract imports
-    this.extractImports(context.imports, context.dependencies);
+    this.extractImports(context.imports, context.dependencies, context.importBindings!);
     
     // Extract exports (__all__)
     this.extractExports(context.exports);
@@ -144,6 +723,39 @@ export class PythonExtractor extends BaseExtractor {
     }
   }
   
+  /**
+   * Set `metadata.scope` to the dotted chain of enclosing classes and
+   * functions (`Outer.Inner` for a method of a nested class), and
+   * `metadata.className` to the class directly enclosing a method.
+   */
+  private assignScopes(symbols: Symbol[]): void {
+    const blocks = symbols
+      .filter(symbol => symbol.type === 'class' || ((symbol.type === 'function' || symbol.type === 'method') && !symbol.metadata?.lambda))
+      .map(symbol => ({ symbol, indent: this.getIndentation(this.lines[symbol.lineStart - 1] || '') }));
+
+    for (const symbol of symbols) {
+      symbol.qualifiedName = symbol.name;
+      const indent = this.getIndentation(this.lines[symbol.lineStart - 1] || '');
+      if (indent === 0) continue;
+
+      const enclosing = blocks
+        .filter(block => block.symbol !== symbol && block.indent < indent &&
+          block.symbol.lineStart < symbol.lineStart && block.symbol.lineEnd >= symbol.lineStart)
+        .sort((a, b) => a.indent - b.indent);
+      if (enclosing.length === 0) continue;
+
+      const parent = enclosing[enclosing.length - 1].symbol;
+      const scope = enclosing.map(block => block.symbol.name).join('.');
+      symbol.qualifiedName = `${scope}.${symbol.name}`;
+      symbol.parent = scope;
+      symbol.metadata = {
+        ...symbol.metadata,
+        scope,
+        ...(symbol.type === 'method' && parent.type === 'class' ? { className: parent.name } : {})
+      };
+    }
+  }
+
   private extractClassMembers(startLine: number, endLine: number, methods: string[], properties: string[]): void {
     const classContent = this.lines.slice(startLine, endLine).join('\n');
     
@@ -164,15 +776,17 @@ export class PythonExtractor extends BaseExtractor {
     }
   }
   
-  private extractImports(imports: string[], dependencies: string[]): void {
+  private extractImports(imports: string[], dependencies: string[], bindings: ImportBinding[]): void {
     // Standard imports
     const importPattern = /^import\s+([\w.,\s]+)(?:\s+as\s+\w+)?$/gm;
     let match;
     while ((match = importPattern.exec(this.content)) !== null) {
       const modules = match[1].split(',').map(m => m.trim());
+      const line = this.getLineNumber(match.index);
       modules.forEach(module => {
         imports.push(module);
         dependencies.push(module);
+        bindings.push({ source: module, names: [], line });
       });
     }
     
@@ -182,6 +796,12 @@ export class PythonExtractor extends BaseExtractor {
       const module = match[1];
       imports.push(module);
       dependencies.push(module);
+
+      // `from m import a, b as c` / `from m import (a, b)` - keep the original names
+      const names = match[2].replace(/[()\\]/g, '').split(',')
+        .map(name => name.trim().split(/\s+as\s+/)[0])
+        .filter(name => /^(\w+|\*)$/.test(name));
+      bindings.push({ source: module, names, line: this.getLineNumber(match.index) });
     }
   }
   
@@ -228,8 +848,7 @@ export class PythonExtractor extends BaseExtractor {
           calleeName: name,
           callType: 'function',
           line: this.getLineNumber(match.index),
-          column: this.getColumnNumber(match.index),
-          isExternal: false
+          column: this.getColumnNumber(match.index)
         });
       }
     }
@@ -243,8 +862,7 @@ export class PythonExtractor extends BaseExtractor {
         calleeName: `${obj}.${method}`,
         callType: 'method',
         line: this.getLineNumber(match.index),
-        column: this.getColumnNumber(match.index),
-        isExternal: false
+        column: this.getColumnNumber(match.index)
       });
     }
     
@@ -256,8 +874,7 @@ export class PythonExtractor extends BaseExtractor {
         calleeName: className,
         callType: 'constructor',
         line: this.getLineNumber(match.index),
-        column: this.getColumnNumber(match.index),
-        isExternal: false
+        column: this.getColumnNumber(match.index)
       });
     }
   }
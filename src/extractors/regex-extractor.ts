@@ -614,8 +614,7 @@ export class RegexExtractor extends BaseExtractor {
             callType: match[0].startsWith('new') ? 'constructor' : 
                      match[2] ? 'method' : 'function',
             line: this.getLineNumber(match.index),
-            column: this.getColumnNumber(match.index),
-            isExternal: false
+            column: this.getColumnNumber(match.index)
           });
         }
       }
@@ -0,0 +1,935 @@
+import { posix } from 'path';
+import { BaseExtractor } from './base.js';
+import type { FileInfo, ExtractedContext, Symbol, CallReference, ImportBinding, SymbolReference } from '../types/index.js';
+import type { StructureCategory, SymbolDetail } from './types.js';
+
+type RustItemKind = 'fn' | 'struct' | 'enum' | 'union' | 'trait' | 'type' | 'impl' | 'mod' | 'const';
+
+interface RustItem {
+  kind: RustItemKind;
+  name: string;
+  start: number;        // offset of the first character of the item (after indentation)
+  headerEnd: number;    // offset of the `{` or `;` that ends the header
+  end: number;          // offset of the closing `}` or `;`
+  hasBody: boolean;
+  visibility: string;
+  implTarget?: string;
+  implTrait?: string;
+  implTraitPath?: string;
+}
+
+interface RustScope {
+  item: RustItem;
+  modulePath: string;
+}
+
+interface RustModuleInfo {
+  modulePath: string;
+  isCrateRoot: boolean;
+  childDir: string;
+}
+
+const VISIBILITY = String.raw`((?:pub(?:\s*\([^)]*\))?\s+)?)`;
+
+const ITEM_PATTERNS: { kind: RustItemKind; pattern: RegExp }[] = [
+  { kind: 'fn', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`(?:default\s+)?(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*fn\s+(\w+)`, 'gm') },
+  { kind: 'struct', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`struct\s+(\w+)`, 'gm') },
+  { kind: 'enum', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`enum\s+(\w+)`, 'gm') },
+  { kind: 'union', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`union\s+(\w+)`, 'gm') },
+  { kind: 'trait', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`(?:unsafe\s+)?(?:auto\s+)?trait\s+(\w+)`, 'gm') },
+  { kind: 'type', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`type\s+(\w+)`, 'gm') },
+  { kind: 'mod', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`mod\s+(\w+)`, 'gm') },
+  { kind: 'const', pattern: new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`(?:const|static)\s+(?:mut\s+)?(\w+)\s*:`, 'gm') },
+  { kind: 'impl', pattern: new RegExp(String.raw`^[ \t]*()(?:unsafe\s+)?impl\b()`, 'gm') }
+];
+
+const CALL_KEYWORDS = new Set([
+  'if', 'while', 'for', 'match', 'loop', 'return', 'fn', 'let', 'in', 'as',
+  'move', 'where', 'impl', 'dyn', 'mut', 'ref', 'use', 'pub', 'crate',
+  'Some', 'None', 'Ok', 'Err'
+]);
+
+// Prelude names that never refer to project items
+const PRELUDE_NAMES = new Set(['Self', 'Some', 'None', 'Ok', 'Err']);
+
+// Call roots that always resolve outside the project: standard crates and
+// prelude types and functions
+const EXTERNAL_ROOTS = new Set([
+  'std', 'core', 'alloc', 'Box', 'Vec', 'String', 'Option', 'Result', 'Rc', 'Arc',
+  'HashMap', 'HashSet', 'BTreeMap', 'BTreeSet', 'VecDeque', 'drop', 'Default', 'From', 'Into',
+  'Iterator', 'ToString', 'Clone'
+]);
+
+// Roots that name the current crate or item
+const PROJECT_ROOTS = new Set(['self', 'Self', 'crate', 'super']);
+
+/**
+ * Rust extractor that understands impl/trait/module structure.
+ *
+ * Works on a copy of the source with comments and string contents blanked out,
+ * so braces and keywords inside literals never confuse block matching.
+ */
+export class RustExtractor extends BaseExtractor {
+  private masked = '';
+  private lineOffsets: number[] = [];
+  private relativePath = '';
+
+  getSupportedLanguages(): string[] {
+    return ['rust'];
+  }
+
+  canHandle(fileInfo: FileInfo): boolean {
+    return fileInfo.language === 'rust';
+  }
+
+  getPriority(): number {
+    return 10;
+  }
+
+  async extract(fileInfo: FileInfo): Promise<ExtractedContext> {
+    this.initialize(fileInfo);
+    this.relativePath = fileInfo.relativePath.replace(/\\/g, '/');
+    this.masked = this.maskSource(this.content);
+    this.lineOffsets = this.computeLineOffsets(this.content);
+
+    const context: ExtractedContext = {
+      symbols: [],
+      imports: [],
+      importBindings: [],
+      exports: [],
+      dependencies: [],
+      comments: [],
+      calls: [],
+      relationships: [],
+      structure: {}
+    };
+
+    const fileModule = RustExtractor.moduleForFile(this.relativePath);
+    const items = this.collectItems();
+
+    this.extractItems(items, fileModule, context);
+    this.extractUses(context.imports, context.dependencies, context.exports, context.importBindings!);
+    this.extractRustComments(context.comments);
+    context.calls = this.extractCalls(items, context.importBindings!);
+    context.references = this.extractReferences();
+
+    // The file itself is a node in the module tree
+    context.symbols.push({
+      name: fileModule.modulePath.split('::').pop() || 'crate',
+      type: 'module',
+      lineStart: 1,
+      lineEnd: this.lines.length,
+      signature: fileModule.isCrateRoot ? 'crate' : `mod ${fileModule.modulePath}`,
+      documentation: this.collectInnerDocs(0),
+      qualifiedName: fileModule.modulePath,
+      metadata: {
+        modulePath: fileModule.modulePath,
+        crateRoot: fileModule.isCrateRoot,
+        file: true
+      }
+    });
+
+    context.structure = this.buildStructure(context.symbols);
+
+    return context;
+  }
+
+  /**
+   * Work out the module path of a file from its location in the crate,
+   * following the `mod.rs` / `foo.rs` conventions.
+   */
+  public static moduleForFile(relativePath: string): RustModuleInfo {
+    const normalized = relativePath.replace(/\\/g, '/');
+    const dir = posix.dirname(normalized);
+    const base = posix.basename(normalized, '.rs');
+    const segments = normalized.split('/');
+    const srcIndex = segments.lastIndexOf('src');
+    const parentDir = posix.basename(dir);
+
+    const isCrateRoot =
+      ((base === 'lib' || base === 'main') && (srcIndex === -1 || srcIndex === segments.length - 2)) ||
+      (base === 'build' && srcIndex === -1) ||
+      ['bin', 'tests', 'examples', 'benches'].includes(parentDir) ||
+      (base === 'main' && posix.basename(posix.dirname(dir)) === 'bin');
+
+    if (isCrateRoot) {
+      return { modulePath: 'crate', isCrateRoot: true, childDir: dir };
+    }
+
+    const moduleSegments = (srcIndex === -1 ? segments.slice(base === 'mod' ? -2 : -1) : segments.slice(srcIndex + 1))
+      .map(segment => segment.replace(/\.rs$/, ''));
+    if (moduleSegments[moduleSegments.length - 1] === 'mod') {
+      moduleSegments.pop();
+      return { modulePath: ['crate', ...moduleSegments].join('::'), isCrateRoot: false, childDir: dir };
+    }
+
+    return {
+      modulePath: ['crate', ...moduleSegments].join('::'),
+      isCrateRoot: false,
+      childDir: posix.join(dir, base)
+    };
+  }
+
+  private collectItems(): RustItem[] {
+    const items: RustItem[] = [];
+
+    for (const { kind, pattern } of ITEM_PATTERNS) {
+      pattern.lastIndex = 0;
+      let match;
+      while ((match = pattern.exec(this.masked)) !== null) {
+        const start = match.index + match[0].length - match[0].trimStart().length;
+        const afterHeader = match.index + match[0].length;
+        const bounds = this.findItemBounds(afterHeader, kind !== 'const');
+        if (!bounds) continue;
+
+        const item: RustItem = {
+          kind,
+          name: match[2] || '',
+          start,
+          headerEnd: bounds.headerEnd,
+          end: bounds.end,
+          hasBody: bounds.hasBody,
+          visibility: (match[1] || '').replace(/\s+/g, '')
+        };
+
+        if (kind === 'impl') {
+          const header = this.parseImplHeader(this.masked.substring(afterHeader, bounds.headerEnd));
+          if (!header) continue;
+          item.name = header.target;
+          item.implTarget = header.target;
+          item.implTrait = header.trait;
+          item.implTraitPath = header.traitPath;
+        }
+
+        items.push(item);
+      }
+    }
+
+    return items.sort((a, b) => a.start - b.start);
+  }
+
+  private extractItems(items: RustItem[], fileModule: RustModuleInfo, context: ExtractedContext): void {
+    const implementedTraits = new Map<string, string[]>();
+
+    for (const item of items) {
+      const scopes = this.enclosingScopes(item, items, fileModule.modulePath);
+      const innermost = scopes[scopes.length - 1];
+      const modulePath = innermost ? innermost.modulePath : fileModule.modulePath;
+      const lineStart = this.lineAt(item.start);
+      const lineEnd = this.lineAt(item.end);
+      const documentation = this.collectOuterDocs(lineStart);
+      const attributes = this.collectAttributes(lineStart);
+      const signature = this.signatureFor(item);
+      const isModuleLevel = !innermost;
+
+      const baseMetadata: Record<string, unknown> = {
+        module: modulePath,
+        visibility: item.visibility || 'private'
+      };
+      if (attributes.length > 0) {
+        baseMetadata.attributes = attributes;
+      }
+
+      switch (item.kind) {
+        case 'impl': {
+          if (item.implTrait && item.implTarget) {
+            context.relationships!.push({
+              source: item.implTarget,
+              target: item.implTrait,
+              kind: 'implements',
+              line: lineStart
+            });
+            const traits = implementedTraits.get(item.implTarget) || [];
+            traits.push(item.implTrait);
+            implementedTraits.set(item.implTarget, traits);
+          }
+          break;
+        }
+
+        case 'fn': {
+          const owner = this.ownerScope(scopes);
+          if (owner && owner.item.kind === 'impl') {
+            context.symbols.push({
+              name: `${owner.item.implTarget}::${item.name}`,
+              type: 'method',
+              lineStart,
+              lineEnd,
+              signature,
+              documentation,
+              qualifiedName: `${modulePath}::${owner.item.implTarget}::${item.name}`,
+              parent: `${modulePath}::${owner.item.implTarget}`,
+              metadata: {
+                ...baseMetadata,
+                owner: owner.item.implTarget,
+                trait: owner.item.implTrait,
+                methodName: item.name,
+                receiver: this.hasSelfReceiver(item)
+              }
+            });
+          } else if (owner && owner.item.kind === 'trait') {
+            context.symbols.push({
+              name: `${owner.item.name}::${item.name}`,
+              type: 'method',
+              lineStart,
+              lineEnd,
+              signature,
+              documentation,
+              qualifiedName: `${modulePath}::${owner.item.name}::${item.name}`,
+              parent: `${modulePath}::${owner.item.name}`,
+              metadata: {
+                ...baseMetadata,
+                trait: owner.item.name,
+                methodName: item.name,
+                required: !item.hasBody,
+                receiver: this.hasSelfReceiver(item)
+              }
+            });
+          } else {
+            const parentFunction = innermost && innermost.item.kind === 'fn' ? innermost.item.name : undefined;
+            const parent = parentFunction ? `${modulePath}::${parentFunction}` : modulePath;
+            context.symbols.push({
+              name: item.name,
+              type: 'function',
+              lineStart,
+              lineEnd,
+              signature,
+              documentation,
+              qualifiedName: `${parent}::${item.name}`,
+              parent,
+              metadata: parentFunction ? { ...baseMetadata, parentFunction } : baseMetadata
+            });
+          }
+          break;
+        }
+
+        case 'struct':
+        case 'union':
+        case 'enum':
+        case 'trait':
+        case 'type': {
+          const symbolType: Symbol['type'] = item.kind === 'union' ? 'struct' : item.kind;
+          const metadata: Record<string, unknown> = { ...baseMetadata };
+          if (item.kind === 'union') {
+            metadata.union = true;
+          }
+          if (item.kind === 'trait') {
+            const supertraits = this.parseSupertraits(item);
+            if (supertraits.length > 0) {
+              metadata.supertraits = supertraits;
+              supertraits.forEach(target => context.relationships!.push({
+                source: item.name,
+                target,
+                kind: 'extends',
+                line: lineStart
+              }));
+            }
+          }
+          context.symbols.push({
+            name: item.name,
+            type: symbolType,
+            lineStart,
+            lineEnd,
+            signature,
+            documentation,
+            qualifiedName: `${modulePath}::${item.name}`,
+            parent: modulePath,
+            metadata
+          });
+          break;
+        }
+
+        case 'mod': {
+          const childPath = `${modulePath}::${item.name}`;
+          if (item.hasBody) {
+            context.symbols.push({
+              name: item.name,
+              type: 'module',
+              lineStart,
+              lineEnd,
+              signature,
+              documentation: this.joinDocs(documentation, this.collectInnerDocs(this.lineAt(item.headerEnd))),
+              qualifiedName: childPath,
+              parent: modulePath,
+              metadata: { ...baseMetadata, modulePath: childPath, inline: true }
+            });
+          } else {
+            // `mod foo;` - the body lives in foo.rs or foo/mod.rs (or a #[path] override)
+            const pathOverride = attributes
+              .map(attr => attr.match(/^path\s*=\s*"([^"]+)"$/))
+              .find(Boolean);
+            const nestedDir = scopes
+              .filter(scope => scope.item.kind === 'mod')
+              .map(scope => scope.item.name);
+            const baseDir = posix.join(fileModule.childDir, ...nestedDir);
+            const candidates = pathOverride
+              ? [posix.join(posix.dirname(this.relativePath), pathOverride[1])]
+              : [posix.join(baseDir, `${item.name}.rs`), posix.join(baseDir, item.name, 'mod.rs')];
+            context.symbols.push({
+              name: item.name,
+              type: 'module',
+              lineStart,
+              lineEnd: lineStart,
+              signature,
+              documentation,
+              qualifiedName: childPath,
+              parent: modulePath,
+              metadata: { ...baseMetadata, modulePath: childPath, declaration: true, candidates }
+            });
+          }
+          break;
+        }
+
+        case 'const': {
+          context.symbols.push({
+            name: item.name,
+            type: 'constant',
+            lineStart,
+            lineEnd,
+            signature,
+            documentation,
+            qualifiedName: `${modulePath}::${item.name}`,
+            parent: modulePath,
+            metadata: baseMetadata
+          });
+          break;
+        }
+      }
+
+      if (isModuleLevel && item.kind !== 'impl' && item.visibility.startsWith('pub') && !item.visibility.includes('(')) {
+        context.exports.push(item.name);
+      }
+    }
+
+    // Attach locally implemented traits to their types
+    for (const symbol of context.symbols) {
+      const traits = implementedTraits.get(symbol.name);
+      if (traits && (symbol.type === 'struct' || symbol.type === 'enum')) {
+        symbol.metadata = { ...symbol.metadata, traits };
+      }
+    }
+  }
+
+  private enclosingScopes(item: RustItem, items: RustItem[], fileModulePath: string): RustScope[] {
+    const containers = items
+      .filter(other => other !== item && other.hasBody && other.headerEnd < item.start && other.end > item.start)
+      .sort((a, b) => a.start - b.start);
+
+    const scopes: RustScope[] = [];
+    let modulePath = fileModulePath;
+    for (const container of containers) {
+      if (container.kind === 'mod') {
+        modulePath = `${modulePath}::${container.name}`;
+      }
+      scopes.push({ item: container, modulePath });
+    }
+    return scopes;
+  }
+
+  private ownerScope(scopes: RustScope[]): RustScope | undefined {
+    // Methods belong to the nearest impl/trait, unless a fn sits in between
+    for (let i = scopes.length - 1; i >= 0; i--) {
+      const kind = scopes[i].item.kind;
+      if (kind === 'impl' || kind === 'trait') return scopes[i];
+      if (kind === 'fn' || kind === 'mod') return undefined;
+    }
+    return undefined;
+  }
+
+  private findItemBounds(from: number, allowBody: boolean): { headerEnd: number; end: number; hasBody: boolean } | null {
+    let depth = 0;
+    for (let i = from; i < this.masked.length; i++) {
+      const char = this.masked[i];
+      if (char === '(' || char === '[' || (!allowBody && char === '{')) {
+        depth++;
+      } else if (char === ')' || char === ']' || (!allowBody && char === '}')) {
+        depth--;
+        if (depth < 0) return null;
+      } else if (char === ';' && depth === 0) {
+        return { headerEnd: i, end: i, hasBody: false };
+      } else if (char === '{' && depth === 0) {
+        const close = this.findMatchingBrace(i);
+        return { headerEnd: i, end: close, hasBody: true };
+      }
+    }
+    return null;
+  }
+
+  private findMatchingBrace(open: number): number {
+    let depth = 0;
+    for (let i = open; i < this.masked.length; i++) {
+      if (this.masked[i] === '{') {
+        depth++;
+      } else if (this.masked[i] === '}') {
+        depth--;
+        if (depth === 0) return i;
+      }
+    }
+    return this.masked.length - 1;
+  }
+
+  private parseImplHeader(header: string): { target: string; trait?: string; traitPath?: string } | null {
+    const text = this.stripGenerics(header.replace(/\bwhere\b[\s\S]*$/, '')).trim();
+    const match = text.match(/^(?:(!?\s*[\w:]+)\s+for\s+)?(?:&\s*(?:'\w+\s+)?(?:mut\s+)?)?(?:dyn\s+)?([\w:]+)/);
+    if (!match) return null;
+
+    const target = match[2].split('::').pop() || match[2];
+    if (!match[1]) {
+      return { target };
+    }
+
+    const traitPath = match[1].replace(/\s+/g, '');
+    return { target, trait: traitPath.split('::').pop(), traitPath };
+  }
+
+  private parseSupertraits(item: RustItem): string[] {
+    const header = this.masked.substring(item.start, item.headerEnd);
+    const bounds = this.stripGenerics(header.replace(/\bwhere\b[\s\S]*$/, '')).match(/trait\s+\w+\s*:\s*([\s\S]+)$/);
+    if (!bounds) return [];
+
+    return bounds[1]
+      .split('+')
+      .map(bound => bound.trim().replace(/^\?/, '').split('::').pop() || '')
+      .filter(bound => /^[A-Za-z_]\w*$/.test(bound));
+  }
+
+  private stripGenerics(text: string): string {
+    let result = '';
+    let depth = 0;
+    const cleaned = text.replace(/->/g, '  ');
+    for (const char of cleaned) {
+      if (char === '<') {
+        depth++;
+      } else if (char === '>') {
+        depth = Math.max(0, depth - 1);
+      } else if (depth === 0) {
+        result += char;
+      }
+    }
+    return result;
+  }
+
+  private hasSelfReceiver(item: RustItem): boolean {
+    const header = this.masked.substring(item.start, item.headerEnd);
+    return /\(\s*(?:&\s*(?:'\w+\s+)?)?(?:mut\s+)?self\b/.test(header);
+  }
+
+  private signatureFor(item: RustItem): string {
+    return this.content
+      .substring(item.start, item.headerEnd)
+      .replace(/\s+/g, ' ')
+      .trim();
+  }
+
+  private collectOuterDocs(lineStart: number): string | undefined {
+    const docs: string[] = [];
+    for (let i = lineStart - 2; i >= 0; i--) {
+      const line = this.lines[i].trim();
+      if (line.startsWith('///') && !line.startsWith('////')) {
+        docs.unshift(line.replace(/^\/\/\/\s?/, ''));
+      } else if (line.startsWith('#[')) {
+        // Attributes may sit between the docs and the item
+        continue;
+      } else {
+        break;
+      }
+    }
+    return docs.length > 0 ? docs.join('\n') : undefined;
+  }
+
+  private collectInnerDocs(fromLine: number): string | undefined {
+    const docs: string[] = [];
+    for (let i = fromLine; i < this.lines.length; i++) {
+      const line = this.lines[i].trim();
+      if (line.startsWith('//!')) {
+        docs.push(line.replace(/^\/\/!\s?/, ''));
+      } else if (line === '' || line.startsWith('#![')) {
+        if (docs.length > 0 && line === '') break;
+      } else {
+        break;
+      }
+    }
+    return docs.length > 0 ? docs.join('\n') : undefined;
+  }
+
+  private joinDocs(outer?: string, inner?: string): string | undefined {
+    const parts = [outer, inner].filter(Boolean);
+    return parts.length > 0 ? parts.join('\n\n') : undefined;
+  }
+
+  private collectAttributes(lineStart: number): string[] {
+    const attributes: string[] = [];
+    for (let i = lineStart - 2; i >= 0; i--) {
+      const line = this.lines[i].trim();
+      const match = line.match(/^#\[(.+)\]$/);
+      if (match) {
+        attributes.unshift(match[1].trim());
+      } else if (!line.startsWith('///')) {
+        break;
+      }
+    }
+    return attributes;
+  }
+
+  private extractUses(imports: string[], dependencies: string[], exports: string[], bindings: ImportBinding[]): void {
+    const usePattern = new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`use\s+([^;]+);`, 'gm');
+    let match;
+    while ((match = usePattern.exec(this.masked)) !== null) {
+      // Mark aliases with '@' so whitespace can be dropped safely
+      const tree = match[2].replace(/\s+as\s+/g, '@').replace(/\s+/g, '');
+      const paths = this.expandUseTree(tree);
+
+      for (const path of paths) {
+        const [fullPath, alias] = path.split('@');
+        imports.push(fullPath);
+        bindings.push({
+          source: fullPath,
+          names: [fullPath.split('::').pop() || fullPath],
+          line: this.lineAt(match.index),
+          reexport: match[1].trim() === 'pub'
+        });
+
+        const root = fullPath.replace(/^::/, '').split('::')[0];
+        if (root && !['crate', 'self', 'super', 'std', 'core', 'alloc'].includes(root) && !dependencies.includes(root)) {
+          dependencies.push(root);
+        }
+
+        // `pub use` re-exports the imported name
+        if (match[1].trim() === 'pub') {
+          const exported = alias || fullPath.split('::').pop();
+          if (exported && exported !== '*' && exported !== 'self') {
+            exports.push(exported);
+          }
+        }
+      }
+    }
+  }
+
+  /**
+   * Expand `a::b::{c, d::{e, f as g}}` into individual paths.
+   */
+  private expandUseTree(tree: string, prefix: string = ''): string[] {
+    const braceStart = tree.indexOf('{');
+    if (braceStart === -1) {
+      const path = prefix ? `${prefix}::${tree}` : tree;
+      return [path.replace(/::self(@|$)/, '$1')];
+    }
+
+    const head = tree.substring(0, braceStart).replace(/::$/, '');
+    const inner = tree.substring(braceStart + 1, tree.lastIndexOf('}'));
+    const newPrefix = [prefix, head].filter(Boolean).join('::');
+
+    const parts: string[] = [];
+    let depth = 0;
+    let current = '';
+    for (const char of inner) {
+      if (char === '{') depth++;
+      if (char === '}') depth--;
+      if (char === ',' && depth === 0) {
+        if (current) parts.push(current);
+        current = '';
+      } else {
+        current += char;
+      }
+    }
+    if (current) parts.push(current);
+
+    return parts.flatMap(part => part === 'self' ? [newPrefix] : this.expandUseTree(part, newPrefix));
+  }
+
+  private extractRustComments(comments: string[]): void {
+    const commentPattern = /\/\/.*$|\/\*[\s\S]*?\*\//gm;
+    let match;
+    while ((match = commentPattern.exec(this.content)) !== null) {
+      // Only count comments the masking pass also saw (skips `//` inside strings)
+      if (this.masked[match.index] === ' ') {
+        comments.push(match[0]);
+      }
+    }
+  }
+
+  private extractCalls(items: RustItem[], bindings: ImportBinding[]): CallReference[] {
+    const calls: CallReference[] = [];
+    const imported = new Map<string, string>();
+    for (const binding of bindings) {
+      const name = binding.names[0];
+      if (name && name !== '*' && !imported.has(name)) imported.set(name, binding.source);
+    }
+    const functions = items.filter(item => item.kind === 'fn' && item.hasBody);
+    const localsByItem = new Map<RustItem, Set<string>>();
+    const scopeAt = (index: number, root: string) => {
+      // Innermost function body containing the call
+      const fn = functions.filter(item => item.headerEnd <= index && index < item.end)
+        .reduce<RustItem | undefined>((inner, item) => !inner || item.start > inner.start ? item : inner, undefined);
+      let locals = fn && localsByItem.get(fn);
+      if (fn && !locals) {
+        locals = this.functionLocals(fn);
+        localsByItem.set(fn, locals);
+      }
+      return this.callScope(root, locals, imported);
+    };
+    const turbofish = String.raw`(?:\s*::\s*<[^()]*?>)?`;
+
+    // Function and path calls: foo(), Config::new(), utils::to_uppercase()
+    const pathCall = new RegExp(String.raw`(?<![\w.:!'])((?:\w+\s*::\s*)*\w+)` + turbofish + String.raw`\s*\(`, 'g');
+    let match;
+    while ((match = pathCall.exec(this.masked)) !== null) {
+      const name = match[1].replace(/\s+/g, '');
+      const last = name.split('::').pop() || name;
+      if (CALL_KEYWORDS.has(name) || /^\d/.test(name)) continue;
+      if (/\bfn\s+$/.test(this.masked.substring(Math.max(0, match.index - 10), match.index))) continue;
+
+      calls.push({
+        calleeName: name,
+        callType: last === 'new' && name.includes('::') ? 'constructor' : 'function',
+        line: this.lineAt(match.index),
+        column: this.columnAt(match.index),
+        ...scopeAt(match.index, name.split('::')[0])
+      });
+    }
+
+    // Method calls: self.data.insert(), cache.get()
+    const methodCall = new RegExp(String.raw`(?:(\w+)|[)\]?])\s*\.\s*(\w+)` + turbofish + String.raw`\s*\(`, 'g');
+    while ((match = methodCall.exec(this.masked)) !== null) {
+      const receiver = match[1];
+      const method = match[2];
+      if (receiver && /^\d+$/.test(receiver)) continue;
+      const index = match.index + match[0].indexOf(method, match[0].indexOf('.'));
+
+      calls.push({
+        calleeName: receiver ? `${receiver}.${method}` : method,
+        callType: 'method',
+        line: this.lineAt(index),
+        column: this.columnAt(index),
+        ...(receiver ? scopeAt(index, receiver) : { scope: 'project' as const })
+      });
+      // Allow chained calls to be matched from this method name onwards
+      methodCall.lastIndex = index;
+    }
+
+    return calls;
+  }
+
+  /**
+   * Classify a call by what its first path segment is bound to. `use`d names
+   * stay project calls until import resolution shows they come from another
+   * crate.
+   */
+  private callScope(root: string, locals: Set<string> | undefined, imported: Map<string, string>): Pick<CallReference, 'scope' | 'importSource'> {
+    if (PROJECT_ROOTS.has(root)) return { scope: 'project' };
+    if (locals?.has(root)) return { scope: 'local' };
+    const source = imported.get(root);
+    if (source) return { scope: 'project', importSource: source };
+    return { scope: EXTERNAL_ROOTS.has(root) ? 'external' : 'project' };
+  }
+
+  /**
+   * Parameters, `let` bindings, `for` patterns and closure parameters of a
+   * function. Nested functions are not excluded; shadowing across them is
+   * rare enough not to matter here.
+   */
+  private functionLocals(item: RustItem): Set<string> {
+    const locals = new Set<string>();
+    const bindAll = (pattern: string) => {
+      for (const word of pattern.match(/\b[a-z_]\w*\b/g) || []) {
+        if (!['mut', 'ref', 'self', '_'].includes(word)) locals.add(word);
+      }
+    };
+
+    const header = this.masked.substring(item.start, item.headerEnd);
+    const name = /\bfn\s+\w+\s*/.exec(header);
+    // Skip generic parameters, which may hold `Fn(..)` bounds, to reach the parameter list
+    let open = name ? name.index + name[0].length : header.length;
+    for (let depth = 0; open < header.length && (depth > 0 || header[open] !== '('); open++) {
+      if (header[open] === '<') depth++;
+      if (header[open] === '>') depth--;
+    }
+    let close = open;
+    for (let depth = 0; close < header.length; close++) {
+      if (header[close] === '(') depth++;
+      if (header[close] === ')' && --depth === 0) break;
+    }
+    const params = header.substring(open + 1, close);
+    for (const match of params.matchAll(/(?:^|,)\s*((?:mut\s+)?\w+|\([^)]*\))\s*:/g)) {
+      bindAll(match[1]);
+    }
+
+    const body = this.masked.substring(item.headerEnd, item.end);
+    for (const match of body.matchAll(/\blet\s+([^=;]+?)\s*(?::[^=;]*)?(?:=|;)/g)) {
+      bindAll(match[1].replace(/\w+\s*(?=\{|\()/g, ''));
+    }
+    for (const match of body.matchAll(/\bfor\s+(.+?)\s+in\b/g)) {
+      bindAll(match[1]);
+    }
+    for (const match of body.matchAll(/(?:^|[(,=]|move)\s*\|([^|]*)\|/gm)) {
+      bindAll(match[1].replace(/:[^,]*/g, ''));
+    }
+    return locals;
+  }
+
+  /**
+   * Type and constant references by path: `User`, `models::User`,
+   * `Ordering::Less`, `MAX_SIZE`. Declared names, generic parameters,
+   * `use` items and tuple-struct constructor calls are skipped.
+   */
+  private extractReferences(): SymbolReference[] {
+    const references: SymbolReference[] = [];
+    // `use` items are recorded through the import bindings
+    const usePattern = new RegExp(String.raw`^[ \t]*` + VISIBILITY + String.raw`use\s+[^;]+;`, 'gm');
+    const source = this.masked.replace(usePattern, item => item.replace(/[^\n]/g, ' '));
+
+    const pathPattern = /(?<![\w:.'])((?:\w+\s*::\s*)*[A-Z]\w*)/g;
+    let match;
+    while ((match = pathPattern.exec(source)) !== null) {
+      const name = match[1].replace(/\s+/g, '');
+      const last = name.split('::').pop() || name;
+      if (PRELUDE_NAMES.has(last) || /^[A-Z]$/.test(last)) continue;
+      if (/\b(?:struct|enum|union|trait|type|mod|fn|const|static|let|mut)\s+$/.test(source.substring(Math.max(0, match.index - 10), match.index))) continue;
+      if (/^\s*(?:::\s*<[^()]*?>)?\s*\(/.test(source.substring(pathPattern.lastIndex, pathPattern.lastIndex + 80))) continue;
+
+      references.push({
+        name,
+        kind: /^[A-Z][A-Z0-9_]+$/.test(last) ? 'read' : 'type',
+        line: this.lineAt(match.index),
+        column: this.columnAt(match.index)
+      });
+    }
+
+    return references;
+  }
+
+  /**
+   * Blank out comments, string and char literal contents, preserving offsets
+   * and newlines.
+   */
+  private maskSource(source: string): string {
+    const out = source.split('');
+    const blank = (from: number, to: number) => {
+      for (let k = from; k < to && k < out.length; k++) {
+        if (out[k] !== '\n') out[k] = ' ';
+      }
+    };
+
+    let i = 0;
+    while (i < source.length) {
+      const char = source[i];
+      const next = source[i + 1];
+
+      if (char === '/' && next === '/') {
+        const end = source.indexOf('\n', i);
+        const stop = end === -1 ? source.length : end;
+        blank(i, stop);
+        i = stop;
+      } else if (char === '/' && next === '*') {
+        // Block comments nest in Rust
+        let depth = 1;
+        let j = i + 2;
+        while (j < source.length && depth > 0) {
+          if (source[j] === '/' && source[j + 1] === '*') { depth++; j += 2; }
+          else if (source[j] === '*' && source[j + 1] === '/') { depth--; j += 2; }
+          else { j++; }
+        }
+        blank(i, j);
+        i = j;
+      } else if ((char === 'r' || (char === 'b' && next === 'r')) && /^b?r#*"/.test(source.substring(i, i + 260)) && !/\w/.test(source[i - 1] || '')) {
+        const opener = source.substring(i, i + 260).match(/^b?r(#*)"/)!;
+        const closer = `"${opener[1]}`;
+        const contentStart = i + opener[0].length;
+        const end = source.indexOf(closer, contentStart);
+        const stop = end === -1 ? source.length : end;
+        blank(contentStart, stop);
+        i = stop + closer.length;
+      } else if (char === '"') {
+        let j = i + 1;
+        while (j < source.length && source[j] !== '"') {
+          j += source[j] === '\\' ? 2 : 1;
+        }
+        blank(i + 1, j);
+        i = j + 1;
+      } else if (char === '\'') {
+        // Char literal ('a', '\n', '\u{1F600}') vs lifetime ('a)
+        const literal = source.substring(i, i + 16).match(/^'(?:\\(?:u\{[0-9a-fA-F]+\}|x[0-9a-fA-F]{2}|.)|[^\\'\n])'/);
+        if (literal) {
+          blank(i + 1, i + literal[0].length - 1);
+          i += literal[0].length;
+        } else {
+          i++;
+        }
+      } else {
+        i++;
+      }
+    }
+
+    return out.join('');
+  }
+
+  private computeLineOffsets(source: string): number[] {
+    const offsets = [0];
+    for (let i = 0; i < source.length; i++) {
+      if (source[i] === '\n') offsets.push(i + 1);
+    }
+    return offsets;
+  }
+
+  private lineAt(index: number): number {
+    let low = 0;
+    let high = this.lineOffsets.length - 1;
+    while (low < high) {
+      const mid = (low + high + 1) >> 1;
+      if (this.lineOffsets[mid] <= index) {
+        low = mid;
+      } else {
+        high = mid - 1;
+      }
+    }
+    return low + 1;
+  }
+
+  private columnAt(index: number): number {
+    return index - this.lineOffsets[this.lineAt(index) - 1] + 1;
+  }
+
+  private buildStructure(symbols: Symbol[]): StructureCategory {
+    const structure: StructureCategory = {
+      functions: [],
+      methods: [],
+      structs: [],
+      enums: [],
+      traits: [],
+      types: [],
+      modules: [],
+      constants: []
+    };
+
+    const categories: Partial<Record<Symbol['type'], string>> = {
+      function: 'functions',
+      method: 'methods',
+      struct: 'structs',
+      enum: 'enums',
+      trait: 'traits',
+      type: 'types',
+      module: 'modules',
+      constant: 'constants'
+    };
+
+    symbols.forEach(symbol => {
+      const category = categories[symbol.type];
+      if (!category || symbol.metadata?.file) return;
+
+      const detail: SymbolDetail = {
+        name: symbol.name,
+        line: symbol.lineStart,
+        signature: symbol.signature || ''
+      };
+      structure[category].push(detail);
+    });
+
+    // Remove empty categories
+    Object.keys(structure).forEach(key => {
+      if (structure[key].length === 0) {
+        delete structure[key];
+      }
+    });
+
+    return structure;
+  }
+}
@@ -1,12 +1,14 @@
-import { Parser, Language, Node } from 'web-tree-sitter';
+import { Parser, Node } from 'web-tree-sitter';
 import { BaseExtractor } from './base.js';
-import type { FileInfo, ExtractedContext, Symbol, CallReference } from '../types/index.js';
+import { GrammarLoader } from './grammars.js';
+import type { FileInfo, ExtractedContext, Symbol, CallReference, SymbolReference, TypeRelationship } from '../types/index.js';
 import type { StructureCategory, SymbolDetail } from './types.js';
-// Note: File path utilities would be used when loading WASM files
-// import { fileURLToPath } from 'url';
-// import { dirname, join } from 'path';
-// const __filename = fileURLToPath(import.meta.url);
-// const __dirname = dirname(__filename);
+
+// Member accesses recorded as `object.field`
+const SELECTOR_TYPES = ['selector_expression', 'field_expression', 'field_access', 'member_expression'];
+const ASSIGNMENT_TYPES = ['assignment_expression', 'assignment_statement', 'assignment', 'short_var_declaration'];
+// Subtrees that never hold references
+const SKIPPED_TYPES = ['comment', 'package_clause', 'package_declaration', 'string_literal', 'interpreted_string_literal', 'raw_string_literal'];
 
 // Language configurations
 const LANGUAGE_CONFIG: Record<string, {
@@ -63,7 +65,7 @@ const LANGUAGE_CONFIG: Record<string, {
     wasmPath: 'tree-sitter-rust.wasm',
     queries: {
       functions: ['function_item', 'function_signature_item'],
-      classes: ['struct_item', 'enum_item', 'trait_item', 'impl_item'],
+      classes: ['struct_item', 'enum_item', 'trait_item'],
       methods: ['function_item'],
       imports: ['use_declaration'],
       calls: ['call_expression']
@@ -112,10 +114,12 @@ const LANGUAGE_CONFIG: Record<string, {
 };
 
 export class TreeSitterExtractor extends BaseExtractor {
-  private parser: Parser | null = null;
-  private static initialized = false;
-  private static parsers: Map<string, Parser> = new Map();
-  private static languages: Map<string, Language> = new Map();
+  private projectRoot: string;
+
+  constructor(projectRoot: string) {
+    super();
+    this.projectRoot = projectRoot;
+  }
   
   getSupportedLanguages(): string[] {
     return Object.keys(LANGUAGE_CONFIG);
@@ -131,6 +135,11 @@ export class TreeSitterExtractor extends BaseExtractor {
   }
   
   async extract(fileInfo: FileInfo): Promise<ExtractedContext> {
+    // Load the parser before setting any per-file state: other files handled
+    // by this extractor can run while we wait for it
+    const parser = fileInfo.language && LANGUAGE_CONFIG[fileInfo.language]
+      ? await this.loadParser(fileInfo.language)
+      : null;
     this.initialize(fileInfo);
     
     const context: ExtractedContext = {
@@ -148,17 +157,15 @@ export class TreeSitterExtractor extends BaseExtractor {
     }
     
     try {
-      // Initialize parser for this language
-      await this.initializeParser(fileInfo.language);
-      
-      if (!this.parser) {
-        return context;
+      if (!parser) {
+        // No grammar available for this language - use the regex patterns
+        return this.extractBasic(fileInfo);
       }
       
       // Parse the content
-      const tree = this.parser.parse(this.content);
+      const tree = parser.parse(this.content);
       if (!tree) {
-        return context;
+        return this.extractBasic(fileInfo);
       }
       
       const rootNode = tree.rootNode;
@@ -166,15 +173,18 @@ export class TreeSitterExtractor extends BaseExtractor {
       // Extract symbols based on language configuration
       const config = LANGUAGE_CONFIG[fileInfo.language];
       
-      // Extract functions
-      this.extractNodesByTypes(rootNode, config.queries.functions, 'function', context.symbols);
+      // Extract functions and methods in one pass; a function nested inside a
+      // class-like node is a method
+      const functionTypes = [...new Set([...config.queries.functions, ...config.queries.methods])];
+      this.extractFunctions(rootNode, functionTypes, config.queries.classes, context.symbols);
       
       // Extract classes and types
       this.extractNodesByTypes(rootNode, config.queries.classes, 'class', context.symbols);
-      
-      // Extract methods (if separate from functions)
-      if (config.queries.methods.length > 0) {
-        this.extractNodesByTypes(rootNode, config.queries.methods, 'method', context.symbols);
+
+      // Go has no inheritance; embedded structs and interfaces play that role
+      if (fileInfo.language === 'go') {
+        context.relationships = [];
+        this.extractGoEmbedding(rootNode, context.relationships);
       }
       
       // Extract imports
@@ -182,6 +192,10 @@ export class TreeSitterExtractor extends BaseExtractor {
       
       // Extract function calls
       this.extractCalls(rootNode, config.queries.calls, context.calls);
+
+      // Extract type names, reads and writes
+      context.references = [];
+      this.extractReferences(rootNode, config.queries, new Set(), context.references);
       
       // Extract comments
       this.extractTreeSitterComments(rootNode, context.comments);
@@ -199,48 +213,94 @@ export class TreeSitterExtractor extends BaseExtractor {
     return context;
   }
   
-  private async initializeParser(language: string): Promise<void> {
-    // Check if we already have a parser for this language
-    if (TreeSitterExtractor.parsers.has(language)) {
-      this.parser = TreeSitterExtractor.parsers.get(language)!;
-      return;
+  private async loadParser(language: string): Promise<Parser | null> {
+    try {
+      return await GrammarLoader.getParser(LANGUAGE_CONFIG[language].wasmPath, this.projectRoot);
+    } catch {
+      // Silently fail - the regex patterns will handle this file
+      return null;
     }
+  }
+  
+  private extractFunctions(node: Node, functionTypes: string[], classTypes: string[], symbols: Symbol[]): void {
+    if (!node) return;
     
-    try {
-      // Initialize tree-sitter if not done
-      if (!TreeSitterExtractor.initialized) {
-        await Parser.init();
-        TreeSitterExtractor.initialized = true;
+    if (functionTypes.includes(node.type) && this.isFunctionNode(node)) {
+      const symbolType: Symbol['type'] = this.hasAncestorOfType(node, classTypes) ? 'method' : 'function';
+      const symbol = this.nodeToSymbol(node, symbolType);
+      if (symbol) {
+        symbols.push(symbol);
       }
-      
-      // Create new parser instance
-      const parser = new Parser();
-      
-      // Try to load the language if we have it cached
-      if (TreeSitterExtractor.languages.has(language)) {
-        const lang = TreeSitterExtractor.languages.get(language)!;
-        parser.setLanguage(lang);
-        TreeSitterExtractor.parsers.set(language, parser);
-        this.parser = parser;
-        return;
+    }
+    
+    for (let i = 0; i < node.childCount; i++) {
+      const child = node.child(i);
+      if (child) {
+        this.extractFunctions(child, functionTypes, classTypes, symbols);
       }
-      
-      // For now, we'll skip loading WASM files as they need to be downloaded
-      // In a production setup, you would:
-      // 1. Download language-specific WASM files from tree-sitter repos
-      // 2. Place them in a specific directory
-      // 3. Load them here with Parser.Language.load()
-      
-      // Since we don't have the WASM files, we'll return null
-      // This allows the fallback extractors to handle the file
-      this.parser = null;
-      
-    } catch {
-      // Silently fail - other extractors will handle this file
-      this.parser = null;
     }
   }
   
+  private isFunctionNode(node: Node): boolean {
+    // C/C++ `declaration` nodes are only functions when they declare a function
+    if (node.type === 'declaration') {
+      return node.descendantsOfType('function_declarator').length > 0;
+    }
+    return true;
+  }
+  
+  private hasAncestorOfType(node: Node, types: string[]): boolean {
+    let current = node.parent;
+    while (current) {
+      if (types.includes(current.type)) {
+        return true;
+      }
+      current = current.parent;
+    }
+    return false;
+  }
+  
+  /**
+   * Record embedded fields (`type Server struct { *Base }`) and embedded
+   * interfaces (`type ReadWriter interface { Reader }`) as `embeds`
+   */
+  private extractGoEmbedding(root: Node, relationships: TypeRelationship[]): void {
+    for (const spec of root.descendantsOfType('type_spec')) {
+      const name = spec?.childForFieldName('name');
+      const type = spec?.childForFieldName('type');
+      if (!spec || !name || !type) continue;
+
+      const embedded: Node[] = [];
+      if (type.type === 'struct_type') {
+        for (const field of type.descendantsOfType('field_declaration')) {
+          // Embedded fields have a type but no name
+          const fieldType = field?.childForFieldName('type');
+          if (field && fieldType && !field.childForFieldName('name') && field.parent?.parent?.equals(type)) {
+            embedded.push(fieldType);
+          }
+        }
+      } else if (type.type === 'interface_type') {
+        for (const element of type.namedChildren) {
+          if (element && ['type_elem', 'constraint_elem', 'interface_type_name', 'type_identifier', 'qualified_type'].includes(element.type)) {
+            embedded.push(element);
+          }
+        }
+      }
+
+      for (const node of embedded) {
+        const target = this.nodeText(node).replace(/^\*/, '').trim();
+        if (/^[\w.]+$/.test(target)) {
+          relationships.push({
+            source: this.nodeText(name),
+            target,
+            kind: 'embeds',
+            line: spec.startPosition.row + 1
+          });
+        }
+      }
+    }
+  }
+
   private extractNodesByTypes(node: Node, types: string[], symbolType: Symbol['type'], symbols: Symbol[]): void {
     if (!node) return;
     
@@ -348,8 +408,7 @@ export class TreeSitterExtractor extends BaseExtractor {
           calleeName: callName,
           callType: this.determineCallType(node),
           line: node.startPosition.row + 1,
-          column: node.startPosition.column,
-          isExternal: false
+          column: node.startPosition.column
         });
       }
     }
@@ -363,25 +422,130 @@ export class TreeSitterExtractor extends BaseExtractor {
     }
   }
   
+  /**
+   * Record type names, identifier reads and writes and member reads.
+   * Declared names and callees (already recorded as calls) are skipped, as
+   * are imports, which come from the import list.
+   */
+  private extractReferences(
+    node: Node,
+    queries: { imports: string[]; calls: string[] },
+    callees: Set<number>,
+    references: SymbolReference[]
+  ): void {
+    if (queries.imports.includes(node.type) || SKIPPED_TYPES.includes(node.type)) return;
+
+    const parent = node.parent;
+    const isField = (field: string) => parent?.childForFieldName(field)?.equals(node) ?? false;
+    const push = (name: string, kind: SymbolReference['kind']) => {
+      references.push({ name, kind, line: node.startPosition.row + 1, column: node.startPosition.column });
+    };
+    const isWrite = () => parent !== null && (
+      (ASSIGNMENT_TYPES.includes(parent.type) && isField('left')) ||
+      (parent.type === 'expression_list' && ASSIGNMENT_TYPES.includes(parent.parent?.type || '') &&
+        parent.parent?.childForFieldName('left')?.equals(parent) === true)
+    );
+
+    if (queries.calls.includes(node.type)) {
+      const target = node.childForFieldName('function') || node.childForFieldName('method') || this.firstCallTarget(node);
+      if (target) callees.add(target.id);
+    }
+
+    switch (node.type) {
+      case 'qualified_type':
+      case 'scoped_type_identifier':
+        // `pkg.Type`, `java.util.List`
+        push(this.nodeText(node).replace(/\s+/g, ''), 'type');
+        return;
+      case 'type_identifier':
+        if (!isField('name')) push(this.nodeText(node), 'type');
+        break;
+      case 'identifier':
+      case 'simple_identifier': {
+        const declared = isField('name') || isField('declarator');
+        const member = parent !== null && SELECTOR_TYPES.includes(parent.type) && isField('field');
+        if (!declared && !member && !callees.has(node.id)) {
+          push(this.nodeText(node), isWrite() ? 'write' : 'read');
+        }
+        break;
+      }
+      default:
+        if (SELECTOR_TYPES.includes(node.type) && !callees.has(node.id)) {
+          const field = node.childForFieldName('field') || node.childForFieldName('property');
+          const operand = node.childForFieldName('operand') || node.childForFieldName('argument') || node.childForFieldName('object');
+          if (field) {
+            const object = operand && /^[\w.]+$/.test(this.nodeText(operand)) ? this.nodeText(operand) : null;
+            push(object ? `${object}.${this.nodeText(field)}` : this.nodeText(field), isWrite() ? 'write' : 'read');
+          }
+        }
+    }
+
+    for (const child of node.namedChildren) {
+      if (child) this.extractReferences(child, queries, callees, references);
+    }
+  }
+
   private extractCallName(node: Node): string | null {
-    // Look for function/method name in call expression
+    // Java-style invocations carry the receiver and method name as fields
+    const nameField = node.childForFieldName('name');
+    if (nameField && node.type === 'method_invocation') {
+      const objectField = node.childForFieldName('object');
+      const objectName = objectField ? this.findLastIdentifier(objectField) : null;
+      const methodName = this.nodeText(nameField);
+      return objectName ? `${this.nodeText(objectName)}.${methodName}` : methodName;
+    }
+    
+    // Constructor calls name the type being created
+    const typeField = node.childForFieldName('type');
+    if (typeField && (node.type === 'object_creation_expression' || node.type === 'new_expression')) {
+      return this.nodeText(typeField);
+    }
+    
+    const target = node.childForFieldName('function') || node.childForFieldName('method') || this.firstCallTarget(node);
+    if (!target) {
+      return null;
+    }
+    
+    switch (target.type) {
+      case 'identifier':
+      case 'field_identifier':
+      case 'constant':
+        return this.nodeText(target);
+      case 'scoped_identifier':
+      case 'qualified_identifier':
+        // Keep paths like `Config::new` intact
+        return this.nodeText(target).replace(/\s+/g, '');
+      case 'field_expression':
+      case 'selector_expression':
+      case 'member_expression': {
+        // Handle method calls like obj.method()
+        const fieldNode = target.childForFieldName('field') || target.childForFieldName('property');
+        const objectNode = target.childForFieldName('argument') || target.childForFieldName('operand') || target.childForFieldName('object');
+        const fieldName = fieldNode ? this.nodeText(fieldNode) : this.nodeText(this.findLastIdentifier(target) || target);
+        const objectName = objectNode ? this.findLastIdentifier(objectNode) : null;
+        return objectName ? `${this.nodeText(objectName)}.${fieldName}` : fieldName;
+      }
+      default: {
+        const identifier = this.findLastIdentifier(target);
+        return identifier ? this.nodeText(identifier) : null;
+      }
+    }
+  }
+  
+  private firstCallTarget(node: Node): Node | null {
     for (let i = 0; i < node.childCount; i++) {
       const child = node.child(i);
-      if (child && (child.type === 'identifier' || child.type === 'field_expression')) {
-        if (child.type === 'identifier') {
-          return this.content.substring(child.startIndex, child.endIndex);
-        } else if (child.type === 'field_expression') {
-          // Handle method calls like obj.method()
-          const fieldName = this.findLastIdentifier(child);
-          if (fieldName) {
-            return this.content.substring(fieldName.startIndex, fieldName.endIndex);
-          }
-        }
+      if (child && (child.type === 'identifier' || child.type === 'field_expression' || child.type === 'selector_expression')) {
+        return child;
       }
     }
     return null;
   }
   
+  private nodeText(node: Node): string {
+    return this.content.substring(node.startIndex, node.endIndex);
+  }
+  
   private findLastIdentifier(node: Node): Node | null {
     const lastIdentifier = null;
     
@@ -405,10 +569,14 @@ export class TreeSitterExtractor extends BaseExtractor {
       return 'constructor';
     }
     
+    if (node.type === 'method_invocation' || node.type === 'member_call_expression' || node.type === 'method_call') {
+      return 'method';
+    }
+    
     // Check if it's a method call
     for (let i = 0; i < node.childCount; i++) {
       const child = node.child(i);
-      if (child && (child.type === 'field_expression' || child.type === 'member_expression')) {
+      if (child && (child.type === 'field_expression' || child.type === 'member_expression' || child.type === 'selector_expression')) {
         return 'method';
       }
     }
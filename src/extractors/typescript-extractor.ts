@@ -5,10 +5,9 @@ import type { NodePath } from '@babel/traverse';
 // eslint-disable-next-line @typescript-eslint/no-explicit-any
 const traverse = (_traverse as any)?.default || _traverse;
 import { BaseExtractor } from './base.js';
-import type { FileInfo, ExtractedContext, Symbol, CallReference } from '../types/index.js';
+import type { FileInfo, ExtractedContext, Symbol, CallReference, SymbolReference, TypeRelationship } from '../types/index.js';
 import type { 
   BabelNode, 
-  BabelIdentifier, 
   BabelClassMember, 
   BabelTSNode, 
   BabelTSPropertySignature,
@@ -18,6 +17,23 @@ import type {
   StructureCategory
 } from './types.js';
 
+// Parents whose identifiers are recorded as a whole, as a type name or export
+const NAME_PARENTS = new Set([
+  'TSTypeReference', 'TSQualifiedName', 'TSExpressionWithTypeArguments',
+  'TSClassImplements', 'TSInterfaceHeritage', 'ExportSpecifier'
+]);
+const CALL_PARENTS = new Set(['CallExpression', 'OptionalCallExpression', 'NewExpression']);
+
+// Globals provided by JS runtimes rather than the project
+const RUNTIME_GLOBALS = new Set([
+  'console', 'Math', 'JSON', 'Object', 'Array', 'String', 'Number', 'Boolean', 'Symbol', 'BigInt',
+  'Promise', 'Date', 'RegExp', 'Error', 'TypeError', 'RangeError', 'Map', 'Set', 'WeakMap', 'WeakSet',
+  'Reflect', 'Proxy', 'Intl', 'URL', 'URLSearchParams', 'parseInt', 'parseFloat', 'isNaN', 'isFinite',
+  'encodeURIComponent', 'decodeURIComponent', 'setTimeout', 'setInterval', 'clearTimeout', 'clearInterval',
+  'setImmediate', 'queueMicrotask', 'structuredClone', 'fetch', 'require', 'process', 'Buffer',
+  'globalThis', 'window', 'document', 'navigator', 'atob', 'btoa'
+]);
+
 export class TypeScriptExtractor extends BaseExtractor {
   getSupportedLanguages(): string[] {
     return ['typescript', 'javascript', 'jsx', 'tsx'];
@@ -38,10 +54,13 @@ export class TypeScriptExtractor extends BaseExtractor {
     const context: ExtractedContext = {
       symbols: [],
       imports: [],
+      importBindings: [],
       exports: [],
       dependencies: [],
       comments: [],
       calls: [],
+      references: [],
+      relationships: [],
       structure: {}
     };
     
@@ -68,6 +87,9 @@ export class TypeScriptExtractor extends BaseExtractor {
       
       // Traverse AST
       traverse(ast, {
+        enter: (path: NodePath) => {
+          this.extractReference(path, context.references!);
+        },
         FunctionDeclaration: (path: NodePath) => {
           this.extractFunction(path.node as unknown as BabelNode, context.symbols);
         },
@@ -82,15 +104,15 @@ export class TypeScriptExtractor extends BaseExtractor {
           }
         },
         ClassDeclaration: (path: NodePath) => {
-          this.extractClass(path.node as unknown as BabelNode, context.symbols);
+          this.extractClass(path.node as unknown as BabelNode, context.symbols, context.relationships!);
         },
         ClassExpression: (path: NodePath) => {
           if (path.parent.type === 'VariableDeclarator' && path.parent.id.type === 'Identifier') {
-            this.extractClass(path.node as unknown as BabelNode, context.symbols, path.parent.id.name);
+            this.extractClass(path.node as unknown as BabelNode, context.symbols, context.relationships!, path.parent.id.name);
           }
         },
         TSInterfaceDeclaration: (path: NodePath) => {
-          this.extractInterface(path.node as unknown as BabelTSNode, context.symbols);
+          this.extractInterface(path.node as unknown as BabelTSNode, context.symbols, context.relationships!);
         },
         TSTypeAliasDeclaration: (path: NodePath) => {
           this.extractTypeAlias(path.node as unknown as BabelTSNode, context.symbols);
@@ -105,6 +127,11 @@ export class TypeScriptExtractor extends BaseExtractor {
           if (source) {
             context.imports.push(source);
             context.dependencies.push(source);
+            context.importBindings!.push({
+              source,
+              names: this.importedNames(node.specifiers || []),
+              line: node.loc?.start.line
+            });
           }
         },
         ExportNamedDeclaration: (path: NodePath) => {
@@ -123,15 +150,41 @@ export class TypeScriptExtractor extends BaseExtractor {
               }
             });
           }
+          // Re-exports (`export { x } from './y'`) are recorded as imports too
+          if (node.source?.value) {
+            context.imports.push(node.source.value);
+            context.dependencies.push(node.source.value);
+            context.importBindings!.push({
+              source: node.source.value,
+              names: this.importedNames(node.specifiers || []),
+              line: node.loc?.start.line,
+              reexport: true
+            });
+          }
+        },
+        ExportAllDeclaration: (path: NodePath) => {
+          // eslint-disable-next-line @typescript-eslint/no-explicit-any
+          const node = path.node as any;
+          if (node.source?.value) {
+            context.imports.push(node.source.value);
+            context.dependencies.push(node.source.value);
+            context.exports.push(node.exported?.name || '*');
+            context.importBindings!.push({
+              source: node.source.value,
+              names: ['*'],
+              line: node.loc?.start.line,
+              reexport: true
+            });
+          }
         },
         ExportDefaultDeclaration: () => {
           context.exports.push('default');
         },
         CallExpression: (path: NodePath) => {
-          this.extractCall(path.node as unknown as BabelNode, context.calls);
+          this.extractCall(path, context.calls);
         },
         NewExpression: (path: NodePath) => {
-          this.extractNewExpression(path.node as unknown as BabelNode, context.calls);
+          this.extractNewExpression(path, context.calls);
         }
       });
       
@@ -206,7 +259,7 @@ export class TypeScriptExtractor extends BaseExtractor {
     });
   }
   
-  private extractClass(node: BabelNode, symbols: Symbol[], name?: string): void {
+  private extractClass(node: BabelNode, symbols: Symbol[], relationships: TypeRelationship[], name?: string): void {
     const className = name || node.id?.name;
     if (!className) return;
     
@@ -215,8 +268,19 @@ export class TypeScriptExtractor extends BaseExtractor {
     
     let signature = `class ${className}`;
     if (node.superClass) {
-      const superName = node.superClass.type === 'Identifier' ? node.superClass.name : 'unknown';
+      const superName = this.heritageName(node.superClass) || 'unknown';
       signature += ` extends ${superName}`;
+      if (superName !== 'unknown') {
+        relationships.push({ source: className, target: superName, kind: 'extends', line: lineStart });
+      }
+    }
+
+    const implemented = ((node.implements as BabelTSExpressionWithTypeArguments[] | undefined) || [])
+      .map(clause => this.heritageName(clause.expression))
+      .filter((target): target is string => target !== null);
+    implemented.forEach(target => relationships.push({ source: className, target, kind: 'implements', line: lineStart }));
+    if (implemented.length > 0) {
+      signature += ` implements ${implemented.join(', ')}`;
     }
     
     const methods: string[] = [];
@@ -266,17 +330,18 @@ export class TypeScriptExtractor extends BaseExtractor {
     });
   }
   
-  private extractInterface(node: BabelTSNode, symbols: Symbol[]): void {
+  private extractInterface(node: BabelTSNode, symbols: Symbol[], relationships: TypeRelationship[]): void {
     const name = node.id.name;
     const lineStart = node.loc?.start.line || 1;
     const lineEnd = node.loc?.end.line || lineStart;
     
     let signature = `interface ${name}`;
     if (node.extends && node.extends.length > 0) {
-      const extendsList = node.extends.map((e: BabelTSExpressionWithTypeArguments) => 
-        (e.expression as BabelIdentifier).name
-      ).join(', ');
-      signature += ` extends ${extendsList}`;
+      const extendsList = node.extends
+        .map((e: BabelTSExpressionWithTypeArguments) => this.heritageName(e.expression))
+        .filter((target): target is string => target !== null);
+      extendsList.forEach(target => relationships.push({ source: name, target, kind: 'extends', line: lineStart }));
+      signature += ` extends ${extendsList.join(', ')}`;
     }
     
     const interfaceBody = node.body as { body: BabelTSPropertySignature[] };
@@ -339,7 +404,123 @@ export class TypeScriptExtractor extends BaseExtractor {
     });
   }
   
-  private extractCall(node: BabelNode, calls: CallReference[]): void {
+  /**
+   * Record non-call references: identifier reads and writes, member reads,
+   * type names, JSX components and local exports. Calls are recorded by
+   * `extractCall`, imported names from the import bindings.
+   */
+  private extractReference(path: NodePath, references: SymbolReference[]): void {
+    const node = path.node as unknown as BabelNode;
+    const parent = path.parent as unknown as BabelNode | null;
+    const push = (name: string | null | undefined, kind: SymbolReference['kind']) => {
+      if (name && !this.isKeyword(name)) {
+        references.push({ name, kind, line: node.loc?.start.line || 1, column: node.loc?.start.column || 0 });
+      }
+    };
+    const isCallee = parent !== null && CALL_PARENTS.has(parent.type) && path.key === 'callee';
+    const isWrite = parent !== null && (
+      (parent.type === 'AssignmentExpression' && path.key === 'left') || parent.type === 'UpdateExpression'
+    );
+
+    switch (node.type) {
+      case 'Identifier':
+        if (!parent || isCallee || NAME_PARENTS.has(parent.type) || !path.isReferencedIdentifier()) return;
+        // Object keys, class members and enum members are declarations, not references
+        if ((path.key === 'key' && !parent.computed) || parent.type === 'TSEnumMember') return;
+        push(node.name, isWrite ? 'write' : path.key === 'superClass' ? 'type' : 'read');
+        break;
+      case 'MemberExpression':
+      case 'OptionalMemberExpression': {
+        if (isCallee || node.computed) return;
+        const property = this.heritageName(node.property);
+        const object = node.object?.type === 'ThisExpression' ? 'this' : this.heritageName(node.object);
+        if (property) {
+          push(object ? `${object}.${property}` : property, isWrite ? 'write' : path.key === 'superClass' ? 'type' : 'read');
+        }
+        break;
+      }
+      case 'TSTypeReference':
+        push(this.heritageName(node.typeName as BabelNode), 'type');
+        break;
+      case 'TSExpressionWithTypeArguments':
+      case 'TSClassImplements':
+      case 'TSInterfaceHeritage':
+        push(this.heritageName(node.expression), 'type');
+        break;
+      case 'JSXOpeningElement': {
+        // Lowercase tags are intrinsic elements
+        const name = this.jsxName(node.name as BabelNode);
+        if (name && /^[A-Z]|\./.test(name)) push(name, 'read');
+        break;
+      }
+      case 'ImportDefaultSpecifier':
+        push((node.local as BabelNode | undefined)?.name, 'import');
+        break;
+      case 'ExportSpecifier':
+        // Re-exports (`export { x } from`) come from the import bindings
+        if (!(parent?.source)) push((node.local as BabelNode | undefined)?.name, 'export');
+        break;
+    }
+  }
+
+  private jsxName(node: BabelNode | undefined): string | null {
+    if (!node) return null;
+    if (node.type === 'JSXIdentifier') return node.name || null;
+    if (node.type === 'JSXMemberExpression') {
+      const object = this.jsxName(node.object);
+      const property = this.jsxName(node.property);
+      return object && property ? `${object}.${property}` : null;
+    }
+    return null;
+  }
+
+  /**
+   * Name of a supertype reference: `Base`, `ns.Base` or `React.Component`
+   */
+  private heritageName(node: BabelNode | undefined): string | null {
+    if (!node) return null;
+    switch (node.type) {
+      case 'Identifier':
+        return node.name || null;
+      case 'MemberExpression': {
+        const object = this.heritageName(node.object);
+        const property = this.heritageName(node.property);
+        return object && property ? `${object}.${property}` : null;
+      }
+      case 'TSQualifiedName': {
+        const left = this.heritageName(node.left as BabelNode);
+        const right = this.heritageName(node.right as BabelNode);
+        return left && right ? `${left}.${right}` : null;
+      }
+      default:
+        return null;
+    }
+  }
+
+  /**
+   * Names an import or re-export takes from its source module
+   */
+  // eslint-disable-next-line @typescript-eslint/no-explicit-any
+  private importedNames(specifiers: any[]): string[] {
+    return specifiers.map(spec => {
+      switch (spec.type) {
+        case 'ImportDefaultSpecifier':
+        case 'ExportDefaultSpecifier':
+          return 'default';
+        case 'ImportNamespaceSpecifier':
+        case 'ExportNamespaceSpecifier':
+          return '*';
+        case 'ImportSpecifier':
+          return spec.imported?.name ?? spec.imported?.value;
+        default:
+          // ExportSpecifier: `export { local as exported } from`
+          return spec.local?.name ?? spec.local?.value;
+      }
+    }).filter((name): name is string => typeof name === 'string');
+  }
+
+  private extractCall(path: NodePath, calls: CallReference[]): void {
+    const node = path.node as unknown as BabelNode;
     if (!node.callee) return;
     
     let calleeName = '';
@@ -349,7 +530,10 @@ export class TypeScriptExtractor extends BaseExtractor {
       calleeName = node.callee.name || '';
       callType = 'function';
     } else if (node.callee.type === 'MemberExpression') {
-      const obj = node.callee.object && node.callee.object.type === 'Identifier' && node.callee.object.name ? node.callee.object.name : 'unknown';
+      const object = node.callee.object;
+      const obj = object?.type === 'ThisExpression' ? 'this'
+        : object?.type === 'Super' ? 'super'
+        : object?.type === 'Identifier' && object.name ? object.name : 'unknown';
       const prop = node.callee.property && node.callee.property.type === 'Identifier' && node.callee.property.name ? node.callee.property.name : 'unknown';
       calleeName = `${obj}.${prop}`;
       callType = 'method';
@@ -361,12 +545,13 @@ export class TypeScriptExtractor extends BaseExtractor {
         callType,
         line: node.loc?.start.line || 1,
         column: node.loc?.start.column || 0,
-        isExternal: false
+        ...this.callScope(path, node.callee)
       });
     }
   }
   
-  private extractNewExpression(node: BabelNode, calls: CallReference[]): void {
+  private extractNewExpression(path: NodePath, calls: CallReference[]): void {
+    const node = path.node as unknown as BabelNode;
     if (!node.callee) return;
     
     if (node.callee.type === 'Identifier' && node.callee.name) {
@@ -375,10 +560,54 @@ export class TypeScriptExtractor extends BaseExtractor {
         callType: 'constructor',
         line: node.loc?.start.line || 1,
         column: node.loc?.start.column || 0,
-        isExternal: false
+        ...this.callScope(path, node.callee)
       });
     }
   }
+
+  /**
+   * Classify a call by what its receiver or name is bound to: a parameter or
+   * local variable, an import (project or package, settled once imports are
+   * resolved), a declaration in this file, or a runtime global. Receivers
+   * other than `this`, `super` or a plain name can't be bound at all.
+   */
+  private callScope(path: NodePath, callee: BabelNode): Pick<CallReference, 'scope' | 'importSource'> {
+    const root = callee.type === 'MemberExpression' || callee.type === 'OptionalMemberExpression' ? callee.object : callee;
+    // `this.save()`, `super.save()`
+    if (root?.type === 'ThisExpression' || root?.type === 'Super') {
+      return { scope: 'project' };
+    }
+    // `this.items.push()`, `[a].map()`, `make().save()`: nothing says what the receiver is
+    if (!root || root.type !== 'Identifier' || !root.name) {
+      return { scope: 'unknown' };
+    }
+
+    const binding = path.scope.getBinding(root.name);
+    if (!binding) {
+      return { scope: RUNTIME_GLOBALS.has(root.name) ? 'external' : 'project' };
+    }
+    if (binding.kind === 'module') {
+      const source = (binding.path.parent as unknown as BabelNode).source?.value;
+      return source ? { scope: 'project', importSource: source } : { scope: 'project' };
+    }
+    if (binding.kind === 'param') {
+      return { scope: 'local' };
+    }
+
+    // Module-level declarations, nested functions and classes are indexed symbols
+    const declaration = binding.path.node as unknown as BabelNode;
+    const init = binding.path.isVariableDeclarator() ? declaration.init as BabelNode | undefined : undefined;
+    if (
+      binding.scope.path.isProgram() ||
+      binding.kind === 'hoisted' ||
+      binding.path.isClassDeclaration() ||
+      init?.type === 'ArrowFunctionExpression' ||
+      init?.type === 'FunctionExpression'
+    ) {
+      return { scope: 'project' };
+    }
+    return { scope: 'local' };
+  }
   
   private isKeyword(word: string): boolean {
     const keywords = new Set([
@@ -307,6 +307,34 @@ export class GitAnalyzer {
       .sort((a, b) => b.commits.length - a.commits.length);
   }
 
+  /**
+   * Date of the last commit touching each file in the most recent commits,
+   * keyed by path relative to the repository root. Empty outside a git repo.
+   */
+  public getLastCommitDates(commitLimit: number = 500): Map<string, Date> {
+    const dates = new Map<string, Date>();
+    if (!this.isGitRepo) return dates;
+
+    let output = '';
+    try {
+      output = this.execGit(`log --pretty=format:"@%aI" --name-only -n ${commitLimit}`);
+    } catch {
+      return dates;
+    }
+
+    let current: Date | null = null;
+    for (const line of output.split('\n')) {
+      const trimmed = line.trim().replace(/^"|"$/g, '');
+      if (trimmed.startsWith('@')) {
+        current = new Date(trimmed.substring(1));
+      } else if (trimmed && current && !dates.has(trimmed)) {
+        // Log is newest first, so the first date seen is the latest
+        dates.set(trimmed, current);
+      }
+    }
+    return dates;
+  }
+
   public getLastCommitForLine(filePath: string, lineNumber: number): GitCommit | null {
     try {
       const output = this.execGit(`blame -L ${lineNumber},${lineNumber} --line-porcelain "${filePath}"`);
@@ -0,0 +1,566 @@
+import { Indexer } from '../index.js';
+import { PrimordynDB } from '../../database/index.js';
+import { ContextRetriever } from '../../retriever/index.js';
+import { mkdirSync, writeFileSync, rmSync, existsSync, renameSync, unlinkSync } from 'fs';
+import { join } from 'path';
+
+describe('Indexer', () => {
+  const testDir = join(process.cwd(), '.test-indexer');
+  let db: PrimordynDB;
+  let indexer: Indexer;
+
+  const indexedPaths = () => (db.getDatabase()
+    .prepare('SELECT relative_path FROM files ORDER BY relative_path')
+    .all() as { relative_path: string }[]).map(row => row.relative_path);
+
+  beforeEach(() => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(testDir, { recursive: true });
+    writeFileSync(join(testDir, 'a.ts'), 'export function alpha() { return 1; }\n');
+    writeFileSync(join(testDir, 'b.ts'), 'export function beta() { return 2; }\n');
+
+    db = new PrimordynDB(testDir);
+    indexer = new Indexer(db);
+  });
+
+  afterEach(() => {
+    db.close();
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should skip unchanged files on reindex', async () => {
+    const first = await indexer.index({ projectRoot: testDir, verbose: false });
+    const second = await indexer.index({ projectRoot: testDir, verbose: false });
+
+    expect(first.filesIndexed).toBe(2);
+    expect(second.filesIndexed).toBe(0);
+    expect(indexedPaths()).toEqual(['a.ts', 'b.ts']);
+  });
+
+  test('should prune files deleted from disk', async () => {
+    await indexer.index({ projectRoot: testDir, verbose: false });
+    unlinkSync(join(testDir, 'b.ts'));
+
+    const stats = await indexer.index({ projectRoot: testDir, verbose: false });
+
+    expect(stats.filesRemoved).toBe(1);
+    expect(indexedPaths()).toEqual(['a.ts']);
+    const orphans = db.getDatabase().prepare("SELECT COUNT(*) as count FROM symbols WHERE name = 'beta'").get() as { count: number };
+    expect(orphans.count).toBe(0);
+  });
+
+  test('should carry renamed files over by content hash', async () => {
+    await indexer.index({ projectRoot: testDir, verbose: false });
+    const before = db.getDatabase().prepare("SELECT id FROM files WHERE relative_path = 'b.ts'").get() as { id: number };
+    renameSync(join(testDir, 'b.ts'), join(testDir, 'c.ts'));
+
+    const stats = await indexer.index({ projectRoot: testDir, verbose: false });
+
+    expect(stats.filesRenamed).toBe(1);
+    expect(stats.filesRemoved).toBe(0);
+    expect(indexedPaths()).toEqual(['a.ts', 'c.ts']);
+    const after = db.getDatabase().prepare("SELECT id FROM files WHERE relative_path = 'c.ts'").get() as { id: number };
+    expect(after.id).toBe(before.id);
+  });
+
+  test('should re-extract renamed Rust files for their new module path', async () => {
+    mkdirSync(join(testDir, 'src'));
+    writeFileSync(join(testDir, 'src', 'old.rs'), 'pub fn helper() -> u32 {\n    1\n}\n');
+    await indexer.index({ projectRoot: testDir, verbose: false });
+    const fileId = (path: string) => (db.getDatabase().prepare('SELECT id FROM files WHERE relative_path = ?').get(path) as { id: number }).id;
+    const before = fileId(join('src', 'old.rs'));
+    renameSync(join(testDir, 'src', 'old.rs'), join(testDir, 'src', 'new.rs'));
+
+    const stats = await indexer.index({ projectRoot: testDir, verbose: false });
+
+    expect(stats.filesRenamed).toBe(1);
+    expect(fileId(join('src', 'new.rs'))).toBe(before);
+    const symbols = db.getDatabase().prepare(`
+      SELECT name, qualified_name as qualifiedName, json_extract(metadata, '$.module') as module
+      FROM symbols WHERE file_id = ? ORDER BY line_start, type
+    `).all(before);
+    expect(symbols).toEqual([
+      { name: 'helper', qualifiedName: 'crate::new::helper', module: 'crate::new' },
+      { name: 'new', qualifiedName: 'crate::new', module: null }
+    ]);
+  });
+
+  test('should keep files skipped by a language filter', async () => {
+    writeFileSync(join(testDir, 'script.py'), 'def gamma():\n    return 3\n');
+    await indexer.index({ projectRoot: testDir, verbose: false });
+
+    const stats = await indexer.index({ projectRoot: testDir, languages: ['typescript'], verbose: false });
+
+    expect(stats.filesRemoved).toBe(0);
+    expect(indexedPaths()).toContain('script.py');
+  });
+
+  test('should link symbols to their parents with qualified names', async () => {
+    writeFileSync(join(testDir, 'service.ts'), [
+      'export class UserService {',
+      '  login() { return true; }',
+      '  init() { return 1; }',
+      '}',
+      'export class Session {',
+      '  init() { return 2; }',
+      '}',
+      'export function outer() {',
+      '  function inner() { return 3; }',
+      '  return inner();',
+      '}',
+      ''
+    ].join('\n'));
+
+    await indexer.index({ projectRoot: testDir, verbose: false });
+
+    const rows = db.getDatabase().prepare(`
+      SELECT s.qualified_name as qualifiedName, p.qualified_name as parent
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      LEFT JOIN symbols p ON s.parent_symbol_id = p.id
+      WHERE f.relative_path = 'service.ts'
+      ORDER BY s.line_start
+    `).all();
+    expect(rows).toEqual(expect.arrayContaining([
+      { qualifiedName: 'UserService.login', parent: 'UserService' },
+      { qualifiedName: 'UserService.init', parent: 'UserService' },
+      { qualifiedName: 'Session.init', parent: 'Session' },
+      { qualifiedName: 'outer.inner', parent: 'outer' }
+    ]));
+
+    const retriever = new ContextRetriever(db);
+    const inits = await retriever.findSymbol('init');
+    expect(inits.map(symbol => symbol.qualifiedName).sort()).toEqual(['Session.init', 'UserService.init']);
+
+    const service = (await retriever.findSymbol('UserService')).find(symbol => symbol.type === 'class');
+    expect(service?.members?.map(member => member.name)).toEqual(['UserService.login', 'UserService.init']);
+  });
+
+  test('should extract each file of a batch from its own content', async () => {
+    writeFileSync(join(testDir, 'first.py'), 'def first_handler(event):\n    return event\n');
+    writeFileSync(join(testDir, 'second.py'), '\n\nclass SecondModel:\n    def save(self):\n        return True\n');
+    writeFileSync(join(testDir, 'third.rs'), 'pub fn third_step() -> u32 {\n    3\n}\n');
+
+    await indexer.index({ projectRoot: testDir, verbose: false, jobs: 1 });
+
+    const rows = db.getDatabase().prepare(`
+      SELECT f.relative_path as path, s.name, s.line_start as line
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      WHERE f.relative_path IN ('first.py', 'second.py', 'third.rs') AND s.type != 'module'
+      ORDER BY f.relative_path, s.line_start
+    `).all();
+    expect(rows).toEqual([
+      { path: 'first.py', name: 'first_handler', line: 1 },
+      { path: 'second.py', name: 'SecondModel', line: 3 },
+      { path: 'second.py', name: 'save', line: 4 },
+      { path: 'third.rs', name: 'third_step', line: 1 }
+    ]);
+  });
+
+  describe('call linking', () => {
+    const calleeOf = (caller: string) => db.getDatabase().prepare(`
+      SELECT f.relative_path as path, c.resolution_confidence as confidence
+      FROM call_graph c
+      JOIN files cf ON c.caller_file_id = cf.id
+      LEFT JOIN files f ON c.callee_file_id = f.id
+      WHERE cf.relative_path = ?
+    `).get(caller) as { path: string | null; confidence: number | null };
+
+    test('should resolve calls through the imported module regardless of indexing order', async () => {
+      mkdirSync(join(testDir, 'lib'));
+      writeFileSync(join(testDir, 'lib', 'format.ts'), 'export function render() { return 1; }\n');
+      writeFileSync(join(testDir, 'other.ts'), 'export function render() { return 2; }\n');
+      writeFileSync(join(testDir, 'main.ts'), "import { render } from './lib/format.js';\nexport function run() { return render(); }\n");
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      expect(calleeOf('main.ts')).toEqual({ path: join('lib', 'format.ts'), confidence: 1 });
+    });
+
+    test('should replace the calls of a re-indexed file', async () => {
+      const source = "import { alpha } from './a';\nalpha();\nexport function run() { return alpha(); }\n";
+      writeFileSync(join(testDir, 'main.ts'), source);
+      const callCount = () => (db.getDatabase().prepare(`
+        SELECT COUNT(*) as count FROM call_graph c JOIN files f ON c.caller_file_id = f.id WHERE f.relative_path = 'main.ts'
+      `).get() as { count: number }).count;
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+      const before = callCount();
+      writeFileSync(join(testDir, 'main.ts'), `// changed\n${source}`);
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      // Including the top-level call, which belongs to no symbol
+      expect(before).toBe(2);
+      expect(callCount()).toBe(before);
+    });
+
+    test('should follow re-exports', async () => {
+      mkdirSync(join(testDir, 'lib'));
+      writeFileSync(join(testDir, 'lib', 'format.ts'), 'export function render() { return 1; }\n');
+      writeFileSync(join(testDir, 'lib', 'index.ts'), "export { render } from './format';\n");
+      writeFileSync(join(testDir, 'other.ts'), 'export function render() { return 2; }\n');
+      writeFileSync(join(testDir, 'main.ts'), "import { render } from './lib';\nexport function run() { return render(); }\n");
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      expect(calleeOf('main.ts').path).toBe(join('lib', 'format.ts'));
+    });
+
+    test('should mark ambiguous matches with a lower confidence', async () => {
+      mkdirSync(join(testDir, 'x'));
+      mkdirSync(join(testDir, 'y'));
+      writeFileSync(join(testDir, 'x', 'util.ts'), 'export function shared() { return 1; }\n');
+      writeFileSync(join(testDir, 'y', 'util.ts'), 'export function shared() { return 2; }\n');
+      writeFileSync(join(testDir, 'main.ts'), 'export function run() { return shared(); }\n');
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      expect(calleeOf('main.ts').confidence).toBeLessThan(1);
+    });
+
+    test('should only link calls to project code', async () => {
+      writeFileSync(join(testDir, 'store.ts'), [
+        'export function push() { return 1; }',
+        'export function log() { return 2; }',
+        'export function parse() { return 3; }',
+        'export function save() { return 4; }',
+        ''
+      ].join('\n'));
+      writeFileSync(join(testDir, 'main.ts'), [
+        "import { parse } from 'yaml';",
+        "import { save } from './store';",
+        'export function run(items: number[]) {',
+        '  items.push(1);',
+        "  console.log('x');",
+        "  parse('a: 1');",
+        '  save();',
+        '}',
+        ''
+      ].join('\n'));
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      const calls = db.getDatabase().prepare(`
+        SELECT c.callee_name as name, c.scope, f.relative_path as target
+        FROM call_graph c
+        LEFT JOIN files f ON c.callee_file_id = f.id
+        ORDER BY c.line_number
+      `).all();
+      expect(calls).toEqual([
+        { name: 'items.push', scope: 'local', target: null },
+        { name: 'console.log', scope: 'external', target: null },
+        { name: 'parse', scope: 'external', target: null },
+        { name: 'save', scope: 'project', target: 'store.ts' }
+      ]);
+    });
+
+    test('should not link calls on receivers of unknown type', async () => {
+      writeFileSync(join(testDir, 'list.ts'), [
+        'export class List {',
+        '  items: number[] = [];',
+        '  push(item: number) { return item; }',
+        '  map(f: (item: number) => number) { return f; }',
+        '  add() {',
+        '    this.items.push(1);',
+        '    [1].map(f => f);',
+        '    this.push(2);',
+        '  }',
+        '}',
+        ''
+      ].join('\n'));
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      const calls = db.getDatabase().prepare(`
+        SELECT c.callee_name as name, c.scope, s.name as target
+        FROM call_graph c
+        LEFT JOIN symbols s ON c.callee_symbol_id = s.id
+        ORDER BY c.line_number
+      `).all();
+      expect(calls).toEqual([
+        { name: 'unknown.push', scope: 'unknown', target: null },
+        { name: 'unknown.map', scope: 'unknown', target: null },
+        { name: 'this.push', scope: 'project', target: 'push' }
+      ]);
+    });
+  });
+
+  describe('import resolution', () => {
+    const targetsOf = (file: string) => (db.getDatabase().prepare(`
+      SELECT i.specifier, t.relative_path as target
+      FROM imports i
+      JOIN files f ON i.file_id = f.id
+      LEFT JOIN files t ON i.target_file_id = t.id
+      WHERE f.relative_path = ?
+      ORDER BY i.id
+    `).all(file) as { specifier: string; target: string | null }[]).map(row => [row.specifier, row.target]);
+
+    test('should resolve relative imports and tsconfig path aliases', async () => {
+      mkdirSync(join(testDir, 'src', 'lib'), { recursive: true });
+      writeFileSync(join(testDir, 'tsconfig.json'), '{\n  // aliases\n  "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["src/*"] }, },\n}\n');
+      writeFileSync(join(testDir, 'src', 'lib', 'index.ts'), 'export const value = 1;\n');
+      writeFileSync(join(testDir, 'src', 'util.ts'), 'export const other = 2;\n');
+      writeFileSync(join(testDir, 'src', 'main.ts'), "import { value } from '@/lib';\nimport { other } from './util.js';\nimport chalk from 'chalk';\n");
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      expect(targetsOf(join('src', 'main.ts'))).toEqual([
+        ['@/lib', join('src', 'lib', 'index.ts')],
+        ['./util.js', join('src', 'util.ts')],
+        ['chalk', null]
+      ]);
+
+      // The tsconfig is found from the index's project root, not from stored file paths
+      db.getDatabase().prepare("UPDATE files SET path = '/moved/' || relative_path").run();
+      indexer.linkCalls();
+      expect(targetsOf(join('src', 'main.ts'))[0]).toEqual(['@/lib', join('src', 'lib', 'index.ts')]);
+
+      const related = await new ContextRetriever(db).getRelatedFiles(join('src', 'main.ts'));
+      expect(related.map(file => file.relativePath)).toEqual([join('src', 'lib', 'index.ts'), join('src', 'util.ts')]);
+    });
+
+    test('should resolve workspace packages through package.json exports and main', async () => {
+      mkdirSync(join(testDir, 'packages', 'db', 'src'), { recursive: true });
+      mkdirSync(join(testDir, 'packages', 'ui', 'src'), { recursive: true });
+      writeFileSync(join(testDir, 'packages', 'db', 'package.json'), JSON.stringify({
+        name: '@app/db',
+        exports: { '.': { import: './dist/index.js', source: './src/index.ts' }, './schema/*': './src/schema/*.ts' }
+      }));
+      mkdirSync(join(testDir, 'packages', 'db', 'src', 'schema'));
+      writeFileSync(join(testDir, 'packages', 'db', 'src', 'index.ts'), 'export function connect() { return 1; }\n');
+      writeFileSync(join(testDir, 'packages', 'db', 'src', 'schema', 'user.ts'), 'export interface User { id: number }\n');
+      writeFileSync(join(testDir, 'packages', 'ui', 'package.json'), JSON.stringify({ name: '@app/ui', main: 'dist/index.js' }));
+      writeFileSync(join(testDir, 'packages', 'ui', 'src', 'index.ts'), 'export const theme = {};\n');
+      writeFileSync(join(testDir, 'main.ts'), [
+        "import { connect } from '@app/db';",
+        "import type { User } from '@app/db/schema/user';",
+        "import { theme } from '@app/ui';",
+        ''
+      ].join('\n'));
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      expect(targetsOf('main.ts')).toEqual([
+        ['@app/db', join('packages', 'db', 'src', 'index.ts')],
+        ['@app/db/schema/user', join('packages', 'db', 'src', 'schema', 'user.ts')],
+        ['@app/ui', join('packages', 'ui', 'src', 'index.ts')]
+      ]);
+
+      const graph = await new ContextRetriever(db).getDependencyGraph('connect');
+      expect(graph?.importedBy.map(edge => edge.filePath)).toEqual(['main.ts']);
+    });
+
+    test('should resolve Python package imports', async () => {
+      mkdirSync(join(testDir, 'app', 'models'), { recursive: true });
+      writeFileSync(join(testDir, 'app', '__init__.py'), '');
+      writeFileSync(join(testDir, 'app', 'models', '__init__.py'), '');
+      writeFileSync(join(testDir, 'app', 'models', 'user.py'), 'class User:\n    pass\n');
+      writeFileSync(join(testDir, 'app', 'main.py'), 'from app.models.user import User\nfrom .models import user\n');
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      expect(targetsOf(join('app', 'main.py'))).toEqual([
+        ['app.models.user', join('app', 'models', 'user.py')],
+        ['.models', join('app', 'models', '__init__.py')]
+      ]);
+    });
+
+    test('should qualify Python symbols by package under src/ and namespace layouts', async () => {
+      mkdirSync(join(testDir, 'src', 'shop', 'models'), { recursive: true });
+      mkdirSync(join(testDir, 'plugins', 'pay'), { recursive: true });
+      writeFileSync(join(testDir, 'src', 'shop', '__init__.py'), '');
+      writeFileSync(join(testDir, 'src', 'shop', 'models', '__init__.py'), '');
+      writeFileSync(join(testDir, 'src', 'shop', 'models', 'cart.py'), [
+        'class Cart:',
+        '    def total(self):',
+        '        return 0',
+        ''
+      ].join('\n'));
+      writeFileSync(join(testDir, 'plugins', 'pay', 'stripe.py'), 'def charge(cart):\n    return cart.total()\n');
+      writeFileSync(join(testDir, 'main.py'), 'from shop.models.cart import Cart\nfrom plugins.pay import stripe\n');
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      expect(targetsOf('main.py')).toEqual([
+        ['shop.models.cart', join('src', 'shop', 'models', 'cart.py')],
+        ['plugins.pay', join('plugins', 'pay', 'stripe.py')]
+      ]);
+
+      const retriever = new ContextRetriever(db);
+      const [method] = await retriever.findSymbol('shop.models.cart.Cart.total');
+      expect(method).toMatchObject({ name: 'total', qualifiedName: 'shop.models.cart.Cart.total' });
+      expect((await retriever.findSymbol('plugins.pay.stripe.charge'))[0]?.filePath).toBe(join('plugins', 'pay', 'stripe.py'));
+    });
+  });
+
+  describe('type hierarchy', () => {
+    test('should link extends and implements across files', async () => {
+      writeFileSync(join(testDir, 'base.ts'), 'export class Base {}\nexport interface Service { run(): void }\n');
+      writeFileSync(join(testDir, 'app.ts'), [
+        "import { Base, Service } from './base.js';",
+        'export class App extends Base implements Service {',
+        '  run() {}',
+        '}',
+        ''
+      ].join('\n'));
+      writeFileSync(join(testDir, 'admin.ts'), "import { App } from './app.js';\nexport class Admin extends App {}\n");
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      const retriever = new ContextRetriever(db);
+      const app = await retriever.getTypeHierarchy('App');
+      expect(app?.supertypes.map(node => [node.kind, node.name, node.filePath])).toEqual([
+        ['extends', 'Base', 'base.ts'],
+        ['implements', 'Service', 'base.ts']
+      ]);
+
+      const base = await retriever.getTypeHierarchy('Base');
+      expect(base?.subtypes).toMatchObject([
+        { kind: 'extends', name: 'App', filePath: 'app.ts', children: [{ kind: 'extends', name: 'Admin', filePath: 'admin.ts' }] }
+      ]);
+      expect((await retriever.getTypeHierarchy('Service'))?.implementors.map(node => node.name)).toEqual(['App']);
+    });
+  });
+
+  describe('references', () => {
+    test('should record type, read and import references with their enclosing symbol', async () => {
+      writeFileSync(join(testDir, 'config.ts'), 'export interface Config { port: number }\nexport const defaults: Config = { port: 80 };\n');
+      writeFileSync(join(testDir, 'server.ts'), [
+        "import { Config, defaults } from './config.js';",
+        'export function listen(config: Config = defaults) {',
+        '  return config.port;',
+        '}',
+        ''
+      ].join('\n'));
+
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      const retriever = new ContextRetriever(db);
+      const [server] = await retriever.findUsages('Config');
+      expect(server.relativePath).toBe('server.ts');
+      expect(server.metadata?.references).toEqual(expect.arrayContaining([
+        expect.objectContaining({ name: 'Config', kind: 'import', line: 1 }),
+        expect.objectContaining({ name: 'Config', kind: 'type', line: 2, symbol: 'listen' })
+      ]));
+
+      const [reads] = await retriever.findUsages('port');
+      expect(reads.metadata?.references).toEqual([
+        expect.objectContaining({ name: 'config.port', kind: 'read', line: 3, symbol: 'listen' })
+      ]);
+    });
+  });
+
+  describe('ranking', () => {
+    beforeEach(() => {
+      writeFileSync(join(testDir, 'parser.ts'), 'export class Parser {\n  parse() { return 1; }\n}\n');
+      writeFileSync(join(testDir, 'consumer.ts'), [
+        "import { Parser } from './parser';",
+        '// Parser, Parser and more Parser',
+        'export function build() { return new Parser(); }',
+        ''
+      ].join('\n'));
+      writeFileSync(join(testDir, 'notes.ts'), "// Parser notes\nexport const label = 'Parser';\n");
+    });
+
+    test('should rank the defining file and symbol first and expose scores', async () => {
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      const result = await new ContextRetriever(db).query('Parser', { sortBy: 'relevance' });
+
+      expect(result.files[0].relativePath).toBe('parser.ts');
+      expect(result.files[0].score).toMatchObject({ definition: 1, centrality: 1 });
+      expect(result.symbols[0]).toMatchObject({ name: 'Parser', type: 'class' });
+      expect(result.symbols[0].score?.exactName).toBe(1);
+
+      const totals = result.files.map(file => file.score!.total);
+      expect(totals).toEqual([...totals].sort((a, b) => b - a));
+    });
+
+    test('should honor the path sort mode', async () => {
+      await indexer.index({ projectRoot: testDir, verbose: false });
+
+      const result = await new ContextRetriever(db).query('Parser', { sortBy: 'path' });
+
+      expect(result.files.map(file => file.relativePath)).toEqual(['consumer.ts', 'notes.ts', 'parser.ts']);
+      expect(result.files.every(file => file.score !== undefined)).toBe(true);
+    });
+
+    test('should apply query operators and filters', async () => {
+      await indexer.index({ projectRoot: testDir, verbose: false });
+      const retriever = new ContextRetriever(db);
+
+      const excluded = await retriever.query('Parser -notes');
+      expect(excluded.files.map(file => file.relativePath).sort()).toEqual(['consumer.ts', 'parser.ts']);
+
+      const prefixed = await retriever.query('Pars* type:method');
+      expect(prefixed.files).toEqual([]);
+      expect(prefixed.symbols.map(symbol => symbol.name)).toEqual(['parse']);
+
+      const filtered = await retriever.query('Parser path:notes');
+      expect(filtered.files.map(file => file.relativePath)).toEqual(['notes.ts']);
+      expect((await retriever.findSymbol('name:Pars* lang:ts')).map(symbol => symbol.name).sort()).toEqual(['Parser', 'parse']);
+    });
+  });
+
+  test('should shorten large results to fit the token budget and report it', async () => {
+    const methods = Array.from({ length: 40 }, (_, i) => `  handle${i}(input: string) {\n    return input.repeat(${i});\n  }`);
+    writeFileSync(join(testDir, 'router.ts'), `export class Router {\n${methods.join('\n')}\n}\n`);
+    await indexer.index({ projectRoot: testDir, verbose: false });
+    const retriever = new ContextRetriever(db);
+
+    const whole = await retriever.query('Router', { includeContent: true, maxTokens: 20000 });
+    expect(whole.files[0]).toMatchObject({ relativePath: 'router.ts', form: 'full' });
+    expect(whole.truncated).toBe(false);
+
+    const packed = await retriever.query('Router', { includeContent: true, maxTokens: 600 });
+    expect(packed.totalTokens).toBeLessThanOrEqual(600);
+    expect(packed.truncated).toBe(true);
+    expect(packed.packing?.reduced.map(item => item.label)).toContain('router.ts');
+    const file = packed.files.find(result => result.relativePath === 'router.ts');
+    expect(file?.form).not.toBe('full');
+    expect(file?.content?.length ?? 0).toBeLessThan(whole.files[0].content!.length);
+  });
+
+  test('should find parts of camelCase and snake_case identifiers', async () => {
+    writeFileSync(join(testDir, 'users.ts'), 'export function getUserById(id: string) { return id; }\n');
+    writeFileSync(join(testDir, 'users.py'), 'def load_user_profile(user_id):\n    return user_id\n');
+    await indexer.index({ projectRoot: testDir, verbose: false });
+    const retriever = new ContextRetriever(db);
+
+    const names = async (term: string) => (await retriever.findSymbol(term)).map(symbol => symbol.name).sort();
+    expect(await names('user')).toEqual(['getUserById', 'load_user_profile']);
+    expect(await names('user_profile')).toEqual(['load_user_profile']);
+    expect(await names('getUser*')).toEqual(['getUserById']);
+    expect(await names('load_user_profile')).toEqual(['load_user_profile']);
+
+    const result = await retriever.query('profile');
+    expect(result.files.map(file => file.relativePath)).toEqual(['users.py']);
+  });
+
+  test('should find symbols despite typos and suggest names', async () => {
+    writeFileSync(join(testDir, 'users.ts'), [
+      'export class UserRepository {',
+      '  findUserById(id: string) { return id; }',
+      '}',
+      ''
+    ].join('\n'));
+    await indexer.index({ projectRoot: testDir, verbose: false });
+    const retriever = new ContextRetriever(db);
+
+    const [best] = retriever.findSymbolsFuzzy('findUsrById');
+    expect(best).toMatchObject({ name: 'findUserById', type: 'method', filePath: 'users.ts' });
+    expect(best.distance).toBe(1);
+
+    expect(retriever.findSymbolsFuzzy('UserRepostory', { types: ['function'] })).toEqual([]);
+    const [withContent] = retriever.findSymbolsFuzzy('UserRepostory', { types: ['class'], includeContent: true });
+    expect(withContent.content).toContain('findUserById');
+
+    expect(retriever.suggestNames('UserRepostory')).toEqual(['UserRepository']);
+    expect(retriever.suggestNames('UserRepository')).toEqual([]);
+  });
+});
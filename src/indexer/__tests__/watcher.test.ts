@@ -0,0 +1,100 @@
+import { Indexer } from '../index.js';
+import { IndexWatcher } from '../watcher.js';
+import type { WatchUpdate } from '../watcher.js';
+import { PrimordynDB } from '../../database/index.js';
+import { mkdirSync, writeFileSync, rmSync, existsSync, unlinkSync } from 'fs';
+import { join } from 'path';
+
+describe('IndexWatcher', () => {
+  const testDir = join(process.cwd(), '.test-watcher');
+  let db: PrimordynDB;
+  let indexer: Indexer;
+  let watcher: IndexWatcher;
+  let updates: WatchUpdate[];
+
+  const indexedPaths = () => (db.getDatabase()
+    .prepare('SELECT relative_path FROM files ORDER BY relative_path')
+    .all() as { relative_path: string }[]).map(row => row.relative_path);
+  const symbolId = (name: string) => (db.getDatabase()
+    .prepare('SELECT id FROM symbols WHERE name = ? AND type = ?')
+    .get(name, 'function') as { id: number } | undefined)?.id;
+
+  beforeEach(async () => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(testDir, { recursive: true });
+    writeFileSync(join(testDir, '.gitignore'), 'generated.ts\n');
+    writeFileSync(join(testDir, 'a.ts'), 'export function alpha() { return 1; }\n');
+    writeFileSync(join(testDir, 'b.ts'), "import { alpha } from './a';\nexport function beta() { return alpha(); }\n");
+
+    db = new PrimordynDB(testDir);
+    indexer = new Indexer(db);
+    await indexer.index({ projectRoot: testDir, verbose: false });
+
+    // Changes are queued by hand and applied by flush(), never by the timer
+    updates = [];
+    watcher = new IndexWatcher(indexer, {
+      projectRoot: testDir,
+      debounceMs: 60_000,
+      onUpdate: update => updates.push(update),
+      onError: error => { throw error; }
+    });
+  });
+
+  afterEach(() => {
+    watcher.close();
+    db.close();
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should re-extract a changed file', async () => {
+    writeFileSync(join(testDir, 'a.ts'), 'export function alpha() { return 1; }\nexport function gamma() { return 3; }\n');
+    watcher.enqueue('a.ts');
+    await watcher.flush();
+
+    expect(updates).toEqual([expect.objectContaining({ indexed: ['a.ts'], removed: [] })]);
+    expect(symbolId('gamma')).toBeDefined();
+  });
+
+  test('should remove a deleted file and its symbols', async () => {
+    unlinkSync(join(testDir, 'b.ts'));
+    watcher.enqueue('b.ts');
+    await watcher.flush();
+
+    expect(updates).toEqual([expect.objectContaining({ indexed: [], removed: ['b.ts'] })]);
+    expect(indexedPaths()).toEqual(['a.ts']);
+    expect(symbolId('beta')).toBeUndefined();
+  });
+
+  test('should skip the index directory and gitignored files', async () => {
+    writeFileSync(join(testDir, 'generated.ts'), 'export function generated() { return 0; }\n');
+    mkdirSync(join(testDir, '.primordyn', 'notes'), { recursive: true });
+    writeFileSync(join(testDir, '.primordyn', 'notes', 'scratch.ts'), 'export function scratch() { return 0; }\n');
+    watcher.enqueue('generated.ts');
+    watcher.enqueue(join('.primordyn', 'notes', 'scratch.ts'));
+    await watcher.flush();
+
+    expect(updates).toEqual([]);
+    expect(indexedPaths()).toEqual(['a.ts', 'b.ts']);
+  });
+
+  test('should relink calls into the symbols of a changed file', async () => {
+    const callee = () => db.getDatabase().prepare(
+      "SELECT callee_symbol_id as id FROM call_graph WHERE callee_name = 'alpha'"
+    ).get() as { id: number | null };
+    const before = callee().id;
+
+    // Re-extraction replaces alpha with a new row
+    writeFileSync(join(testDir, 'a.ts'), '// moved down\n\nexport function alpha() { return 1; }\n');
+    watcher.enqueue('a.ts');
+    await watcher.flush();
+
+    expect(before).not.toBeNull();
+    expect(symbolId('alpha')).not.toBe(before);
+    expect(callee().id).toBe(symbolId('alpha'));
+    expect(updates[0].relinked).toBeGreaterThan(0);
+  });
+});
@@ -0,0 +1,134 @@
+import { ExtractionPool } from '../worker-pool.js';
+import { mkdirSync, writeFileSync, readFileSync, rmSync, existsSync } from 'fs';
+import { join } from 'path';
+import { pathToFileURL } from 'url';
+import type { FileInfo } from '../../types/index.js';
+
+describe('ExtractionPool', () => {
+  const testDir = join(process.cwd(), '.test-worker-pool');
+
+  // The pool's own worker, loaded from sources through tsx
+  const sourceWorker = { url: new URL('../extract-worker.ts', import.meta.url), execArgv: ['--import', 'tsx'] };
+
+  const fixtures: FileInfo[] = [
+    ['test.py', 'python'],
+    ['lib.rs', 'rust'],
+    ['server.go', 'go'],
+    ['example.java', 'java']
+  ].map(([name, language]) => {
+    const content = readFileSync(join(process.cwd(), 'test-files', name), 'utf-8');
+    return {
+      path: join(process.cwd(), 'test-files', name),
+      relativePath: join('test-files', name),
+      content,
+      hash: 'test-hash',
+      size: content.length,
+      language,
+      lastModified: new Date()
+    };
+  });
+
+  beforeEach(() => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(testDir, { recursive: true });
+  });
+
+  afterEach(() => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should extract inline with one job or without a compiled worker', async () => {
+    const single = new ExtractionPool(process.cwd(), 1, sourceWorker);
+    const missing = new ExtractionPool(process.cwd(), 4, { url: pathToFileURL(join(testDir, 'missing-worker.js')) });
+
+    expect(single.size).toBe(1);
+    expect(missing.size).toBe(1);
+    const context = await missing.extract(fixtures[0]);
+    expect(context.symbols.some(s => s.name === 'DataProcessor')).toBe(true);
+
+    await Promise.all([single.close(), missing.close()]);
+  });
+
+  test('should extract concurrent inline files without mixing them up', async () => {
+    const pool = new ExtractionPool(process.cwd(), 1);
+    const contexts = await Promise.all(fixtures.map(file => pool.extract(file)));
+    await pool.close();
+
+    for (const [index, file] of fixtures.entries()) {
+      const alone = await new ExtractionPool(process.cwd(), 1).extract(file);
+      expect(contexts[index].symbols.map(s => [s.name, s.lineStart])).toEqual(alone.symbols.map(s => [s.name, s.lineStart]));
+    }
+  });
+
+  test('should extract the same symbols on worker threads as inline', async () => {
+    const workers = new ExtractionPool(process.cwd(), 4, sourceWorker);
+    const inline = new ExtractionPool(process.cwd(), 1);
+
+    try {
+      expect(workers.size).toBe(4);
+      const threaded = await Promise.all(fixtures.map(file => workers.extract(file)));
+      const local = await Promise.all(fixtures.map(file => inline.extract(file)));
+
+      expect(threaded.map(context => context.symbols.length)).not.toContain(0);
+      expect(threaded).toEqual(local);
+    } finally {
+      await Promise.all([workers.close(), inline.close()]);
+    }
+  }, 60000);
+
+  test('should fail the file of a crashed worker and replace the worker', async () => {
+    // Echoes each file's path back, and dies on files containing "crash"
+    const script = join(testDir, 'crashing-worker.mjs');
+    writeFileSync(script, [
+      "import { parentPort } from 'worker_threads';",
+      'parentPort.on("message", ({ id, file }) => {',
+      '  if (file.content === "crash") throw new Error(`crashed on ${file.relativePath}`);',
+      '  parentPort.postMessage({ id, context: { symbols: [], imports: [], exports: [], dependencies: [], comments: [file.relativePath], calls: [], structure: {} } });',
+      '});'
+    ].join('\n'));
+    const pool = new ExtractionPool(testDir, 2, { url: pathToFileURL(script) });
+    const file = (relativePath: string, content: string): FileInfo => ({
+      path: join(testDir, relativePath), relativePath, content, hash: 'test-hash', size: content.length, language: 'typescript', lastModified: new Date()
+    });
+
+    try {
+      await expect(pool.extract(file('bad.ts', 'crash'))).rejects.toThrow('crashed on bad.ts');
+
+      const contexts = await Promise.all(['a.ts', 'b.ts', 'c.ts'].map(name => pool.extract(file(name, 'ok'))));
+      expect(contexts.map(context => context.comments)).toEqual([['a.ts'], ['b.ts'], ['c.ts']]);
+      expect(pool.size).toBe(2);
+    } finally {
+      await pool.close();
+    }
+  });
+
+  test('should fail the file of an exited worker and replace the worker', async () => {
+    // Echoes each file's path back, and exits without an error on files containing "exit"
+    const script = join(testDir, 'exiting-worker.mjs');
+    writeFileSync(script, [
+      "import { parentPort } from 'worker_threads';",
+      'parentPort.on("message", ({ id, file }) => {',
+      '  if (file.content === "exit") process.exit(3);',
+      '  parentPort.postMessage({ id, context: { symbols: [], imports: [], exports: [], dependencies: [], comments: [file.relativePath], calls: [], structure: {} } });',
+      '});'
+    ].join('\n'));
+    const pool = new ExtractionPool(testDir, 2, { url: pathToFileURL(script) });
+    const file = (relativePath: string, content: string): FileInfo => ({
+      path: join(testDir, relativePath), relativePath, content, hash: 'test-hash', size: content.length, language: 'typescript', lastModified: new Date()
+    });
+
+    try {
+      await expect(pool.extract(file('bad.ts', 'exit'))).rejects.toThrow('Extraction worker exited with code 3');
+
+      const contexts = await Promise.all(['a.ts', 'b.ts', 'c.ts'].map(name => pool.extract(file(name, 'ok'))));
+      expect(contexts.map(context => context.comments)).toEqual([['a.ts'], ['b.ts'], ['c.ts']]);
+      expect(pool.size).toBe(2);
+    } finally {
+      await pool.close();
+    }
+  });
+});
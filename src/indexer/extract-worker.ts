@@ -0,0 +1,18 @@
+import { parentPort, workerData } from 'worker_threads';
+import { ExtractorManager } from '../extractors/extractor-manager.js';
+import type { FileInfo } from '../types/index.js';
+
+/**
+ * Worker-thread entry point for the extraction pool: parses files sent by
+ * the main thread and posts the extracted context back.
+ */
+const extractorManager = new ExtractorManager((workerData as { projectRoot: string }).projectRoot);
+
+parentPort?.on('message', async (message: { id: number; file: FileInfo }) => {
+  try {
+    const context = await extractorManager.extract(message.file);
+    parentPort!.postMessage({ id: message.id, context });
+  } catch (error) {
+    parentPort!.postMessage({ id: message.id, error: error instanceof Error ? error.message : String(error) });
+  }
+});
@@ -0,0 +1,88 @@
+import type { Symbol } from '../types/index.js';
+
+export interface SymbolHierarchy {
+  qualifiedName: string;
+  parentIndex: number | null;  // Index of the parent in the same symbol list
+}
+
+// Symbol types that can contain other symbols
+const CONTAINER_TYPES = new Set<Symbol['type']>([
+  'class', 'interface', 'struct', 'enum', 'trait', 'namespace', 'module', 'function', 'method'
+]);
+
+/**
+ * Work out the parent and qualified name of each symbol in a file.
+ *
+ * Extractors that know the structure set `parent` (the parent's qualified
+ * name) and `qualifiedName` themselves. For the rest, the parent is the
+ * innermost container whose line range encloses the symbol, and the
+ * qualified name is the parent's qualified name plus the symbol's name.
+ */
+export function buildHierarchy(symbols: Symbol[]): SymbolHierarchy[] {
+  const result: SymbolHierarchy[] = symbols.map(symbol => ({
+    qualifiedName: symbol.qualifiedName || '',
+    parentIndex: null
+  }));
+
+  const byQualifiedName = new Map<string, number>();
+  symbols.forEach((symbol, index) => {
+    if (symbol.qualifiedName && !byQualifiedName.has(symbol.qualifiedName)) {
+      byQualifiedName.set(symbol.qualifiedName, index);
+    }
+  });
+
+  const span = (symbol: Symbol) => symbol.lineEnd - symbol.lineStart;
+
+  // Outer symbols first, so parents are qualified before their children
+  const order = symbols.map((_, index) => index).sort((a, b) => span(symbols[b]) - span(symbols[a]));
+
+  for (const index of order) {
+    const symbol = symbols[index];
+    let parentIndex: number | null = null;
+
+    if (symbol.parent !== undefined) {
+      parentIndex = byQualifiedName.get(symbol.parent) ?? null;
+    } else {
+      for (let i = 0; i < symbols.length; i++) {
+        const candidate = symbols[i];
+        if (i === index || !CONTAINER_TYPES.has(candidate.type)) continue;
+        if (candidate.lineStart > symbol.lineStart || candidate.lineEnd < symbol.lineEnd) continue;
+        if (span(candidate) <= span(symbol)) continue;
+        if (parentIndex === null || span(candidate) < span(symbols[parentIndex])) {
+          parentIndex = i;
+        }
+      }
+    }
+
+    result[index].parentIndex = parentIndex === index ? null : parentIndex;
+
+    if (!result[index].qualifiedName) {
+      const parent = result[index].parentIndex !== null ? result[result[index].parentIndex!].qualifiedName : '';
+      result[index].qualifiedName = qualify(parent, symbol.name);
+    }
+  }
+
+  return result;
+}
+
+/**
+ * Join a parent's qualified name and a child name. Names that already carry
+ * their owner (`Class.method`, `Type::fn`) are only prefixed when the parent
+ * is that owner, so `outer.Class` + `Class.method` gives `outer.Class.method`.
+ */
+function qualify(parent: string, name: string): string {
+  if (!parent) return name;
+
+  const separator = parent.includes('::') || name.includes('::') ? '::' : '.';
+  const split = Math.max(name.lastIndexOf('.'), name.lastIndexOf('::'));
+  if (split === -1) {
+    return `${parent}${separator}${name}`;
+  }
+
+  const owner = name.substring(0, split);
+  const last = name.substring(split + (name[split] === ':' ? 2 : 1));
+  if (parent === owner || parent.endsWith(`.${owner}`) || parent.endsWith(`::${owner}`)) {
+    return `${parent}${separator}${last}`;
+  }
+  return name;
+}
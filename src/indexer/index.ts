@@ -1,21 +1,57 @@
 import { PrimordynDB } from '../database/index.js';
 import { FileScanner } from '../scanner/index.js';
+import type { KnownFile } from '../scanner/index.js';
 import { ExtractorManager } from '../extractors/extractor-manager.js';
+import { CrateGraph } from '../scanner/cargo.js';
+import { CallLinker, TYPE_SYMBOLS } from './linker.js';
+import { ImportResolver } from './resolver.js';
+import { buildHierarchy } from './hierarchy.js';
+import type { LinkStats } from './linker.js';
+import { ExtractionPool } from './worker-pool.js';
+import { EmbeddingIndex } from '../embeddings/index.js';
+import { identifierParts } from '../utils/identifiers.js';
 import ora from 'ora';
 import chalk from 'chalk';
 import { encodingForModel, Tiktoken } from 'js-tiktoken';
-import type { FileInfo, ScanOptions, IndexOptions, IndexStats } from '../types/index.js';
+import { basename, sep } from 'path';
+import { existsSync } from 'fs';
+import type { FileInfo, ScanOptions, IndexOptions, IndexStats, ExtractedContext, ImportBinding, SymbolReference } from '../types/index.js';
+
+interface IndexedFile extends KnownFile {
+  id: number;
+  path: string;
+}
+
+interface ReconcileState {
+  known: Map<string, IndexedFile>;   // path -> row, as indexed before this run
+  byHash: Map<string, IndexedFile[]>;
+  seen: Set<string>;                 // paths found by the scan (or renamed away)
+  reextract: Set<string>;            // renamed paths whose symbols depend on the path
+}
+
+interface ExtractedFile {
+  file: FileInfo;
+  existingId: number | null;
+  tokens: number;
+  context: ExtractedContext;
+}
 
 export class Indexer {
   private db: PrimordynDB;
   private tokenEncoder: Tiktoken;
   private extractorManager: ExtractorManager;
+  private linker: CallLinker;
+  private resolver: ImportResolver;
+  private crateGraph: CrateGraph | null = null;
+  private crateIds: Map<string, number> = new Map(); // manifest path -> crates.id
 
   constructor(db: PrimordynDB) {
     this.db = db;
     // Use GPT-4 encoder as it's similar to Claude's tokenization
     this.tokenEncoder = encodingForModel('gpt-4');
-    this.extractorManager = new ExtractorManager();
+    this.extractorManager = new ExtractorManager(db.getProjectRoot());
+    this.linker = new CallLinker(db);
+    this.resolver = new ImportResolver(db);
   }
 
   public async index(options: IndexOptions = {}): Promise<IndexStats> {
@@ -26,13 +62,8 @@ export class Indexer {
       ? ora('Scanning project files...').start()
       : null;
 
-    const stats: IndexStats = {
-      filesIndexed: 0,
-      symbolsExtracted: 0,
-      totalTokens: 0,
-      timeElapsed: 0,
-      errors: 0
-    };
+    const stats = Indexer.emptyStats();
+    const database = this.db.getDatabase();
 
     try {
       // Configure scanner
@@ -45,21 +76,89 @@ export class Indexer {
       };
 
       const scanner = new FileScanner(scanOptions);
-      const files = await scanner.scan();
+      const filtered = scanOptions.includePatterns!.length > 0;
+
+      // Tag files with crates already known; manifests found while scanning
+      // are picked up once the scan completes
+      this.syncCrates(projectRoot, []);
+      const manifests: string[] = [];
+
+      const reconcile = this.startReconcile();
+
+      // Scanning, extraction (on the worker pool) and writes are pipelined: one
+      // batch is extracted and written, in a single transaction, while the
+      // next is being scanned
+      const pool = new ExtractionPool(this.db.getProjectRoot(), options.jobs ?? ExtractionPool.defaultJobs());
+      const batchSize = Math.max(10, pool.size * 4);
+      let scanned = 0;
+      let batch: FileInfo[] = [];
+      let inFlight: Promise<void> = Promise.resolve();
+
+      const processBatch = async (files: FileInfo[]): Promise<void> => {
+        const extracted = await Promise.all(files.map(file =>
+          this.extractFile(file, stats, options, pool, reconcile.reextract.has(file.path))
+        ));
+        database.transaction(() => {
+          for (const entry of extracted) {
+            if (entry) this.storeFile(entry, stats, options);
+          }
+        })();
+      };
 
-      if (spinner) {
-        spinner.text = `Found ${files.length} files to index`;
+      try {
+        // Unchanged files are only skipped by mtime and size when not forcing an update
+        const known = options.updateExisting ? undefined : reconcile.known;
+        for await (const file of scanner.scanStream(known)) {
+          scanned++;
+          if (basename(file.relativePath) === 'Cargo.toml') {
+            manifests.push(file.relativePath);
+          }
+
+          // Unchanged files and renames need no extraction
+          if (this.reconcileFile(reconcile, file, stats) || file.unchanged) {
+            continue;
+          }
+
+          batch.push(file);
+          if (batch.length >= batchSize) {
+            await inFlight;
+            inFlight = processBatch(batch);
+            batch = [];
+            if (spinner) {
+              spinner.text = `Indexing files... ${stats.filesIndexed} indexed, ${scanned} scanned`;
+            }
+          }
+        }
+
+        await inFlight;
+        if (batch.length > 0) {
+          await processBatch(batch);
+        }
+      } finally {
+        await pool.close();
       }
 
-      // Process files in batches for better performance
-      const batchSize = 10;
-      for (let i = 0; i < files.length; i += batchSize) {
-        const batch = files.slice(i, i + batchSize);
-        await Promise.all(batch.map(file => this.indexFile(file, stats, options)));
+      // Drop files that disappeared
+      this.pruneMissing(reconcile, filtered, stats);
 
+      stats.crates = this.syncCrates(projectRoot, manifests);
+
+      // Resolve imports and calls now that every file's symbols are known
+      if (spinner) {
+        spinner.text = 'Linking imports and calls...';
+      }
+      this.resolver.resolve();
+      const links = this.linker.link();
+      this.linker.linkTypes();
+      stats.callsResolved = links.resolved;
+      stats.callsAmbiguous = links.ambiguous;
+
+      // Vectors for semantic search; only new and changed files are embedded
+      if (options.embeddings) {
         if (spinner) {
-          spinner.text = `Indexing files... ${Math.min(i + batchSize, files.length)}/${files.length}`;
+          spinner.text = 'Embedding symbols...';
         }
+        stats.chunksEmbedded = await new EmbeddingIndex(this.db).update();
       }
 
       stats.timeElapsed = Date.now() - startTime;
@@ -84,16 +183,40 @@ export class Indexer {
     return stats;
   }
 
-  private async indexFile(fileInfo: FileInfo, stats: IndexStats, options: IndexOptions): Promise<void> {
+  /**
+   * Index a single file. Unchanged files are skipped unless `updateExisting` is set.
+   */
+  public async indexFile(fileInfo: FileInfo, stats: IndexStats = Indexer.emptyStats(), options: IndexOptions = {}): Promise<void> {
+    const extracted = await this.extractFile(fileInfo, stats, options);
+    if (extracted) {
+      this.storeFile(extracted, stats, options);
+    }
+  }
+
+  /**
+   * Extract a file's context, or return null when it is unchanged or fails.
+   */
+  private async extractFile(
+    fileInfo: FileInfo,
+    stats: IndexStats,
+    options: IndexOptions,
+    pool?: ExtractionPool,
+    force = false
+  ): Promise<ExtractedFile | null> {
     try {
       const database = this.db.getDatabase();
 
       // Check if file already exists
       const existing = database.prepare('SELECT id, hash FROM files WHERE path = ?').get(fileInfo.path) as { id: number; hash: string } | undefined;
 
-      if (existing && existing.hash === fileInfo.hash && !options.updateExisting) {
-        // File hasn't changed, skip
-        return;
+      if (existing && existing.hash === fileInfo.hash && !options.updateExisting && !force) {
+        // File hasn't changed, skip - but record a touched mtime so the next
+        // scan's size/mtime pre-check can skip reading it
+        if (!fileInfo.unchanged) {
+          database.prepare('UPDATE files SET last_modified = ? WHERE id = ?')
+            .run(fileInfo.lastModified.toISOString(), existing.id);
+        }
+        return null;
       }
 
       // Count tokens
@@ -101,59 +224,93 @@ export class Indexer {
       stats.totalTokens += tokens;
 
       // Extract context using the appropriate language extractor
-      const context = await this.extractorManager.extract(fileInfo);
+      const context = pool
+        ? await pool.extract(fileInfo)
+        : await this.extractorManager.extract(fileInfo);
 
-      // Begin transaction
-      database.prepare('BEGIN').run();
+      return { file: fileInfo, existingId: existing?.id ?? null, tokens, context };
+    } catch (error) {
+      stats.errors++;
+      if (options.verbose) {
+        console.error(chalk.red(`Error indexing ${fileInfo.relativePath}:`), error);
+      }
+      return null;
+    }
+  }
 
-      try {
+  /**
+   * Write an extracted file to the database. Runs in its own transaction, or
+   * as a savepoint when called inside a batch transaction, so one failing
+   * file doesn't roll back the others.
+   */
+  private storeFile(extracted: ExtractedFile, stats: IndexStats, options: IndexOptions): void {
+    const database = this.db.getDatabase();
+    const { file, existingId, tokens, context } = extracted;
+
+    const crate = this.crateGraph?.crateForPath(file.relativePath) || null;
+    const crateId = crate ? this.crateIds.get(crate.manifestPath) ?? null : null;
+
+    try {
+      database.transaction(() => {
         let fileId: number;
 
-        if (existing) {
+        if (existingId !== null) {
           // Update existing file
           database.prepare(`
             UPDATE files 
-            SET content = ?, hash = ?, size = ?, language = ?, last_modified = ?, indexed_at = CURRENT_TIMESTAMP, metadata = ?
+            SET content = ?, identifier_parts = ?, hash = ?, size = ?, language = ?, last_modified = ?, indexed_at = CURRENT_TIMESTAMP, metadata = ?, crate_id = COALESCE(?, crate_id)
             WHERE id = ?
           `).run(
-            fileInfo.content,
-            fileInfo.hash,
-            fileInfo.size,
-            fileInfo.language,
-            fileInfo.lastModified.toISOString(),
+            file.content,
+            identifierParts(file.content),
+            file.hash,
+            file.size,
+            file.language,
+            file.lastModified.toISOString(),
             JSON.stringify({ tokens, structure: context.structure }),
-            existing.id
+            crateId,
+            existingId
           );
-          fileId = existing.id;
+          fileId = existingId;
 
-          // Delete old symbols
+          // Delete old symbols, imports, exports and outgoing calls
           database.prepare('DELETE FROM symbols WHERE file_id = ?').run(fileId);
+          database.prepare('DELETE FROM imports WHERE file_id = ?').run(fileId);
+          database.prepare('DELETE FROM exports WHERE file_id = ?').run(fileId);
+          database.prepare('DELETE FROM type_relations WHERE file_id = ?').run(fileId);
+          database.prepare('DELETE FROM symbol_references WHERE file_id = ?').run(fileId);
+          database.prepare('DELETE FROM embeddings WHERE file_id = ?').run(fileId);
+          database.prepare('DELETE FROM call_graph WHERE caller_file_id = ?').run(fileId);
         } else {
           // Insert new file
           const result = database.prepare(`
-            INSERT INTO files (path, relative_path, content, hash, size, language, last_modified, metadata)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO files (path, relative_path, content, identifier_parts, hash, size, language, last_modified, metadata, crate_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
           `).run(
-            fileInfo.path,
-            fileInfo.relativePath,
-            fileInfo.content,
-            fileInfo.hash,
-            fileInfo.size,
-            fileInfo.language,
-            fileInfo.lastModified.toISOString(),
-            JSON.stringify({ tokens, structure: context.structure })
+            file.path,
+            file.relativePath,
+            file.content,
+            identifierParts(file.content),
+            file.hash,
+            file.size,
+            file.language,
+            file.lastModified.toISOString(),
+            JSON.stringify({ tokens, structure: context.structure }),
+            crateId
           );
           fileId = result.lastInsertRowid as number;
         }
 
-        // Insert symbols
+        // Insert symbols, then link each to its parent
         const insertSymbol = database.prepare(`
-          INSERT INTO symbols (file_id, name, type, line_start, line_end, signature, documentation, metadata)
-          VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+          INSERT INTO symbols (file_id, name, type, line_start, line_end, signature, documentation, metadata, qualified_name, identifier_parts)
+          VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         `);
+        const hierarchy = buildHierarchy(context.symbols);
+        const symbolIds: number[] = [];
 
-        for (const symbol of context.symbols) {
-          insertSymbol.run(
+        context.symbols.forEach((symbol, index) => {
+          const result = insertSymbol.run(
             fileId,
             symbol.name,
             symbol.type,
@@ -161,10 +318,20 @@ export class Indexer {
             symbol.lineEnd,
             symbol.signature,
             symbol.documentation || null,
-            JSON.stringify(symbol.metadata || {})
+            JSON.stringify(symbol.metadata || {}),
+            hierarchy[index].qualifiedName,
+            identifierParts(symbol.name)
           );
+          symbolIds.push(result.lastInsertRowid as number);
           stats.symbolsExtracted++;
-        }
+        });
+
+        const setParent = database.prepare('UPDATE symbols SET parent_symbol_id = ? WHERE id = ?');
+        hierarchy.forEach((entry, index) => {
+          if (entry.parentIndex !== null) {
+            setParent.run(symbolIds[entry.parentIndex], symbolIds[index]);
+          }
+        });
 
         // Store call relationships
         if (context.calls && context.calls.length > 0) {
@@ -172,94 +339,415 @@ export class Indexer {
             INSERT INTO call_graph (
               caller_symbol_id, caller_file_id, callee_name, 
               callee_symbol_id, callee_file_id, call_type, 
-              line_number, column_number
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+              line_number, column_number, scope, import_source
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
           `);
 
-          // For each call, try to find the caller symbol and callee symbol
+          // For each call, find the caller symbol
           for (const call of context.calls) {
-            // Find which symbol contains this call (the caller)
+            // Find which symbol contains this call (the caller) - the innermost
+            // one wins, so calls inside methods aren't attributed to modules
             let callerSymbolId: number | null = null;
-            for (const symbol of context.symbols) {
-              if (call.line >= symbol.lineStart && call.line <= symbol.lineEnd) {
-                // Find the symbol's database ID
-                const dbSymbol = database.prepare(
-                  'SELECT id FROM symbols WHERE file_id = ? AND name = ? AND line_start = ?'
-                ).get(fileId, symbol.name, symbol.lineStart) as { id: number } | undefined;
-                if (dbSymbol) {
-                  callerSymbolId = dbSymbol.id;
-                  break;
-                }
+            const enclosing = context.symbols
+              .filter(symbol => call.line >= symbol.lineStart && call.line <= symbol.lineEnd)
+              .sort((a, b) => (a.lineEnd - a.lineStart) - (b.lineEnd - b.lineStart));
+            for (const symbol of enclosing) {
+              // Find the symbol's database ID
+              const dbSymbol = database.prepare(
+                'SELECT id FROM symbols WHERE file_id = ? AND name = ? AND line_start = ?'
+              ).get(fileId, symbol.name, symbol.lineStart) as { id: number } | undefined;
+              if (dbSymbol) {
+                callerSymbolId = dbSymbol.id;
+                break;
               }
             }
 
-            // Try to find the callee symbol (might be in a different file)
-            let calleeSymbolId: number | null = null;
-            let calleeFileId: number | null = null;
-            
-            // First check if it's a local symbol in the same file
-            const localCallee = database.prepare(
-              'SELECT id FROM symbols WHERE file_id = ? AND name = ?'
-            ).get(fileId, call.calleeName.split('.').pop()) as { id: number } | undefined;
-            
-            if (localCallee) {
-              calleeSymbolId = localCallee.id;
-              calleeFileId = fileId;
-            } else if (!call.isExternal) {
-              // Try to find it in other files (global search)
-              const globalCallee = database.prepare(`
-                SELECT s.id as symbol_id, s.file_id 
-                FROM symbols s 
-                WHERE s.name = ? 
-                LIMIT 1
-              `).get(call.calleeName.split('.').pop()) as { symbol_id: number; file_id: number } | undefined;
-              
-              if (globalCallee) {
-                calleeSymbolId = globalCallee.symbol_id;
-                calleeFileId = globalCallee.file_id;
-              }
-            }
-
-            // Insert the call relationship
+            // Insert the call relationship; callees are resolved by the link phase
             insertCall.run(
               callerSymbolId,
               fileId,
               call.calleeName,
-              calleeSymbolId,
-              calleeFileId,
+              null,
+              null,
               call.callType,
               call.line,
-              call.column || null
+              call.column || null,
+              call.scope || 'project',
+              call.importSource || null
             );
           }
         }
 
-        // Store imports/exports in metadata
-        if (context.imports.length > 0 || context.exports.length > 0) {
-          database.prepare(`
-            UPDATE files 
-            SET metadata = json_patch(metadata, ?)
-            WHERE id = ?
-          `).run(
-            JSON.stringify({ imports: context.imports, exports: context.exports }),
-            fileId
-          );
-        }
+        // Store imports and exports; targets are resolved once all files are indexed
+        this.storeImports(fileId, context);
 
-        database.prepare('COMMIT').run();
-        stats.filesIndexed++;
+        // Store type relationships; targets are resolved by the link phase
+        this.storeTypeRelations(fileId, context, hierarchy.map(entry => entry.qualifiedName), symbolIds);
 
-      } catch (error) {
-        database.prepare('ROLLBACK').run();
-        throw error;
-      }
+        // Store every reference - calls, imports, type names, reads and writes
+        this.storeReferences(fileId, context, symbolIds);
 
+      })();
+      stats.filesIndexed++;
     } catch (error) {
       stats.errors++;
       if (options.verbose) {
-        console.error(chalk.red(`Error indexing ${fileInfo.relativePath}:`), error);
+        console.error(chalk.red(`Error indexing ${file.relativePath}:`), error);
+      }
+    }
+  }
+
+  /**
+   * Write a file's type relations, attributing each to the type in the file
+   * that declares it. Generic arguments are dropped from both ends, so
+   * `impl From<u8> for Wrapper<T>` relates `Wrapper` to `From`.
+   */
+  private storeTypeRelations(fileId: number, context: ExtractedContext, qualifiedNames: string[], symbolIds: number[]): void {
+    const relationships = context.relationships || [];
+    if (relationships.length === 0) {
+      return;
+    }
+
+    const insert = this.db.getDatabase().prepare(`
+      INSERT INTO type_relations (file_id, source_symbol_id, source_name, target_name, target_short_name, kind, line)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `);
+
+    for (const relationship of relationships) {
+      const source = stripTypeArguments(relationship.source);
+      const target = stripTypeArguments(relationship.target);
+      if (!source || !target) continue;
+
+      const matches = context.symbols
+        .map((symbol, index) => ({ symbol, index }))
+        .filter(({ symbol, index }) => qualifiedNames[index] === source || symbol.name === source);
+      // Prefer the type declaration over a same-named constructor or variable
+      const match = matches.find(({ symbol }) => TYPE_SYMBOLS.has(symbol.type)) || matches[0];
+
+      insert.run(
+        fileId,
+        match ? symbolIds[match.index] : null,
+        source,
+        target,
+        target.split(/::|\./).pop() || target,
+        relationship.kind,
+        relationship.line
+      );
+    }
+  }
+
+  /**
+   * Write a file's reference stream: the extractor's own references plus its
+   * calls and imported names, each attributed to the innermost symbol that
+   * contains it.
+   */
+  private storeReferences(fileId: number, context: ExtractedContext, symbolIds: number[]): void {
+    const references: SymbolReference[] = [
+      ...(context.references || []),
+      ...context.calls.map(call => ({ name: call.calleeName, kind: 'call' as const, line: call.line, column: call.column }))
+    ];
+
+    for (const binding of context.importBindings || []) {
+      if (binding.line === undefined) continue;
+      const kind = binding.reexport ? 'export' as const : 'import' as const;
+      // `import os.path` binds the module itself
+      const names = binding.names.length > 0 ? binding.names : (/^[\w.:]+$/.test(binding.source) ? [binding.source] : []);
+      for (const name of names) {
+        if (name === '*' || name === 'default') continue;
+        // Rust `use a::b::C` keeps its path
+        references.push({ name: binding.source.endsWith(`::${name}`) ? binding.source : name, kind, line: binding.line });
       }
     }
+
+    if (references.length === 0) {
+      return;
+    }
+
+    // Innermost first, so a reference in a method belongs to the method
+    const containers = context.symbols
+      .map((symbol, index) => ({ symbol, id: symbolIds[index] }))
+      .filter(({ symbol }) => symbol.type !== 'import' && symbol.type !== 'export')
+      .sort((a, b) => (a.symbol.lineEnd - a.symbol.lineStart) - (b.symbol.lineEnd - b.symbol.lineStart));
+
+    const insert = this.db.getDatabase().prepare(`
+      INSERT INTO symbol_references (file_id, symbol_id, name, short_name, kind, line, column_number)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `);
+    for (const reference of references) {
+      const name = reference.name.trim();
+      const shortName = name.split(/::|\./).pop();
+      if (!name || !shortName) continue;
+
+      const container = containers.find(({ symbol }) =>
+        reference.line >= symbol.lineStart && reference.line <= symbol.lineEnd
+      );
+      insert.run(fileId, container?.id ?? null, name, shortName, reference.kind, reference.line, reference.column ?? null);
+    }
+  }
+
+  /**
+   * Write a file's import and export rows. Extractors without import
+   * bindings only provide specifiers, so their imported names are unknown.
+   */
+  private storeImports(fileId: number, context: ExtractedContext): void {
+    const database = this.db.getDatabase();
+    const bindings: ImportBinding[] = context.importBindings
+      || context.imports.map(source => ({ source, names: [] }));
+
+    const insertImport = database.prepare(`
+      INSERT INTO imports (file_id, specifier, names, line, reexport)
+      VALUES (?, ?, ?, ?, ?)
+    `);
+    for (const binding of bindings) {
+      insertImport.run(
+        fileId,
+        binding.source,
+        JSON.stringify(binding.names),
+        binding.line ?? null,
+        binding.reexport ? 1 : 0
+      );
+    }
+
+    // Re-exported names point at the module they come from
+    const reexportSources = new Map<string, string>();
+    for (const binding of bindings.filter(b => b.reexport)) {
+      for (const name of binding.names) {
+        reexportSources.set(name, binding.source);
+      }
+    }
+
+    const insertExport = database.prepare('INSERT INTO exports (file_id, name, specifier) VALUES (?, ?, ?)');
+    for (const name of new Set(context.exports)) {
+      insertExport.run(fileId, name, reexportSources.get(name) ?? null);
+    }
+  }
+
+  /**
+   * Snapshot the indexed files so the scan can be reconciled against them.
+   */
+  private startReconcile(): ReconcileState {
+    const rows = this.db.getDatabase().prepare(
+      'SELECT id, path, relative_path, hash, size, last_modified FROM files'
+    ).all() as { id: number; path: string; relative_path: string; hash: string; size: number; last_modified: string }[];
+
+    const state: ReconcileState = { known: new Map(), byHash: new Map(), seen: new Set(), reextract: new Set() };
+    for (const row of rows) {
+      const file: IndexedFile = { id: row.id, path: row.path, hash: row.hash, size: row.size, lastModified: row.last_modified };
+      state.known.set(row.path, file);
+      const sameHash = state.byHash.get(row.hash) || [];
+      sameHash.push(file);
+      state.byHash.set(row.hash, sameHash);
+    }
+    return state;
+  }
+
+  /**
+   * Record a scanned file. A new path whose content hash matches an indexed
+   * file that no longer exists is treated as a rename: the row is moved to
+   * the new path and keeps its symbols. Rust symbols carry the module path
+   * derived from the file's path, so renamed Rust files are queued for
+   * re-extraction instead. Returns true for renames that need no extraction.
+   */
+  private reconcileFile(state: ReconcileState, file: FileInfo, stats: IndexStats): boolean {
+    state.seen.add(file.path);
+    if (state.known.has(file.path)) {
+      return false;
+    }
+
+    const candidates = state.byHash.get(file.hash) || [];
+    const index = candidates.findIndex(row => !state.seen.has(row.path) && !existsSync(row.path));
+    if (index === -1) {
+      return false;
+    }
+
+    const [previous] = candidates.splice(index, 1);
+    state.seen.add(previous.path);
+
+    const crate = this.crateGraph?.crateForPath(file.relativePath);
+    this.db.getDatabase().prepare(`
+      UPDATE files SET path = ?, relative_path = ?, crate_id = ?, last_modified = ? WHERE id = ?
+    `).run(
+      file.path,
+      file.relativePath,
+      crate ? this.crateIds.get(crate.manifestPath) ?? null : null,
+      file.lastModified.toISOString(),
+      previous.id
+    );
+    stats.filesRenamed = (stats.filesRenamed || 0) + 1;
+
+    if (file.language === 'rust') {
+      state.reextract.add(file.path);
+      return false;
+    }
+    return true;
+  }
+
+  /**
+   * Prune indexed files the scan didn't see (symbols and calls cascade).
+   *
+   * With a language filter active, files that still exist on disk are kept,
+   * since the scanner only skipped them because of the filter.
+   */
+  private pruneMissing(state: ReconcileState, filtered: boolean, stats: IndexStats): void {
+    const database = this.db.getDatabase();
+    const missing = Array.from(state.known.values()).filter(row =>
+      !state.seen.has(row.path) && (!filtered || !existsSync(row.path))
+    );
+    if (missing.length === 0) {
+      return;
+    }
+
+    const remove = database.prepare('DELETE FROM files WHERE id = ?');
+    database.transaction(() => {
+      for (const row of missing) {
+        remove.run(row.id);
+        stats.filesRemoved = (stats.filesRemoved || 0) + 1;
+      }
+    })();
+  }
+
+  /**
+   * Remove a file, or every file under a directory, from the index.
+   * Symbols and outgoing calls are removed with it; calls into it become unresolved.
+   */
+  public removeFile(relativePath: string): number {
+    const database = this.db.getDatabase();
+    const prefix = relativePath.replace(/[!%_]/g, '!$&') + sep + '%';
+    const result = database.prepare(
+      "DELETE FROM files WHERE relative_path = ? OR relative_path LIKE ? ESCAPE '!'"
+    ).run(relativePath, prefix);
+    return result.changes;
+  }
+
+  /**
+   * Resolve all imports, then every call that is unresolved or only weakly resolved.
+   */
+  public linkCalls(): LinkStats {
+    this.resolver.resolve();
+    const stats = this.linker.link();
+    this.linker.linkTypes();
+    return stats;
+  }
+
+  /**
+   * Relink after re-indexing individual files: their imports, calls made from those files,
+   * and calls elsewhere whose callee name matches a symbol they define.
+   * Re-indexing a file replaces its symbols, which leaves calls from other
+   * files pointing at nothing until they are relinked.
+   */
+  public relinkCalls(relativePaths: string[]): number {
+    const database = this.db.getDatabase();
+    if (relativePaths.length === 0) {
+      return 0;
+    }
+
+    const placeholders = relativePaths.map(() => '?').join(',');
+    const fileIds = (database.prepare(
+      `SELECT id FROM files WHERE relative_path IN (${placeholders})`
+    ).all(...relativePaths) as { id: number }[]).map(row => row.id);
+    const names = (database.prepare(`
+      SELECT DISTINCT s.name
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      WHERE f.relative_path IN (${placeholders})
+    `).all(...relativePaths) as { name: string }[]).map(row => row.name.split(/::|\./).pop() || row.name);
+
+    this.resolver.resolve(fileIds);
+    this.linker.linkTypes({ callerFileIds: fileIds, calleeNames: names });
+    return this.linker.link({ callerFileIds: fileIds, calleeNames: names }).resolved;
+  }
+
+  public static emptyStats(): IndexStats {
+    return {
+      filesIndexed: 0,
+      filesRemoved: 0,
+      filesRenamed: 0,
+      symbolsExtracted: 0,
+      totalTokens: 0,
+      timeElapsed: 0,
+      errors: 0
+    };
+  }
+
+  /**
+   * Reload the crate graph outside a full index run (e.g. before indexing
+   * single files from the language server).
+   */
+  public refreshCrates(projectRoot: string): number {
+    return this.syncCrates(projectRoot, []);
+  }
+
+  /**
+   * Parse Cargo manifests and store the crate graph, returning the number of
+   * crates found. Every indexed file is re-tagged with its crate.
+   */
+  private syncCrates(projectRoot: string, manifestPaths: string[]): number {
+    const database = this.db.getDatabase();
+    const known = database.prepare('SELECT manifest_path FROM crates').all() as { manifest_path: string }[];
+    const manifests = manifestPaths.concat(known.map(row => row.manifest_path));
+
+    const graph = CrateGraph.load(projectRoot, manifests);
+    this.crateGraph = graph.crates.length > 0 ? graph : null;
+    this.crateIds.clear();
+
+    if (graph.crates.length === 0 && known.length === 0) {
+      return 0;
+    }
+
+    const sync = database.transaction(() => {
+      // Files referencing removed crates fall back to NULL via ON DELETE SET NULL
+      database.prepare('DELETE FROM crates').run();
+
+      const insertCrate = database.prepare(`
+        INSERT INTO crates (name, lib_name, manifest_path, root_path, version, workspace_member)
+        VALUES (?, ?, ?, ?, ?, ?)
+      `);
+      for (const crate of graph.crates) {
+        const result = insertCrate.run(
+          crate.name,
+          crate.libName,
+          crate.manifestPath,
+          crate.rootDir,
+          crate.version || null,
+          crate.workspaceMember ? 1 : 0
+        );
+        this.crateIds.set(crate.manifestPath, result.lastInsertRowid as number);
+      }
+
+      const insertDependency = database.prepare(`
+        INSERT INTO crate_dependencies (crate_id, name, package, kind, path, workspace, dependency_crate_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+      `);
+      for (const crate of graph.crates) {
+        for (const dependency of crate.dependencies) {
+          const target = graph.resolveDependency(dependency);
+          insertDependency.run(
+            this.crateIds.get(crate.manifestPath),
+            dependency.name,
+            dependency.package,
+            dependency.kind,
+            dependency.path ?? null,
+            dependency.workspace ? 1 : 0,
+            target ? this.crateIds.get(target.manifestPath) : null
+          );
+        }
+      }
+
+      // Tag files, shallowest crates first so nested crates override their parents
+      const tagAll = database.prepare('UPDATE files SET crate_id = ?');
+      const tagPrefix = database.prepare("UPDATE files SET crate_id = ? WHERE relative_path LIKE ? ESCAPE '!'");
+      for (const crate of [...graph.crates].reverse()) {
+        const crateId = this.crateIds.get(crate.manifestPath);
+        if (crate.rootDir === '') {
+          tagAll.run(crateId);
+        } else {
+          tagPrefix.run(crateId, crate.rootDir.replace(/[!%_]/g, '!$&') + sep + '%');
+        }
+      }
+    });
+    sync();
+
+    return graph.crates.length;
   }
 
   private countTokens(text: string): number {
@@ -317,8 +805,18 @@ export class Indexer {
   public async clearIndex(): Promise<void> {
     const database = this.db.getDatabase();
     database.prepare('DELETE FROM call_graph').run();
+    database.prepare('DELETE FROM type_relations').run();
+    database.prepare('DELETE FROM symbol_references').run();
+    database.prepare('DELETE FROM embeddings').run();
+    database.prepare('DELETE FROM imports').run();
+    database.prepare('DELETE FROM exports').run();
     database.prepare('DELETE FROM symbols').run();
     database.prepare('DELETE FROM files').run();
+    database.prepare('DELETE FROM crates').run();
     database.prepare('DELETE FROM context_cache').run();
   }
-}
\ No newline at end of file
+}
+
+function stripTypeArguments(name: string): string {
+  return name.replace(/[<[].*$/, '').replace(/^[&*]+/, '').trim();
+}
@@ -0,0 +1,528 @@
+import { posix } from 'path';
+import { PrimordynDB } from '../database/index.js';
+import { CrateGraph } from '../scanner/cargo.js';
+
+export interface LinkScope {
+  callerFileIds?: number[];  // Calls made from these files
+  calleeNames?: string[];    // Calls whose callee name ends in one of these
+}
+
+export interface LinkStats {
+  resolved: number;
+  ambiguous: number;
+  unresolved: number;
+}
+
+interface LinkFile {
+  id: number;
+  path: string;           // posix-style relative path
+  language: string | null;
+  crateId: number | null;
+  imports: string[];      // Specifiers as written
+  importTargets: Set<number>;
+  reexports: Map<number, Set<string>>; // source file -> names re-exported from it
+  modulePath?: string;    // Rust module path of the file
+}
+
+interface LinkSymbol {
+  id: number;
+  name: string;
+  type: string;
+  fileId: number;
+  qualifier?: string;     // `Foo` in `Foo.bar` / `Foo::bar`
+  owner?: string;
+  module?: string;
+  qualifiedName?: string; // `pkg.mod.Foo.bar`, `UserService.login`
+}
+
+interface CallRow {
+  id: number;
+  caller_symbol_id: number | null;
+  caller_file_id: number;
+  callee_name: string;
+  callee_symbol_id: number | null;
+}
+
+interface RelationRow {
+  id: number;
+  file_id: number;
+  target_name: string;
+  target_short_name: string;
+  target_symbol_id: number | null;
+}
+
+interface RustTarget {
+  crateId: number | null;
+  path: string;
+}
+
+// Receivers that refer to the caller's own type
+const SELF_QUALIFIERS = new Set(['this', 'self', 'Self', 'cls']);
+
+// Symbol types a type relation can point at
+export const TYPE_SYMBOLS = new Set(['class', 'interface', 'struct', 'trait', 'type', 'enum']);
+
+/**
+ * Resolves call-graph edges once every file has been extracted.
+ *
+ * Candidates are symbols whose last name segment matches the callee. Each is
+ * scored on evidence - same file, imported module (directly or through a
+ * re-export), Rust `use`/crate paths, Python module-qualified names,
+ * qualified names such as `Foo.bar` or `module::func` - and the best one is
+ * linked. Confidence is 1.0 for a single strongly supported match and drops
+ * for weak evidence or ties. Calls on locals and into packages or runtime
+ * globals are never linked.
+ */
+export class CallLinker {
+  private db: PrimordynDB;
+  private files: Map<number, LinkFile> = new Map();
+  private symbolsById: Map<number, LinkSymbol> = new Map();
+  private symbolsByName: Map<string, LinkSymbol[]> = new Map();
+  private crates: CrateGraph | null = null;
+  private importCache: Map<number, { direct: Set<number>; reexports: Map<number, Set<string>> }> = new Map();
+
+  constructor(db: PrimordynDB) {
+    this.db = db;
+  }
+
+  public link(scope?: LinkScope): LinkStats {
+    const database = this.db.getDatabase();
+    const stats: LinkStats = { resolved: 0, ambiguous: 0, unresolved: 0 };
+
+    // Calls through an import are project calls only when the import resolved
+    // to an indexed file; anything else comes from a package
+    database.exec(`
+      UPDATE call_graph
+      SET scope = CASE WHEN EXISTS (
+        SELECT 1 FROM imports i
+        WHERE i.file_id = call_graph.caller_file_id
+          AND i.specifier = call_graph.import_source
+          AND i.target_file_id IS NOT NULL
+      ) THEN 'project' ELSE 'external' END
+      WHERE import_source IS NOT NULL;
+
+      UPDATE call_graph
+      SET callee_symbol_id = NULL, callee_file_id = NULL, resolution_confidence = NULL
+      WHERE scope IN ('local', 'external', 'unknown') AND callee_symbol_id IS NOT NULL;
+    `);
+
+    let rows = database.prepare(`
+      SELECT id, caller_symbol_id, caller_file_id, callee_name, callee_symbol_id
+      FROM call_graph
+      WHERE caller_file_id IS NOT NULL
+        AND COALESCE(scope, 'project') = 'project'
+        AND (callee_symbol_id IS NULL OR resolution_confidence IS NULL OR resolution_confidence < 1)
+    `).all() as CallRow[];
+
+    if (scope) {
+      const callerFiles = new Set(scope.callerFileIds || []);
+      const calleeNames = new Set(scope.calleeNames || []);
+      rows = rows.filter(row =>
+        callerFiles.has(row.caller_file_id) || calleeNames.has(lastSegment(row.callee_name))
+      );
+    }
+
+    if (rows.length === 0) {
+      return stats;
+    }
+
+    this.load();
+
+    const update = database.prepare(`
+      UPDATE call_graph
+      SET callee_symbol_id = ?, callee_file_id = ?, resolution_confidence = ?
+      WHERE id = ?
+    `);
+
+    database.transaction(() => {
+      for (const row of rows) {
+        const match = this.resolve(row);
+        if (match) {
+          update.run(match.symbol.id, match.symbol.fileId, match.confidence, row.id);
+          stats.resolved++;
+          if (match.confidence < 1) stats.ambiguous++;
+        } else {
+          if (row.callee_symbol_id !== null) {
+            update.run(null, null, null, row.id);
+          }
+          stats.unresolved++;
+        }
+      }
+    })();
+
+    return stats;
+  }
+
+  /**
+   * Resolve `type_relations` targets (supertypes, implemented interfaces and
+   * traits, embedded types) to type symbols. Unqualified names with no
+   * supporting evidence are only linked when a single type has that name.
+   */
+  public linkTypes(scope?: LinkScope): LinkStats {
+    const database = this.db.getDatabase();
+    const stats: LinkStats = { resolved: 0, ambiguous: 0, unresolved: 0 };
+
+    let rows = database.prepare(
+      'SELECT id, file_id, target_name, target_short_name, target_symbol_id FROM type_relations'
+    ).all() as RelationRow[];
+
+    if (scope) {
+      const files = new Set(scope.callerFileIds || []);
+      const names = new Set(scope.calleeNames || []);
+      rows = rows.filter(row => files.has(row.file_id) || names.has(row.target_short_name));
+    }
+
+    if (rows.length === 0) {
+      return stats;
+    }
+
+    this.load();
+
+    const update = database.prepare('UPDATE type_relations SET target_symbol_id = ? WHERE id = ?');
+
+    database.transaction(() => {
+      for (const row of rows) {
+        const match = this.resolveType(row);
+        if (match) {
+          update.run(match.symbol.id, row.id);
+          stats.resolved++;
+          if (match.confidence < 1) stats.ambiguous++;
+        } else {
+          if (row.target_symbol_id !== null) {
+            update.run(null, row.id);
+          }
+          stats.unresolved++;
+        }
+      }
+    })();
+
+    return stats;
+  }
+
+  private load(): void {
+    const database = this.db.getDatabase();
+    this.files.clear();
+    this.symbolsById.clear();
+    this.symbolsByName.clear();
+    this.importCache.clear();
+
+    const fileRows = database.prepare(
+      'SELECT id, relative_path, language, crate_id FROM files'
+    ).all() as { id: number; relative_path: string; language: string | null; crate_id: number | null }[];
+
+    for (const row of fileRows) {
+      this.files.set(row.id, {
+        id: row.id,
+        path: row.relative_path.replace(/\\/g, '/'),
+        language: row.language,
+        crateId: row.crate_id,
+        imports: [],
+        importTargets: new Set(),
+        reexports: new Map()
+      });
+    }
+
+    const importRows = database.prepare(
+      'SELECT file_id, specifier, target_file_id FROM imports'
+    ).all() as { file_id: number; specifier: string; target_file_id: number | null }[];
+    for (const row of importRows) {
+      const file = this.files.get(row.file_id);
+      if (!file) continue;
+      file.imports.push(row.specifier);
+      if (row.target_file_id !== null) file.importTargets.add(row.target_file_id);
+    }
+
+    const exportRows = database.prepare(
+      'SELECT file_id, name, target_file_id FROM exports WHERE target_file_id IS NOT NULL'
+    ).all() as { file_id: number; name: string; target_file_id: number | null }[];
+    for (const row of exportRows) {
+      const file = this.files.get(row.file_id);
+      if (!file || row.target_file_id === null) continue;
+      const names = file.reexports.get(row.target_file_id) || new Set<string>();
+      names.add(row.name);
+      file.reexports.set(row.target_file_id, names);
+    }
+
+    const symbolRows = database.prepare(`
+      SELECT id, name, type, file_id, qualified_name,
+        (SELECT p.name FROM symbols p WHERE p.id = symbols.parent_symbol_id) as parentName,
+        json_extract(metadata, '$.owner') as owner,
+        json_extract(metadata, '$.className') as className,
+        json_extract(metadata, '$.module') as module,
+        json_extract(metadata, '$.modulePath') as modulePath,
+        json_extract(metadata, '$.file') as isFile
+      FROM symbols
+      WHERE type NOT IN ('import', 'export')
+    `).all() as {
+      id: number; name: string; type: string; file_id: number;
+      qualified_name: string | null; parentName: string | null; owner: string | null; className: string | null; module: string | null;
+      modulePath: string | null; isFile: number | null;
+    }[];
+
+    for (const row of symbolRows) {
+      if (row.isFile && row.modulePath) {
+        const file = this.files.get(row.file_id);
+        if (file) file.modulePath = row.modulePath;
+      }
+      if (row.type === 'module' || row.type === 'namespace') {
+        continue;
+      }
+
+      const separator = row.name.includes('::') ? '::' : '.';
+      const index = row.name.lastIndexOf(separator);
+      const symbol: LinkSymbol = {
+        id: row.id,
+        name: row.name,
+        type: row.type,
+        fileId: row.file_id,
+        // Methods with bare names (Python, tree-sitter languages) are qualified by their class
+        qualifier: index > 0
+          ? row.name.substring(0, index)
+          : (row.type === 'method' ? row.className || row.parentName || undefined : undefined),
+        owner: row.owner || row.className || undefined,
+        module: row.module || undefined,
+        qualifiedName: row.qualified_name || undefined
+      };
+
+      this.symbolsById.set(symbol.id, symbol);
+      const key = lastSegment(row.name);
+      const list = this.symbolsByName.get(key) || [];
+      list.push(symbol);
+      this.symbolsByName.set(key, list);
+    }
+
+    this.crates = CrateGraph.fromIndex(database);
+  }
+
+  private resolve(row: CallRow): { symbol: LinkSymbol; confidence: number } | null {
+    const caller = this.files.get(row.caller_file_id);
+    if (!caller) return null;
+
+    const segments = row.callee_name.split(/::|\./).filter(Boolean);
+    const last = segments[segments.length - 1];
+    const qualifier = segments.length > 1 ? segments[segments.length - 2] : undefined;
+    if (!last) return null;
+
+    const candidates = (this.symbolsByName.get(last) || []).filter(symbol =>
+      sameLanguageFamily(this.files.get(symbol.fileId)?.language ?? null, caller.language)
+    );
+    if (candidates.length === 0) return null;
+
+    const callerSymbol = row.caller_symbol_id ? this.symbolsById.get(row.caller_symbol_id) : undefined;
+    const callerOwner = callerSymbol ? (callerSymbol.owner || callerSymbol.qualifier) : undefined;
+    const imports = this.importedFiles(caller);
+    const rustTargets = caller.language === 'rust' ? this.rustTargets(caller, callerSymbol, segments) : [];
+
+    let best: LinkSymbol[] = [];
+    let bestScore = -Infinity;
+    let bestEvidence = 0;
+
+    for (const symbol of candidates) {
+      const file = this.files.get(symbol.fileId)!;
+      let score = 0;
+      let evidence = 0.4; // Name match only
+
+      if (symbol.fileId === caller.id) {
+        score += 8;
+        evidence = 1;
+      }
+
+      if (imports.direct.has(symbol.fileId)) {
+        score += 6;
+        evidence = 1;
+      } else if (reexported(imports.reexports.get(symbol.fileId), last)) {
+        score += 5;
+        evidence = 1;
+      }
+
+      if (rustTargets.some(target =>
+        target.crateId === file.crateId && symbol.module && target.path === `${symbol.module}::${symbol.name}`
+      )) {
+        score += 7;
+        evidence = 1;
+      }
+
+      // `mod.func()` / `pkg.mod.Class.method()` naming the symbol's module path
+      if (qualifier && symbol.qualifiedName) {
+        const dotted = segments.join('.');
+        if (symbol.qualifiedName === dotted || symbol.qualifiedName.endsWith(`.${dotted}`)) {
+          score += 7;
+          evidence = 1;
+        }
+      }
+
+      if (qualifier && symbol.qualifier) {
+        const qualified = symbol.qualifier === qualifier ||
+          symbol.qualifier.endsWith(`::${qualifier}`) ||
+          symbol.qualifier.endsWith(`.${qualifier}`);
+        if (qualified || (SELF_QUALIFIERS.has(qualifier) && callerOwner === symbol.qualifier)) {
+          score += 4;
+          evidence = Math.max(evidence, 0.9);
+        }
+      } else if (!qualifier && symbol.qualifier) {
+        score -= 3; // A bare call is unlikely to target a method
+      }
+
+      if (caller.crateId !== null && file.crateId === caller.crateId) score += 1;
+      if (posix.dirname(file.path) === posix.dirname(caller.path)) score += 1;
+
+      // Receiver calls (`obj.method()`) need some evidence beyond the name
+      if (score <= 0 && !(!qualifier && !symbol.qualifier)) {
+        continue;
+      }
+
+      if (score > bestScore) {
+        best = [symbol];
+        bestScore = score;
+        bestEvidence = evidence;
+      } else if (score === bestScore) {
+        best.push(symbol);
+        bestEvidence = Math.max(bestEvidence, evidence);
+      }
+    }
+
+    if (best.length === 0) return null;
+
+    best.sort((a, b) => a.id - b.id);
+    return {
+      symbol: best[0],
+      confidence: Math.round((bestEvidence / best.length) * 100) / 100
+    };
+  }
+
+  private resolveType(row: RelationRow): { symbol: LinkSymbol; confidence: number } | null {
+    const file = this.files.get(row.file_id);
+    if (!file) return null;
+
+    const candidates = (this.symbolsByName.get(row.target_short_name) || []).filter(symbol =>
+      TYPE_SYMBOLS.has(symbol.type) &&
+      sameLanguageFamily(this.files.get(symbol.fileId)?.language ?? null, file.language)
+    );
+    if (candidates.length === 0) return null;
+
+    const segments = row.target_name.split(/::|\./).filter(Boolean);
+    const imports = this.importedFiles(file);
+    const rustTargets = file.language === 'rust' ? this.rustTargets(file, undefined, segments) : [];
+
+    let best: LinkSymbol[] = [];
+    let bestScore = -Infinity;
+
+    for (const symbol of candidates) {
+      const target = this.files.get(symbol.fileId)!;
+      let score = 0;
+
+      if (symbol.fileId === file.id) score += 8;
+      if (imports.direct.has(symbol.fileId)) {
+        score += 6;
+      } else if (reexported(imports.reexports.get(symbol.fileId), row.target_short_name)) {
+        score += 5;
+      }
+
+      if (rustTargets.some(path =>
+        path.crateId === target.crateId && symbol.module && path.path === `${symbol.module}::${symbol.name}`
+      )) {
+        score += 7;
+      }
+
+      // `base.Model` naming the symbol's module path
+      if (segments.length > 1 && symbol.qualifiedName) {
+        const dotted = segments.join('.');
+        if (symbol.qualifiedName === dotted || symbol.qualifiedName.endsWith(`.${dotted}`)) {
+          score += 7;
+        }
+      }
+
+      if (file.crateId !== null && target.crateId === file.crateId) score += 1;
+      if (posix.dirname(target.path) === posix.dirname(file.path)) score += 1;
+
+      if (score > bestScore) {
+        best = [symbol];
+        bestScore = score;
+      } else if (score === bestScore) {
+        best.push(symbol);
+      }
+    }
+
+    if (best.length === 0 || (bestScore <= 0 && candidates.length > 1)) return null;
+
+    best.sort((a, b) => a.id - b.id);
+    return {
+      symbol: best[0],
+      confidence: Math.round((bestScore > 0 ? 1 : 0.4) / best.length * 100) / 100
+    };
+  }
+
+  /**
+   * Files a caller imports directly, plus the names re-exported through them
+   * by the files they come from
+   */
+  private importedFiles(caller: LinkFile): { direct: Set<number>; reexports: Map<number, Set<string>> } {
+    const cached = this.importCache.get(caller.id);
+    if (cached) return cached;
+
+    const direct = caller.importTargets;
+    const reexports = new Map<number, Set<string>>();
+
+    for (const id of direct) {
+      for (const [source, names] of this.files.get(id)?.reexports || []) {
+        if (direct.has(source)) continue;
+        const merged = reexports.get(source) || new Set<string>();
+        names.forEach(name => merged.add(name));
+        reexports.set(source, merged);
+      }
+    }
+
+    const result = { direct, reexports };
+    this.importCache.set(caller.id, result);
+    return result;
+  }
+
+  /**
+   * Fully qualified `crate::...` paths a Rust call may refer to, taking `use`
+   * declarations, `self`/`super` and other workspace crates into account.
+   */
+  private rustTargets(caller: LinkFile, callerSymbol: LinkSymbol | undefined, segments: string[]): RustTarget[] {
+    const crates = this.crates;
+    if (!crates) return [];
+
+    const callerModule = callerSymbol?.module || caller.modulePath || 'crate';
+    const callerCrate = crates.crateWithId(caller.crateId);
+    const targets: RustTarget[] = [];
+
+    const expand = (path: string[]): RustTarget => {
+      const expanded = crates.expandPath(path, callerCrate, callerModule);
+      return { crateId: expanded.crate?.id ?? caller.crateId, path: expanded.segments.join('::') };
+    };
+
+    // The call path as written
+    targets.push(expand(segments));
+
+    // A leading segment brought into scope by `use`
+    for (const imported of caller.imports) {
+      const importSegments = imported.split('::');
+      if (importSegments[importSegments.length - 1] === segments[0]) {
+        targets.push(expand([...importSegments, ...segments.slice(1)]));
+      }
+    }
+
+    return targets;
+  }
+}
+
+/**
+ * The final segment of a `::` or `.` qualified name
+ */
+export function lastSegment(name: string): string {
+  const segments = name.split(/::|\./);
+  return segments[segments.length - 1];
+}
+
+function reexported(names: Set<string> | undefined, name: string): boolean {
+  return !!names && (names.has(name) || names.has('*'));
+}
+
+function sameLanguageFamily(a: string | null, b: string | null): boolean {
+  const family = (language: string | null) =>
+    language === 'typescript' || language === 'javascript' ? 'js' : language;
+  return family(a) === family(b);
+}
@@ -0,0 +1,564 @@
+import { posix, join } from 'path';
+import { PrimordynDB } from '../database/index.js';
+import { CrateGraph } from '../scanner/cargo.js';
+import { readJsonc } from '../utils/jsonc.js';
+
+interface ResolverFile {
+  id: number;
+  path: string;          // posix-style relative path
+  language: string | null;
+  crateId: number | null;
+  modulePath?: string;   // Rust module path of the file
+  pythonModule?: string; // Dotted Python module name (`pkg.mod`)
+}
+
+interface PackageInfo {
+  name: string;
+  dir: string;           // Relative to the project root ('' for the root package)
+  manifest: PackageManifest;
+}
+
+interface PackageManifest {
+  name?: string;
+  main?: string;
+  module?: string;
+  types?: string;
+  typings?: string;
+  source?: string;
+  exports?: unknown;
+}
+
+interface PathMapping {
+  prefix: string;        // Pattern text before '*'
+  suffix: string;        // Pattern text after '*' (or '' for exact patterns)
+  wildcard: boolean;
+  targets: string[];     // Relative to the project root
+}
+
+const TS_EXTENSIONS = ['', '.ts', '.tsx', '.d.ts', '.mts', '.cts', '.js', '.jsx', '.mjs', '.cjs'];
+
+// Emitted extensions and the source extensions they compile from
+const EMITTED_EXTENSIONS: Record<string, string[]> = {
+  '.js': ['.ts', '.tsx'],
+  '.jsx': ['.tsx'],
+  '.mjs': ['.mts'],
+  '.cjs': ['.cts']
+};
+
+// `exports` conditions in the order we prefer them for locating source files
+const EXPORT_CONDITIONS = ['source', 'types', 'import', 'module', 'node', 'require', 'default'];
+
+/**
+ * Resolves the module specifiers stored in the `imports` and `exports`
+ * tables to indexed files.
+ *
+ * - TypeScript/JavaScript: relative paths, tsconfig `baseUrl` and `paths`,
+ *   workspace packages via package.json `exports`/`main`, index files and
+ *   `.js` specifiers that point at `.ts` sources
+ * - Python: relative and absolute imports against the package layout
+ *   (`__init__.py` packages, `src/` layouts and namespace packages)
+ * - Rust: `crate::`, `self::`, `super::` and workspace crate paths
+ */
+export class ImportResolver {
+  private db: PrimordynDB;
+  private files: Map<number, ResolverFile> = new Map();
+  private pathIndex: Map<string, number> = new Map();
+  private pythonModules: Map<string, number[]> = new Map();  // Module name -> files
+  private pythonSuffixes: Map<string, number[]> = new Map(); // Dotted suffixes, as a fallback
+  private rustModules: Map<string, number> = new Map(); // `${crateId}|${modulePath}` -> file
+  private crates: CrateGraph | null = null;
+  private packages: PackageInfo[] = [];
+  private baseUrl: string | null = null;
+  private pathMappings: PathMapping[] = [];
+
+  constructor(db: PrimordynDB) {
+    this.db = db;
+  }
+
+  /**
+   * Resolve import and re-export targets, and set the module-qualified
+   * names of Python symbols. Without `fileIds` every row is re-resolved;
+   * otherwise only rows from those files and rows that are still
+   * unresolved. Returns the number of rows pointing at a file.
+   */
+  public resolve(fileIds?: number[]): number {
+    const database = this.db.getDatabase();
+    const scope = fileIds ? new Set(fileIds) : null;
+    const inScope = (row: { file_id: number; target_file_id: number | null }) =>
+      !scope || scope.has(row.file_id) || row.target_file_id === null;
+
+    const imports = (database.prepare(
+      'SELECT id, file_id, specifier, names, target_file_id FROM imports'
+    ).all() as { id: number; file_id: number; specifier: string; names: string | null; target_file_id: number | null }[]).filter(inScope);
+    const exports = (database.prepare(
+      'SELECT id, file_id, specifier, NULL as names, target_file_id FROM exports WHERE specifier IS NOT NULL'
+    ).all() as { id: number; file_id: number; specifier: string; names: string | null; target_file_id: number | null }[]).filter(inScope);
+
+    this.load();
+
+    const updateImport = database.prepare('UPDATE imports SET target_file_id = ? WHERE id = ?');
+    const updateExport = database.prepare('UPDATE exports SET target_file_id = ? WHERE id = ?');
+    let resolved = 0;
+
+    database.transaction(() => {
+      for (const [rows, update] of [[imports, updateImport], [exports, updateExport]] as const) {
+        for (const row of rows) {
+          const from = this.files.get(row.file_id);
+          const names = row.names ? JSON.parse(row.names) as string[] : [];
+          const target = from ? this.resolveSpecifier(from, row.specifier, names) : null;
+          if (target !== row.target_file_id) {
+            update.run(target, row.id);
+          }
+          if (target !== null) resolved++;
+        }
+      }
+
+      this.qualifyPythonSymbols(scope);
+    })();
+
+    return resolved;
+  }
+
+  private load(): void {
+    const database = this.db.getDatabase();
+    this.files.clear();
+    this.pathIndex.clear();
+    this.pythonModules.clear();
+    this.pythonSuffixes.clear();
+    this.rustModules.clear();
+
+    const rows = database.prepare(
+      'SELECT id, relative_path, language, crate_id FROM files'
+    ).all() as { id: number; relative_path: string; language: string | null; crate_id: number | null }[];
+
+    for (const row of rows) {
+      const path = row.relative_path.replace(/\\/g, '/');
+      this.files.set(row.id, { id: row.id, path, language: row.language, crateId: row.crate_id });
+      this.pathIndex.set(path, row.id);
+    }
+    this.indexPythonModules();
+
+    const modules = database.prepare(`
+      SELECT file_id, json_extract(metadata, '$.modulePath') as modulePath
+      FROM symbols
+      WHERE type = 'module' AND json_extract(metadata, '$.file') = 1
+    `).all() as { file_id: number; modulePath: string }[];
+    for (const row of modules) {
+      const file = this.files.get(row.file_id);
+      if (!file || !row.modulePath) continue;
+      file.modulePath = row.modulePath;
+
+      // A crate with both lib.rs and main.rs resolves `crate::` to the library
+      const key = `${file.crateId}|${row.modulePath}`;
+      if (!this.rustModules.has(key) || file.path.endsWith('lib.rs')) {
+        this.rustModules.set(key, file.id);
+      }
+    }
+
+    this.crates = CrateGraph.fromIndex(database);
+
+    this.loadTsConfig(this.db.getProjectRoot());
+    this.loadPackages();
+  }
+
+  private resolveSpecifier(from: ResolverFile, specifier: string, names: string[]): number | null {
+    switch (from.language) {
+      case 'typescript':
+      case 'javascript':
+        return this.resolveScript(from, specifier);
+      case 'python':
+        return this.resolvePython(from, specifier, names);
+      case 'rust':
+        return this.resolveRust(from, specifier);
+      default:
+        return null;
+    }
+  }
+
+  private resolveScript(from: ResolverFile, specifier: string): number | null {
+    if (specifier.startsWith('.')) {
+      return this.tryScriptPath(posix.join(posix.dirname(from.path), specifier));
+    }
+
+    for (const mapping of this.pathMappings) {
+      const matches = mapping.wildcard
+        ? specifier.startsWith(mapping.prefix) && specifier.endsWith(mapping.suffix) &&
+          specifier.length >= mapping.prefix.length + mapping.suffix.length
+        : specifier === mapping.prefix;
+      if (!matches) continue;
+
+      const captured = mapping.wildcard
+        ? specifier.substring(mapping.prefix.length, specifier.length - mapping.suffix.length)
+        : '';
+      for (const target of mapping.targets) {
+        const id = this.tryScriptPath(target.replace('*', captured));
+        if (id !== null) return id;
+      }
+    }
+
+    if (this.baseUrl !== null) {
+      const id = this.tryScriptPath(posix.join(this.baseUrl, specifier));
+      if (id !== null) return id;
+    }
+
+    return this.resolvePackage(specifier);
+  }
+
+  /**
+   * Try a module path as written, with its emitted extension swapped for
+   * the source one, with each script extension, then as a directory index
+   */
+  private tryScriptPath(path: string): number | null {
+    const normalized = posix.normalize(path);
+    const exact = this.pathIndex.get(normalized);
+    if (exact !== undefined) return exact;
+
+    const emitted = posix.extname(normalized);
+    const base = EMITTED_EXTENSIONS[emitted] ? normalized.slice(0, -emitted.length) : normalized;
+    for (const ext of EMITTED_EXTENSIONS[emitted] || []) {
+      const id = this.pathIndex.get(base + ext);
+      if (id !== undefined) return id;
+    }
+    for (const ext of TS_EXTENSIONS) {
+      const id = this.pathIndex.get(base + ext);
+      if (id !== undefined) return id;
+    }
+    const indexBase = base === '.' ? 'index' : `${base}/index`;
+    for (const ext of TS_EXTENSIONS.slice(1)) {
+      const id = this.pathIndex.get(indexBase + ext);
+      if (id !== undefined) return id;
+    }
+    return null;
+  }
+
+  /**
+   * Resolve a bare specifier to a package in the project (a workspace
+   * package or the root package importing itself by name)
+   */
+  private resolvePackage(specifier: string): number | null {
+    const pkg = this.packages.find(candidate =>
+      specifier === candidate.name || specifier.startsWith(`${candidate.name}/`));
+    if (!pkg) return null;
+
+    const subpath = specifier === pkg.name ? '.' : `.${specifier.substring(pkg.name.length)}`;
+    const { manifest } = pkg;
+
+    if (manifest.exports !== undefined && manifest.exports !== null) {
+      for (const target of exportTargets(manifest.exports, subpath)) {
+        const id = this.tryScriptPath(posix.join(pkg.dir, target));
+        if (id !== null) return id;
+      }
+    }
+
+    if (subpath === '.') {
+      for (const entry of [manifest.source, manifest.types, manifest.typings, manifest.module, manifest.main]) {
+        if (typeof entry !== 'string') continue;
+        const id = this.tryScriptPath(posix.join(pkg.dir, entry));
+        if (id !== null) return id;
+      }
+      // Built entry points usually aren't indexed; fall back to the sources
+      return this.tryScriptPath(posix.join(pkg.dir, 'src', 'index')) ?? this.tryScriptPath(pkg.dir || '.');
+    }
+
+    return this.tryScriptPath(posix.join(pkg.dir, subpath))
+      ?? this.tryScriptPath(posix.join(pkg.dir, 'src', subpath));
+  }
+
+  /**
+   * Resolve a Python module. `from pkg import sub` where `pkg` has no file
+   * of its own (a namespace package) resolves to the `sub` submodule.
+   */
+  private resolvePython(from: ResolverFile, specifier: string, names: string[]): number | null {
+    const dots = specifier.match(/^\.*/)![0].length;
+    const rest = specifier.substring(dots);
+    const submodule = names.length > 0 && names[0] !== '*' ? names[0] : null;
+
+    if (dots > 0) {
+      let dir = posix.dirname(from.path);
+      for (let i = 1; i < dots; i++) dir = posix.dirname(dir);
+      const base = rest ? posix.join(dir, rest.replace(/\./g, '/')) : dir;
+      return this.tryPythonPath(base) ?? (submodule ? this.tryPythonPath(posix.join(base, submodule)) : null);
+    }
+
+    const module = this.findPythonModule(from, specifier);
+    if (module !== null || !submodule) return module;
+    return this.findPythonModule(from, `${specifier}.${submodule}`);
+  }
+
+  private tryPythonPath(base: string): number | null {
+    for (const candidate of [`${base}.py`, `${base}.pyi`, `${base}/__init__.py`, `${base}/__init__.pyi`]) {
+      const id = this.pathIndex.get(posix.normalize(candidate));
+      if (id !== undefined) return id;
+    }
+    return null;
+  }
+
+  /**
+   * Find an absolute module by its name under the package layout, falling
+   * back to matching a dotted suffix of the file path. Between several
+   * matches, prefer the one sharing the longest path prefix with the importer.
+   */
+  private findPythonModule(from: ResolverFile, name: string): number | null {
+    const candidates = this.pythonModules.get(name) || this.pythonSuffixes.get(name) || [];
+    let best: number | null = null;
+    let bestShared = -1;
+    for (const id of candidates) {
+      const shared = sharedPrefixLength(this.files.get(id)!.path, from.path);
+      if (shared > bestShared) {
+        best = id;
+        bestShared = shared;
+      }
+    }
+    return best;
+  }
+
+  /**
+   * Give every Python file its module name. Regular packages (directories
+   * with `__init__.py`) are named from the parent of their top-level
+   * package; other files from the deepest source root containing them -
+   * the project root, `src/`, or a directory holding a top-level package.
+   */
+  private indexPythonModules(): void {
+    const pythonFiles = [...this.files.values()].filter(file => file.language === 'python');
+    const packageDirs = new Set(pythonFiles
+      .filter(file => /(^|\/)__init__\.pyi?$/.test(file.path))
+      .map(file => posix.dirname(file.path))
+      .filter(dir => dir !== '.'));
+
+    const topPackage = (dir: string) => {
+      while (packageDirs.has(posix.dirname(dir))) dir = posix.dirname(dir);
+      return dir;
+    };
+
+    const roots = new Set<string>(['.']);
+    for (const file of pythonFiles) {
+      if (file.path.startsWith('src/')) roots.add('src');
+      const dir = posix.dirname(file.path);
+      if (packageDirs.has(dir)) roots.add(posix.dirname(topPackage(dir)));
+    }
+    const sortedRoots = [...roots].sort((a, b) => b.length - a.length);
+
+    for (const file of pythonFiles) {
+      const dir = posix.dirname(file.path);
+      const root = packageDirs.has(dir)
+        ? posix.dirname(topPackage(dir))
+        : sortedRoots.find(candidate => candidate === '.' || file.path.startsWith(`${candidate}/`))!;
+
+      const relative = root === '.' ? file.path : file.path.substring(root.length + 1);
+      const parts = relative.replace(/\.pyi?$/, '').split('/');
+      if (parts[parts.length - 1] === '__init__') parts.pop();
+      if (parts.length === 0) continue;
+
+      file.pythonModule = parts.join('.');
+      addToList(this.pythonModules, file.pythonModule, file.id);
+
+      // Every dotted suffix of the path, so `b.c` still finds `x/a/b/c.py`
+      const pathParts = file.path.replace(/\.pyi?$/, '').split('/');
+      if (pathParts[pathParts.length - 1] === '__init__') pathParts.pop();
+      for (let i = 0; i < pathParts.length; i++) {
+        addToList(this.pythonSuffixes, pathParts.slice(i).join('.'), file.id);
+      }
+    }
+  }
+
+  /**
+   * Set `qualified_name` (`pkg.mod.Class.method`) on Python symbols from
+   * their file's module name and enclosing scope
+   */
+  private qualifyPythonSymbols(scope: Set<number> | null): void {
+    const database = this.db.getDatabase();
+    const symbols = database.prepare(`
+      SELECT s.id, s.file_id, s.name, s.qualified_name,
+        json_extract(s.metadata, '$.scope') as scope
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      WHERE f.language = 'python'
+    `).all() as { id: number; file_id: number; name: string; qualified_name: string | null; scope: string | null }[];
+
+    const update = database.prepare('UPDATE symbols SET qualified_name = ? WHERE id = ?');
+    for (const symbol of symbols) {
+      if (scope && !scope.has(symbol.file_id)) continue;
+
+      const module = this.files.get(symbol.file_id)?.pythonModule;
+      const qualified = [module, symbol.scope, symbol.name].filter(Boolean).join('.');
+      if (qualified !== symbol.qualified_name) {
+        update.run(qualified, symbol.id);
+      }
+    }
+  }
+
+  /**
+   * Resolve a `use` path to the file of the deepest module it names
+   */
+  private resolveRust(from: ResolverFile, specifier: string): number | null {
+    if (!this.crates) return null;
+    const target = this.crates.expandPath(
+      specifier.replace(/^::/, '').split('::'),
+      this.crates.crateWithId(from.crateId),
+      from.modulePath
+    );
+    const crateId = target.crate?.id ?? from.crateId;
+
+    for (let length = target.segments.length; length >= target.minLength; length--) {
+      const id = this.rustModules.get(`${crateId}|${target.segments.slice(0, length).join('::')}`);
+      if (id !== undefined) return id;
+    }
+    return null;
+  }
+
+  /**
+   * Read `baseUrl` and `paths` from the project's tsconfig.json, following
+   * relative `extends` chains
+   */
+  private loadTsConfig(projectRoot: string): void {
+    this.baseUrl = null;
+    this.pathMappings = [];
+
+    let baseUrl: string | undefined;
+    let paths: { dir: string; entries: Record<string, string[]> } | undefined;
+    let configDir = '.';
+    let configFile = 'tsconfig.json';
+    const visited = new Set<string>();
+
+    // Options in the extending config override those it extends
+    while (!visited.has(posix.join(configDir, configFile))) {
+      visited.add(posix.join(configDir, configFile));
+      const config = readJsonc(join(projectRoot, configDir, configFile)) as {
+        extends?: string;
+        compilerOptions?: { baseUrl?: string; paths?: Record<string, string[]> };
+      } | null;
+      if (!config) break;
+
+      const options = config.compilerOptions || {};
+      if (baseUrl === undefined && options.baseUrl !== undefined) {
+        baseUrl = posix.join(configDir, options.baseUrl.replace(/\\/g, '/'));
+      }
+      if (paths === undefined && options.paths) {
+        paths = { dir: configDir, entries: options.paths };
+      }
+
+      if (typeof config.extends !== 'string' || !config.extends.startsWith('.')) break;
+      const extended = posix.join(configDir, config.extends.replace(/\\/g, '/'));
+      configDir = posix.dirname(extended);
+      configFile = posix.basename(extended).endsWith('.json') ? posix.basename(extended) : `${posix.basename(extended)}.json`;
+    }
+
+    if (baseUrl !== undefined) {
+      this.baseUrl = posix.normalize(baseUrl);
+    }
+    if (!paths) return;
+
+    // `paths` are relative to baseUrl, or to the defining tsconfig without one
+    const base = this.baseUrl ?? paths.dir;
+    for (const [pattern, targets] of Object.entries(paths.entries)) {
+      const star = pattern.indexOf('*');
+      this.pathMappings.push({
+        prefix: star === -1 ? pattern : pattern.substring(0, star),
+        suffix: star === -1 ? '' : pattern.substring(star + 1),
+        wildcard: star !== -1,
+        targets: (Array.isArray(targets) ? targets : []).map(target => posix.join(base, target))
+      });
+    }
+
+    // The most specific pattern wins
+    this.pathMappings.sort((a, b) => b.prefix.length - a.prefix.length);
+  }
+
+  /**
+   * Collect named packages from the indexed package.json files
+   */
+  private loadPackages(): void {
+    this.packages = [];
+    const manifests = this.db.getDatabase().prepare(`
+      SELECT relative_path, content FROM files WHERE relative_path LIKE '%package.json'
+    `).all() as { relative_path: string; content: string }[];
+
+    for (const row of manifests) {
+      const path = row.relative_path.replace(/\\/g, '/');
+      if (posix.basename(path) !== 'package.json') continue;
+
+      let manifest: PackageManifest;
+      try {
+        manifest = JSON.parse(row.content) as PackageManifest;
+      } catch {
+        continue;
+      }
+      if (typeof manifest.name !== 'string' || !manifest.name) continue;
+
+      const dir = posix.dirname(path);
+      this.packages.push({ name: manifest.name, dir: dir === '.' ? '' : dir, manifest });
+    }
+
+    // `@scope/pkg/sub` must match `@scope/pkg/sub` before `@scope/pkg`
+    this.packages.sort((a, b) => b.name.length - a.name.length);
+  }
+}
+
+/**
+ * Targets of a package.json `exports` field for a subpath (`.` or `./x`),
+ * most preferred first. Handles sugar, subpath maps, `*` patterns,
+ * condition objects and fallback arrays.
+ */
+function exportTargets(exports: unknown, subpath: string): string[] {
+  const isSubpathMap = typeof exports === 'object' && exports !== null && !Array.isArray(exports) &&
+    Object.keys(exports).some(key => key.startsWith('.'));
+
+  if (!isSubpathMap) {
+    return subpath === '.' ? conditionTargets(exports, '') : [];
+  }
+
+  const map = exports as Record<string, unknown>;
+  if (subpath in map) {
+    return conditionTargets(map[subpath], '');
+  }
+
+  // Longest matching `./prefix*suffix` pattern wins
+  let best: { key: string; captured: string } | null = null;
+  for (const key of Object.keys(map)) {
+    const star = key.indexOf('*');
+    if (star === -1) continue;
+    const prefix = key.substring(0, star);
+    const suffix = key.substring(star + 1);
+    if (subpath.startsWith(prefix) && subpath.endsWith(suffix) && subpath.length >= prefix.length + suffix.length &&
+        (!best || prefix.length > best.key.indexOf('*'))) {
+      best = { key, captured: subpath.substring(prefix.length, subpath.length - suffix.length) };
+    }
+  }
+  return best ? conditionTargets(map[best.key], best.captured) : [];
+}
+
+function conditionTargets(value: unknown, captured: string): string[] {
+  if (typeof value === 'string') {
+    return [value.replace(/\*/g, captured)];
+  }
+  if (Array.isArray(value)) {
+    return value.flatMap(entry => conditionTargets(entry, captured));
+  }
+  if (typeof value === 'object' && value !== null) {
+    const conditions = value as Record<string, unknown>;
+    const keys = Object.keys(conditions).sort((a, b) => conditionRank(a) - conditionRank(b));
+    return keys.flatMap(key => conditionTargets(conditions[key], captured));
+  }
+  return [];
+}
+
+function conditionRank(condition: string): number {
+  const rank = EXPORT_CONDITIONS.indexOf(condition);
+  return rank === -1 ? EXPORT_CONDITIONS.length : rank;
+}
+
+function addToList(map: Map<string, number[]>, key: string, id: number): void {
+  const ids = map.get(key) || [];
+  ids.push(id);
+  map.set(key, ids);
+}
+
+function sharedPrefixLength(a: string, b: string): number {
+  const left = a.split('/');
+  const right = b.split('/');
+  let shared = 0;
+  while (shared < left.length - 1 && shared < right.length - 1 && left[shared] === right[shared]) {
+    shared++;
+  }
+  return shared;
+}
@@ -0,0 +1,197 @@
+import { watch, existsSync, statSync, readdirSync } from 'fs';
+import type { FSWatcher } from 'fs';
+import { join, relative } from 'path';
+import { FileScanner } from '../scanner/index.js';
+import { Indexer } from './index.js';
+import type { IndexOptions } from '../types/index.js';
+
+export interface WatchOptions extends IndexOptions {
+  debounceMs?: number;
+  onUpdate?: (update: WatchUpdate) => void;
+  onError?: (error: unknown) => void;
+}
+
+export interface WatchUpdate {
+  indexed: string[];
+  removed: string[];
+  relinked: number;
+}
+
+/**
+ * Keeps the index in sync with the working tree.
+ *
+ * Changes are collected and debounced, then only the affected files are
+ * re-extracted (or removed), and calls into them are relinked.
+ */
+export class IndexWatcher {
+  private indexer: Indexer;
+  private scanner: FileScanner;
+  private options: WatchOptions;
+  private projectRoot: string;
+  private watchers: Map<string, FSWatcher> = new Map();
+  private pending: Set<string> = new Set();
+  private timer: NodeJS.Timeout | null = null;
+  private flushing: Promise<void> = Promise.resolve();
+
+  constructor(indexer: Indexer, options: WatchOptions = {}) {
+    this.indexer = indexer;
+    this.options = options;
+    this.projectRoot = options.projectRoot || process.cwd();
+    this.scanner = new FileScanner({
+      rootPath: this.projectRoot,
+      ignorePatterns: options.ignorePatterns,
+      includePatterns: options.languages || options.includePatterns || [],
+      maxFileSize: options.maxFileSize,
+      followSymlinks: options.followSymlinks
+    });
+  }
+
+  public start(): void {
+    try {
+      // Recursive watching is not available on every platform / Node version
+      const watcher = watch(this.projectRoot, { recursive: true }, (_event, filename) => {
+        if (filename) this.enqueue(filename.toString());
+      });
+      watcher.on('error', error => this.options.onError?.(error));
+      this.watchers.set('', watcher);
+    } catch {
+      this.watchDirectory('');
+    }
+  }
+
+  public close(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+    this.watchers.forEach(watcher => watcher.close());
+    this.watchers.clear();
+  }
+
+  /**
+   * Resolves once all queued changes have been applied
+   */
+  public async flush(): Promise<void> {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+      this.flushing = this.flushing.then(() => this.applyChanges());
+    }
+    await this.flushing;
+  }
+
+  /**
+   * Queue a path, relative to the project root, that was created, changed or
+   * removed. Called by the file system watchers; ignored paths are dropped.
+   */
+  public enqueue(relativePath: string): void {
+    if (this.scanner.isIgnored(relativePath)) {
+      return;
+    }
+
+    this.pending.add(relativePath);
+    if (this.timer) {
+      clearTimeout(this.timer);
+    }
+    this.timer = setTimeout(() => {
+      this.timer = null;
+      this.flushing = this.flushing.then(() => this.applyChanges());
+    }, this.options.debounceMs ?? 300);
+  }
+
+  private watchDirectory(relativeDir: string): void {
+    if (this.watchers.has(relativeDir) || (relativeDir && this.scanner.isIgnored(relativeDir))) {
+      return;
+    }
+
+    const fullDir = join(this.projectRoot, relativeDir);
+    try {
+      const watcher = watch(fullDir, (_event, filename) => {
+        if (filename) this.enqueue(join(relativeDir, filename.toString()));
+      });
+      watcher.on('error', () => {
+        // Directory was removed
+        watcher.close();
+        this.watchers.delete(relativeDir);
+      });
+      this.watchers.set(relativeDir, watcher);
+
+      for (const entry of readdirSync(fullDir, { withFileTypes: true })) {
+        if (entry.isDirectory()) {
+          this.watchDirectory(join(relativeDir, entry.name));
+        }
+      }
+    } catch {
+      // Unreadable directory
+    }
+  }
+
+  private async applyChanges(): Promise<void> {
+    const paths = Array.from(this.pending);
+    this.pending.clear();
+
+    const update: WatchUpdate = { indexed: [], removed: [], relinked: 0 };
+
+    try {
+      for (const relativePath of paths) {
+        const fullPath = join(this.projectRoot, relativePath);
+
+        if (!existsSync(fullPath)) {
+          if (this.indexer.removeFile(relativePath) > 0) {
+            update.removed.push(relativePath);
+          }
+          continue;
+        }
+
+        const stats = statSync(fullPath);
+        if (stats.isDirectory()) {
+          // New directory (e.g. moved into place) - index its contents
+          if (!this.watchers.has('')) {
+            this.watchDirectory(relativePath);
+          }
+          for (const file of await this.listFiles(fullPath)) {
+            if (await this.indexPath(file)) update.indexed.push(relative(this.projectRoot, file));
+          }
+          continue;
+        }
+
+        if (await this.indexPath(fullPath)) {
+          update.indexed.push(relativePath);
+        }
+      }
+
+      update.relinked = this.indexer.relinkCalls(update.indexed);
+
+      if (update.indexed.length > 0 || update.removed.length > 0) {
+        this.options.onUpdate?.(update);
+      }
+    } catch (error) {
+      this.options.onError?.(error);
+    }
+  }
+
+  private async indexPath(fullPath: string): Promise<boolean> {
+    const fileInfo = await this.scanner.scanFile(fullPath);
+    if (!fileInfo) {
+      return false;
+    }
+
+    const stats = Indexer.emptyStats();
+    await this.indexer.indexFile(fileInfo, stats, { ...this.options, verbose: false });
+    return stats.filesIndexed > 0;
+  }
+
+  private async listFiles(dir: string): Promise<string[]> {
+    const files: string[] = [];
+    for (const entry of readdirSync(dir, { withFileTypes: true })) {
+      const fullPath = join(dir, entry.name);
+      if (this.scanner.isIgnored(relative(this.projectRoot, fullPath))) continue;
+      if (entry.isDirectory()) {
+        files.push(...await this.listFiles(fullPath));
+      } else if (entry.isFile()) {
+        files.push(fullPath);
+      }
+    }
+    return files;
+  }
+}
@@ -0,0 +1,145 @@
+import { Worker } from 'worker_threads';
+import { existsSync } from 'fs';
+import * as os from 'os';
+import { fileURLToPath } from 'url';
+import { ExtractorManager } from '../extractors/extractor-manager.js';
+import type { FileInfo, ExtractedContext } from '../types/index.js';
+
+interface Task {
+  id: number;
+  file: FileInfo;
+  resolve: (context: ExtractedContext) => void;
+  reject: (error: Error) => void;
+}
+
+interface PoolWorker {
+  worker: Worker;
+  task: Task | null;
+}
+
+export interface WorkerScript {
+  url: URL;
+  execArgv?: string[];  // Node options for the worker, e.g. a TypeScript loader
+}
+
+/**
+ * Runs `ExtractorManager.extract` on a pool of worker threads.
+ *
+ * Parsing is synchronous and CPU-bound, so extraction only runs in parallel
+ * when it happens off the main thread. With a single job, or when the
+ * compiled worker script is unavailable (e.g. running from sources), files
+ * are extracted inline instead, one at a time: extractors keep the file
+ * being parsed in instance fields.
+ */
+export class ExtractionPool {
+  private workers: PoolWorker[] = [];
+  private queue: Task[] = [];
+  private nextId = 1;
+  private inline: ExtractorManager | null = null;
+  private inlineQueue: Promise<unknown> = Promise.resolve();
+  private script: WorkerScript;
+  private projectRoot: string;
+
+  constructor(
+    projectRoot: string,
+    jobs: number = ExtractionPool.defaultJobs(),
+    script: WorkerScript = { url: new URL('./extract-worker.js', import.meta.url) }
+  ) {
+    this.script = script;
+    this.projectRoot = projectRoot;
+
+    if (jobs <= 1 || !existsSync(fileURLToPath(script.url))) {
+      this.inline = new ExtractorManager(projectRoot);
+      return;
+    }
+
+    for (let i = 0; i < jobs; i++) {
+      this.workers.push(this.spawn());
+    }
+  }
+
+  public static defaultJobs(): number {
+    // os.availableParallelism() is only available from Node 18.14
+    const cores = os.availableParallelism?.() ?? os.cpus().length;
+    return Math.max(1, cores - 1);
+  }
+
+  /**
+   * Number of files that can be extracted concurrently; 1 when extracting inline
+   */
+  public get size(): number {
+    return this.workers.length || 1;
+  }
+
+  public extract(file: FileInfo): Promise<ExtractedContext> {
+    if (this.inline) {
+      const inline = this.inline;
+      const result = this.inlineQueue.then(() => inline.extract(file));
+      this.inlineQueue = result.catch(() => undefined);
+      return result;
+    }
+
+    return new Promise((resolve, reject) => {
+      this.queue.push({ id: this.nextId++, file, resolve, reject });
+      this.dispatch();
+    });
+  }
+
+  public async close(): Promise<void> {
+    const workers = this.workers;
+    this.workers = [];
+    for (const task of this.queue.splice(0)) {
+      task.reject(new Error('Extraction pool closed'));
+    }
+    await Promise.all(workers.map(entry => entry.worker.terminate()));
+  }
+
+  private spawn(): PoolWorker {
+    const worker = new Worker(this.script.url, {
+      workerData: { projectRoot: this.projectRoot },
+      execArgv: this.script.execArgv
+    });
+    const entry: PoolWorker = { worker, task: null };
+
+    entry.worker.on('message', (message: { id: number; context?: ExtractedContext; error?: string }) => {
+      const task = entry.task;
+      entry.task = null;
+      if (task && task.id === message.id) {
+        if (message.error !== undefined) {
+          task.reject(new Error(message.error));
+        } else {
+          task.resolve(message.context!);
+        }
+      }
+      this.dispatch();
+    });
+
+    // A crashed or exited worker fails its current file and is replaced,
+    // unless the pool is closing
+    entry.worker.on('error', error => this.retire(entry, error));
+    entry.worker.on('exit', code => this.retire(entry, new Error(`Extraction worker exited with code ${code}`)));
+
+    return entry;
+  }
+
+  private retire(entry: PoolWorker, error: Error): void {
+    entry.task?.reject(error);
+    entry.task = null;
+    const index = this.workers.indexOf(entry);
+    if (index !== -1) {
+      this.workers[index] = this.spawn();
+      this.dispatch();
+    }
+  }
+
+  private dispatch(): void {
+    for (const entry of this.workers) {
+      if (this.queue.length === 0) return;
+      if (entry.task) continue;
+
+      const task = this.queue.shift()!;
+      entry.task = task;
+      entry.worker.postMessage({ id: task.id, file: task.file });
+    }
+  }
+}
@@ -0,0 +1,138 @@
+import { PrimordynLanguageServer } from '../server.js';
+import { PrimordynDB } from '../../database/index.js';
+import { Indexer } from '../../indexer/index.js';
+import { PassThrough } from 'stream';
+import { mkdirSync, writeFileSync, rmSync, existsSync } from 'fs';
+import { join } from 'path';
+import { pathToFileURL } from 'url';
+
+/**
+ * Scripted LSP client speaking Content-Length framed JSON-RPC
+ */
+class TestClient {
+  private input = new PassThrough();
+  private output = new PassThrough();
+  private buffer = '';
+  private nextId = 1;
+  private pending = new Map<number, (result: unknown) => void>();
+
+  constructor(projectRoot: string) {
+    const server = new PrimordynLanguageServer(this.input, this.output, { projectRoot, onExit: () => undefined });
+    server.listen();
+
+    this.output.on('data', (chunk: Buffer) => {
+      this.buffer += chunk.toString('utf-8');
+      let match;
+      while ((match = this.buffer.match(/^Content-Length: (\d+)\r\n\r\n/))) {
+        const start = match[0].length;
+        const length = parseInt(match[1], 10);
+        if (this.buffer.length < start + length) break;
+        const message = JSON.parse(this.buffer.substring(start, start + length));
+        this.buffer = this.buffer.substring(start + length);
+        this.pending.get(message.id)?.(message.result ?? message.error);
+      }
+    });
+  }
+
+  request<T>(method: string, params: unknown): Promise<T> {
+    const id = this.nextId++;
+    const body = JSON.stringify({ jsonrpc: '2.0', id, method, params });
+    return new Promise(resolve => {
+      this.pending.set(id, result => resolve(result as T));
+      this.input.write(`Content-Length: ${Buffer.byteLength(body)}\r\n\r\n${body}`);
+    });
+  }
+
+  notify(method: string, params: unknown): void {
+    const body = JSON.stringify({ jsonrpc: '2.0', method, params });
+    this.input.write(`Content-Length: ${Buffer.byteLength(body)}\r\n\r\n${body}`);
+  }
+}
+
+describe('PrimordynLanguageServer', () => {
+  const testDir = join(process.cwd(), '.test-lsp');
+  const mathUri = pathToFileURL(join(testDir, 'math.ts')).toString();
+  const appUri = pathToFileURL(join(testDir, 'app.ts')).toString();
+  let client: TestClient;
+
+  beforeAll(async () => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(testDir, { recursive: true });
+
+    writeFileSync(join(testDir, 'math.ts'), [
+      '/** Adds two numbers */',
+      'export function add(a: number, b: number): number {',
+      '  return a + b;',
+      '}'
+    ].join('\n'));
+    writeFileSync(join(testDir, 'app.ts'), [
+      "import { add } from './math';",
+      '',
+      'export function main() {',
+      '  return add(1, 2);',
+      '}'
+    ].join('\n'));
+
+    const db = new PrimordynDB(testDir);
+    await new Indexer(db).index({ projectRoot: testDir, verbose: false });
+    db.close();
+
+    client = new TestClient(testDir);
+    await client.request('initialize', { rootUri: pathToFileURL(testDir).toString() });
+  });
+
+  afterAll(async () => {
+    await client.request('shutdown', null);
+    client.notify('exit', null);
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should serve workspace symbols from the index', async () => {
+    const symbols = await client.request<{ name: string; location: { uri: string } }[]>(
+      'workspace/symbol', { query: 'ad' }
+    );
+
+    const add = symbols.filter(s => s.name === 'add');
+    // The export of `add` is not listed next to its definition
+    expect(add).toHaveLength(1);
+    expect(add[0].location.uri).toBe(mathUri);
+  });
+
+  test('should go to the definition of a called function', async () => {
+    const location = await client.request<{ uri: string; range: { start: { line: number } } }>(
+      'textDocument/definition', { textDocument: { uri: appUri }, position: { line: 3, character: 10 } }
+    );
+
+    expect(location.uri).toBe(mathUri);
+    expect(location.range.start.line).toBe(1);
+  });
+
+  test('should list references from the call graph', async () => {
+    const locations = await client.request<{ uri: string; range: { start: { line: number } } }[]>(
+      'textDocument/references', { textDocument: { uri: mathUri }, position: { line: 1, character: 17 } }
+    );
+
+    expect(locations).toContainEqual(expect.objectContaining({
+      uri: appUri,
+      range: expect.objectContaining({ start: { line: 3, character: 9 } })
+    }));
+  });
+
+  test('should show the signature on hover', async () => {
+    const hover = await client.request<{ contents: { value: string } }>(
+      'textDocument/hover', { textDocument: { uri: appUri }, position: { line: 3, character: 10 } }
+    );
+
+    expect(hover.contents.value).toContain('function add(');
+    expect(hover.contents.value).toContain('math.ts:2');
+  });
+
+  test('should reject unknown methods', async () => {
+    const error = await client.request<{ code: number }>('textDocument/unknown', {});
+    expect(error.code).toBe(-32601);
+  });
+});
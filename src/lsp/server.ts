@@ -0,0 +1,407 @@
+import { readFileSync } from 'fs';
+import { isAbsolute, relative, resolve } from 'path';
+import { fileURLToPath, pathToFileURL } from 'url';
+import type { Readable, Writable } from 'stream';
+import { JsonRpcConnection, JsonRpcError, ErrorCodes } from '../rpc/connection.js';
+import { PrimordynDB } from '../database/index.js';
+import { Indexer } from '../indexer/index.js';
+import { lastSegment } from '../indexer/linker.js';
+import { FileScanner } from '../scanner/index.js';
+
+interface Position {
+  line: number;       // 0-based
+  character: number;  // 0-based
+}
+
+interface Range {
+  start: Position;
+  end: Position;
+}
+
+interface Location {
+  uri: string;
+  range: Range;
+}
+
+interface TextDocumentPositionParams {
+  textDocument: { uri: string };
+  position: Position;
+}
+
+interface ReferenceParams extends TextDocumentPositionParams {
+  context?: { includeDeclaration?: boolean };
+}
+
+interface InitializeParams {
+  rootUri?: string | null;
+  rootPath?: string | null;
+}
+
+interface SymbolInformation {
+  name: string;
+  kind: number;
+  location: Location;
+  containerName?: string;
+}
+
+interface SymbolRow {
+  id: number;
+  name: string;
+  type: string;
+  line_start: number;
+  line_end: number;
+  signature: string | null;
+  documentation: string | null;
+  metadata: string | null;
+  file_id: number;
+  relative_path: string;
+  language: string | null;
+}
+
+export interface LanguageServerOptions {
+  projectRoot?: string;
+  onExit?: (code: number) => void;
+}
+
+// LSP SymbolKind values
+const SYMBOL_KINDS: Record<string, number> = {
+  module: 2,
+  namespace: 3,
+  class: 5,
+  method: 6,
+  property: 7,
+  enum: 10,
+  interface: 11,
+  function: 12,
+  variable: 13,
+  constant: 14,
+  struct: 23,
+  trait: 11,
+  type: 26
+};
+
+const SYMBOL_COLUMNS = `
+  s.id, s.name, s.type, s.line_start, s.line_end, s.signature, s.documentation, s.metadata,
+  s.file_id, f.relative_path, f.language
+`;
+
+/**
+ * Language server backed by the Primordyn index.
+ *
+ * Serves workspace symbols from `symbols_fts`, definitions and references
+ * from `call_graph` and `symbol_references`, hover from symbol signatures
+ * and documentation, and re-indexes files when they are saved.
+ */
+export class PrimordynLanguageServer {
+  private connection: JsonRpcConnection;
+  private projectRoot: string;
+  private db: PrimordynDB | null = null;
+  private indexer: Indexer | null = null;
+  private scanner: FileScanner | null = null;
+  private shutdownRequested = false;
+  private onExit: (code: number) => void;
+
+  constructor(input: Readable, output: Writable, options: LanguageServerOptions = {}) {
+    this.connection = new JsonRpcConnection(input, output, 'header');
+    this.projectRoot = options.projectRoot || process.cwd();
+    this.onExit = options.onExit || ((code) => process.exit(code));
+    this.registerHandlers();
+  }
+
+  public listen(): void {
+    this.connection.listen();
+  }
+
+  private registerHandlers(): void {
+    this.connection.onRequest('initialize', (params) => this.initialize(params as InitializeParams));
+    this.connection.onRequest('shutdown', () => {
+      this.shutdownRequested = true;
+      return null;
+    });
+    this.connection.onNotification('exit', () => {
+      this.db?.close();
+      this.db = null;
+      this.onExit(this.shutdownRequested ? 0 : 1);
+    });
+
+    this.connection.onRequest('workspace/symbol', (params) =>
+      this.workspaceSymbols((params as { query?: string }).query || '')
+    );
+    this.connection.onRequest('textDocument/definition', (params) =>
+      this.definition(params as TextDocumentPositionParams)
+    );
+    this.connection.onRequest('textDocument/references', (params) =>
+      this.references(params as ReferenceParams)
+    );
+    this.connection.onRequest('textDocument/hover', (params) =>
+      this.hover(params as TextDocumentPositionParams)
+    );
+    this.connection.onNotification('textDocument/didSave', (params) =>
+      this.didSave((params as { textDocument: { uri: string } }).textDocument.uri)
+    );
+  }
+
+  private initialize(params: InitializeParams) {
+    if (params?.rootUri) {
+      this.projectRoot = fileURLToPath(params.rootUri);
+    } else if (params?.rootPath) {
+      this.projectRoot = params.rootPath;
+    }
+
+    this.db = new PrimordynDB(this.projectRoot);
+    this.indexer = new Indexer(this.db);
+    this.indexer.refreshCrates(this.projectRoot);
+    this.scanner = new FileScanner({ rootPath: this.projectRoot });
+
+    return {
+      capabilities: {
+        textDocumentSync: { openClose: false, change: 0, save: { includeText: false } },
+        workspaceSymbolProvider: true,
+        definitionProvider: true,
+        referencesProvider: true,
+        hoverProvider: true
+      },
+      serverInfo: { name: 'primordyn', version: '0.1.0' }
+    };
+  }
+
+  private workspaceSymbols(query: string): SymbolInformation[] {
+    const database = this.getDatabase();
+    const terms = query.replace(/[^a-zA-Z0-9_\s]/g, ' ').split(/\s+/).filter(Boolean);
+
+    const rows = terms.length > 0
+      ? database.prepare(`
+          SELECT ${SYMBOL_COLUMNS}
+          FROM symbols_fts fts
+          JOIN symbols s ON fts.rowid = s.id
+          JOIN files f ON s.file_id = f.id
+          WHERE symbols_fts MATCH ? AND s.type NOT IN ('import', 'export')
+          ORDER BY bm25(symbols_fts), LENGTH(s.name)
+          LIMIT 100
+        `).all(terms.map(term => `"${term}"*`).join(' ')) as SymbolRow[]
+      : database.prepare(`
+          SELECT ${SYMBOL_COLUMNS}
+          FROM symbols s
+          JOIN files f ON s.file_id = f.id
+          WHERE s.type NOT IN ('import', 'export')
+          ORDER BY s.name
+          LIMIT 100
+        `).all() as SymbolRow[];
+
+    return rows.map(row => {
+      const metadata = row.metadata ? JSON.parse(row.metadata) : {};
+      return {
+        name: row.name,
+        kind: SYMBOL_KINDS[row.type] || 13,
+        location: this.symbolLocation(row),
+        containerName: metadata.owner || metadata.className || row.relative_path
+      };
+    });
+  }
+
+  private definition(params: TextDocumentPositionParams): Location | null {
+    const symbol = this.resolveSymbolAt(params.textDocument.uri, params.position);
+    return symbol ? this.symbolLocation(symbol) : null;
+  }
+
+  private references(params: ReferenceParams): Location[] {
+    const database = this.getDatabase();
+    const target = this.resolveSymbolAt(params.textDocument.uri, params.position);
+    const word = this.wordAt(params.textDocument.uri, params.position);
+    if (!target && !word) {
+      return [];
+    }
+
+    const name = target ? lastSegment(target.name) : word!;
+    type ReferenceLocation = { line_number: number; column_number: number | null; relative_path: string };
+    // Calls to a resolved symbol come from the call graph; type names, reads
+    // and imports (and, without a target, calls too) from the reference index
+    const calls = (target
+      ? database.prepare(`
+          SELECT cg.line_number, cg.column_number, f.relative_path
+          FROM call_graph cg
+          JOIN files f ON cg.caller_file_id = f.id
+          WHERE cg.callee_symbol_id = ?
+        `).all(target.id)
+      : []
+    ) as ReferenceLocation[];
+    const references = database.prepare(`
+      SELECT r.line as line_number, r.column_number, f.relative_path
+      FROM symbol_references r
+      JOIN files f ON r.file_id = f.id
+      WHERE r.short_name = ? AND (? = 0 OR r.kind != 'call')
+    `).all(name, target ? 1 : 0) as ReferenceLocation[];
+    const rows = [...calls, ...references].sort((a, b) =>
+      a.relative_path.localeCompare(b.relative_path) || a.line_number - b.line_number
+    );
+
+    const locations = rows.map(row => ({
+      uri: this.toUri(row.relative_path),
+      range: this.nameRange(row.relative_path, row.line_number, name, row.column_number || 0)
+    }));
+
+    if (target && params.context?.includeDeclaration) {
+      locations.unshift(this.symbolLocation(target));
+    }
+
+    return locations;
+  }
+
+  private hover(params: TextDocumentPositionParams) {
+    const symbol = this.resolveSymbolAt(params.textDocument.uri, params.position);
+    if (!symbol) {
+      return null;
+    }
+
+    const parts: string[] = [];
+    if (symbol.signature) {
+      parts.push('```' + (symbol.language || '') + '\n' + symbol.signature + '\n```');
+    }
+    if (symbol.documentation) {
+      parts.push(symbol.documentation);
+    }
+    parts.push(`*${symbol.type}* — ${symbol.relative_path}:${symbol.line_start}`);
+
+    return {
+      contents: { kind: 'markdown', value: parts.join('\n\n') }
+    };
+  }
+
+  private async didSave(uri: string): Promise<void> {
+    if (!this.indexer || !this.scanner) {
+      return;
+    }
+
+    const fileInfo = await this.scanner.scanFile(fileURLToPath(uri));
+    if (fileInfo) {
+      await this.indexer.indexFile(fileInfo);
+      this.indexer.relinkCalls([fileInfo.relativePath]);
+    }
+  }
+
+  /**
+   * Find the symbol referred to at a position: a declaration on that line,
+   * a resolved call from the call graph, or a symbol with the same name.
+   */
+  private resolveSymbolAt(uri: string, position: Position): SymbolRow | null {
+    const database = this.getDatabase();
+    const word = this.wordAt(uri, position);
+    if (!word) {
+      return null;
+    }
+
+    const file = database.prepare('SELECT id FROM files WHERE relative_path = ?')
+      .get(this.toRelative(uri)) as { id: number } | undefined;
+    const line = position.line + 1;
+
+    if (file) {
+      // Cursor on a declaration
+      const declarations = database.prepare(`
+        SELECT ${SYMBOL_COLUMNS}
+        FROM symbols s
+        JOIN files f ON s.file_id = f.id
+        WHERE s.file_id = ? AND s.line_start = ? AND s.type NOT IN ('import', 'export')
+      `).all(file.id, line) as SymbolRow[];
+      const declaration = declarations.find(row => lastSegment(row.name) === word);
+      if (declaration) {
+        return declaration;
+      }
+
+      // Cursor on a call the indexer already resolved
+      const calls = database.prepare(`
+        SELECT callee_name, callee_symbol_id
+        FROM call_graph
+        WHERE caller_file_id = ? AND line_number = ? AND callee_symbol_id IS NOT NULL
+      `).all(file.id, line) as { callee_name: string; callee_symbol_id: number }[];
+      const call = calls.find(row => lastSegment(row.callee_name) === word);
+      if (call) {
+        const callee = database.prepare(`
+          SELECT ${SYMBOL_COLUMNS}
+          FROM symbols s
+          JOIN files f ON s.file_id = f.id
+          WHERE s.id = ?
+        `).get(call.callee_symbol_id) as SymbolRow | undefined;
+        if (callee) {
+          return callee;
+        }
+      }
+    }
+
+    // Fall back to a name match, preferring the current file
+    return (database.prepare(`
+      SELECT ${SYMBOL_COLUMNS}
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      WHERE (s.name = ? OR s.name LIKE ? OR s.name LIKE ?)
+        AND s.type NOT IN ('import', 'export')
+      ORDER BY
+        CASE WHEN s.file_id = ? THEN 0 ELSE 1 END,
+        CASE WHEN s.name = ? THEN 0 ELSE 1 END
+      LIMIT 1
+    `).get(word, `%.${word}`, `%::${word}`, file?.id ?? -1, word) as SymbolRow | undefined) || null;
+  }
+
+  private wordAt(uri: string, position: Position): string | null {
+    const lineText = this.readLines(this.toRelative(uri))[position.line];
+    if (lineText === undefined) {
+      return null;
+    }
+
+    const isWordChar = (char: string | undefined) => char !== undefined && /[A-Za-z0-9_$]/.test(char);
+    let start = position.character;
+    let end = position.character;
+    while (start > 0 && isWordChar(lineText[start - 1])) start--;
+    while (isWordChar(lineText[end])) end++;
+
+    const word = lineText.substring(start, end);
+    return word.length > 0 ? word : null;
+  }
+
+  private symbolLocation(symbol: SymbolRow): Location {
+    return {
+      uri: this.toUri(symbol.relative_path),
+      range: this.nameRange(symbol.relative_path, symbol.line_start, lastSegment(symbol.name), 0)
+    };
+  }
+
+  /**
+   * Range of `name` on a 1-based line, searching from the recorded column
+   */
+  private nameRange(relativePath: string, line: number, name: string, column: number): Range {
+    const lineText = this.readLines(relativePath)[line - 1] || '';
+    let character = lineText.indexOf(name, Math.max(0, column - 1));
+    if (character === -1) {
+      character = Math.max(0, lineText.indexOf(name));
+    }
+    return {
+      start: { line: line - 1, character },
+      end: { line: line - 1, character: character + name.length }
+    };
+  }
+
+  private readLines(relativePath: string): string[] {
+    try {
+      return readFileSync(resolve(this.projectRoot, relativePath), 'utf-8').split('\n');
+    } catch {
+      const row = this.getDatabase().prepare('SELECT content FROM files WHERE relative_path = ?')
+        .get(relativePath) as { content: string } | undefined;
+      return row ? row.content.split('\n') : [];
+    }
+  }
+
+  private toUri(relativePath: string): string {
+    return pathToFileURL(resolve(this.projectRoot, relativePath)).toString();
+  }
+
+  private toRelative(uri: string): string {
+    const fullPath = uri.startsWith('file:') ? fileURLToPath(uri) : uri;
+    return isAbsolute(fullPath) ? relative(this.projectRoot, fullPath) : fullPath;
+  }
+
+  private getDatabase() {
+    if (!this.db) {
+      throw new JsonRpcError(ErrorCodes.InvalidRequest, 'Server not initialized');
+    }
+    return this.db.getDatabase();
+  }
+}
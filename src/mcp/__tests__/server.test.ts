@@ -0,0 +1,84 @@
+import { PrimordynMcpServer } from '../server.js';
+import { PrimordynDB } from '../../database/index.js';
+import { Indexer } from '../../indexer/index.js';
+import { PassThrough } from 'stream';
+import { mkdirSync, writeFileSync, rmSync, existsSync } from 'fs';
+import { join } from 'path';
+
+describe('PrimordynMcpServer', () => {
+  const testDir = join(process.cwd(), '.test-mcp');
+  const input = new PassThrough();
+  const output = new PassThrough();
+  const pending = new Map<number, (message: { result?: Record<string, unknown>; error?: { code: number } }) => void>();
+  let nextId = 1;
+  let buffer = '';
+
+  const request = (method: string, params: unknown = {}) => {
+    const id = nextId++;
+    return new Promise<{ result?: Record<string, unknown>; error?: { code: number } }>(resolve => {
+      pending.set(id, resolve);
+      input.write(JSON.stringify({ jsonrpc: '2.0', id, method, params }) + '\n');
+    });
+  };
+
+  beforeAll(async () => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(testDir, { recursive: true });
+    writeFileSync(join(testDir, 'greet.ts'), 'export function greet(name: string) {\n  return `hi ${name}`;\n}\n');
+
+    const db = new PrimordynDB(testDir);
+    await new Indexer(db).index({ projectRoot: testDir, verbose: false });
+    db.close();
+
+    output.on('data', (chunk: Buffer) => {
+      buffer += chunk.toString('utf-8');
+      let newline;
+      while ((newline = buffer.indexOf('\n')) !== -1) {
+        const message = JSON.parse(buffer.substring(0, newline));
+        buffer = buffer.substring(newline + 1);
+        pending.get(message.id)?.(message);
+      }
+    });
+
+    new PrimordynMcpServer(input, output, { projectRoot: testDir }).listen();
+    await request('initialize', { protocolVersion: '2024-11-05', capabilities: {} });
+  });
+
+  afterAll(() => {
+    input.end();
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should list tools with JSON-schema inputs', async () => {
+    const { result } = await request('tools/list');
+    const tools = result?.tools as { name: string; inputSchema: { required?: string[] } }[];
+
+    expect(tools.map(t => t.name)).toEqual(expect.arrayContaining([
+      'query', 'find_symbol', 'find_usages', 'dependency_graph', 'impact_analysis', 'git_history'
+    ]));
+    expect(tools.find(t => t.name === 'find_symbol')?.inputSchema.required).toEqual(['name']);
+  });
+
+  test('should call tools against the index', async () => {
+    const { result } = await request('tools/call', { name: 'find_symbol', arguments: { name: 'greet' } });
+    const content = result?.content as { text: string }[];
+
+    expect(JSON.parse(content[0].text)).toContainEqual(expect.objectContaining({ name: 'greet', type: 'function' }));
+  });
+
+  test('should report invalid tool arguments as tool errors', async () => {
+    const { result } = await request('tools/call', { name: 'query', arguments: {} });
+    expect(result?.isError).toBe(true);
+  });
+
+  test('should expose indexed files as resources', async () => {
+    const { result } = await request('resources/read', { uri: 'primordyn://file/greet.ts' });
+    const contents = result?.contents as { text: string }[];
+
+    expect(contents[0].text).toContain('function greet');
+  });
+});
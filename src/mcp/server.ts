@@ -0,0 +1,290 @@
+import type { Readable, Writable } from 'stream';
+import { JsonRpcConnection, JsonRpcError, ErrorCodes } from '../rpc/connection.js';
+import { PrimordynDB } from '../database/index.js';
+import { ContextRetriever } from '../retriever/index.js';
+import { Indexer } from '../indexer/index.js';
+
+const PROTOCOL_VERSION = '2024-11-05';
+
+interface JsonSchema {
+  type: 'object';
+  properties: Record<string, { type: string; description: string; items?: { type: string }; minimum?: number; maximum?: number }>;
+  required?: string[];
+}
+
+interface ToolDefinition {
+  name: string;
+  description: string;
+  inputSchema: JsonSchema;
+  handler: (args: Record<string, unknown>) => Promise<unknown>;
+}
+
+interface ResourceDefinition {
+  uri: string;
+  name: string;
+  description: string;
+  mimeType: string;
+  read: () => Promise<string>;
+}
+
+export interface McpServerOptions {
+  projectRoot?: string;
+}
+
+/**
+ * Model Context Protocol server exposing the retriever as tools and the
+ * index as resources, over newline-delimited JSON-RPC on stdio.
+ */
+export class PrimordynMcpServer {
+  private connection: JsonRpcConnection;
+  private db: PrimordynDB;
+  private retriever: ContextRetriever;
+  private indexer: Indexer;
+  private tools: ToolDefinition[];
+  private resources: ResourceDefinition[];
+
+  constructor(input: Readable, output: Writable, options: McpServerOptions = {}) {
+    this.connection = new JsonRpcConnection(input, output, 'newline');
+    this.db = new PrimordynDB(options.projectRoot || process.cwd());
+    this.retriever = new ContextRetriever(this.db);
+    this.indexer = new Indexer(this.db);
+    this.tools = this.defineTools();
+    this.resources = this.defineResources();
+    this.registerHandlers();
+  }
+
+  public listen(): void {
+    this.connection.onClose(() => this.db.close());
+    this.connection.listen();
+  }
+
+  private registerHandlers(): void {
+    this.connection.onRequest('initialize', (params) => ({
+      protocolVersion: (params as { protocolVersion?: string })?.protocolVersion || PROTOCOL_VERSION,
+      capabilities: { tools: {}, resources: {} },
+      serverInfo: { name: 'primordyn', version: '0.1.0' }
+    }));
+    this.connection.onRequest('ping', () => ({}));
+
+    this.connection.onRequest('tools/list', () => ({
+      tools: this.tools.map(({ name, description, inputSchema }) => ({ name, description, inputSchema }))
+    }));
+    this.connection.onRequest('tools/call', (params) =>
+      this.callTool(params as { name: string; arguments?: Record<string, unknown> })
+    );
+
+    this.connection.onRequest('resources/list', () => ({
+      resources: this.resources.map(({ uri, name, description, mimeType }) => ({ uri, name, description, mimeType }))
+    }));
+    this.connection.onRequest('resources/templates/list', () => ({
+      resourceTemplates: [{
+        uriTemplate: 'primordyn://file/{path}',
+        name: 'Indexed file',
+        description: 'Content of an indexed file, by project-relative path',
+        mimeType: 'text/plain'
+      }]
+    }));
+    this.connection.onRequest('resources/read', (params) =>
+      this.readResource((params as { uri: string }).uri)
+    );
+  }
+
+  private async callTool(params: { name: string; arguments?: Record<string, unknown> }) {
+    const tool = this.tools.find(t => t.name === params?.name);
+    if (!tool) {
+      throw new JsonRpcError(ErrorCodes.InvalidParams, `Unknown tool: ${params?.name}`);
+    }
+
+    try {
+      const result = await tool.handler(params.arguments || {});
+      return {
+        content: [{ type: 'text', text: JSON.stringify(result ?? null, null, 2) }]
+      };
+    } catch (error) {
+      // Tool failures are reported to the model rather than as protocol errors
+      return {
+        content: [{ type: 'text', text: error instanceof Error ? error.message : String(error) }],
+        isError: true
+      };
+    }
+  }
+
+  private async readResource(uri: string) {
+    const resource = this.resources.find(r => r.uri === uri);
+    if (resource) {
+      return { contents: [{ uri, mimeType: resource.mimeType, text: await resource.read() }] };
+    }
+
+    const filePrefix = 'primordyn://file/';
+    if (uri?.startsWith(filePrefix)) {
+      const relativePath = decodeURIComponent(uri.substring(filePrefix.length));
+      const file = this.db.getDatabase().prepare('SELECT content FROM files WHERE relative_path = ?')
+        .get(relativePath) as { content: string } | undefined;
+      if (file) {
+        return { contents: [{ uri, mimeType: 'text/plain', text: file.content }] };
+      }
+    }
+
+    throw new JsonRpcError(ErrorCodes.InvalidParams, `Resource not found: ${uri}`);
+  }
+
+  private defineTools(): ToolDefinition[] {
+    return [
+      {
+        name: 'query',
+        description: 'Search the index for files and symbols matching a query, within a token budget',
+        inputSchema: {
+          type: 'object',
+          properties: {
+            query: { type: 'string', description: 'Search terms: "phrases", prefix*, AND/OR/NOT, and type:, lang:, path:, name: filters' },
+            maxTokens: { type: 'number', description: 'Maximum tokens in the result (default: 8000)', minimum: 100, maximum: 100000 },
+            languages: { type: 'array', items: { type: 'string' }, description: 'Only include these languages' },
+            crates: { type: 'array', items: { type: 'string' }, description: 'Only include these Cargo crates' },
+            semantic: { type: 'boolean', description: 'Also match by meaning, for natural-language queries' }
+          },
+          required: ['query']
+        },
+        handler: (args) => this.retriever.query(requireString(args, 'query'), {
+          maxTokens: optionalNumber(args, 'maxTokens') || 8000,
+          includeContent: true,
+          includeSymbols: true,
+          includeImports: true,
+          fileTypes: optionalStrings(args, 'languages'),
+          crates: optionalStrings(args, 'crates'),
+          sortBy: 'relevance',
+          semantic: args.semantic === true
+        })
+      },
+      {
+        name: 'find_symbol',
+        description: 'Find symbol definitions (functions, classes, types, ...) by name',
+        inputSchema: {
+          type: 'object',
+          properties: {
+            name: { type: 'string', description: 'Symbol name' },
+            languages: { type: 'array', items: { type: 'string' }, description: 'Only include these languages' }
+          },
+          required: ['name']
+        },
+        handler: (args) => this.retriever.findSymbol(requireString(args, 'name'), {
+          fileTypes: optionalStrings(args, 'languages')
+        })
+      },
+      {
+        name: 'find_usages',
+        description: 'Find files that use a symbol, within a token budget',
+        inputSchema: {
+          type: 'object',
+          properties: {
+            name: { type: 'string', description: 'Symbol name' },
+            maxTokens: { type: 'number', description: 'Maximum tokens in the result (default: 2000)', minimum: 100, maximum: 100000 }
+          },
+          required: ['name']
+        },
+        handler: (args) => this.retriever.findUsages(requireString(args, 'name'), {
+          maxTokens: optionalNumber(args, 'maxTokens') || 2000
+        })
+      },
+      {
+        name: 'dependency_graph',
+        description: 'Show what a symbol calls and what calls it',
+        inputSchema: {
+          type: 'object',
+          properties: {
+            symbol: { type: 'string', description: 'Symbol name' },
+            depth: { type: 'number', description: 'Traversal depth (default: 1)', minimum: 1, maximum: 5 }
+          },
+          required: ['symbol']
+        },
+        handler: (args) => this.retriever.getDependencyGraphWithDepth(
+          requireString(args, 'symbol'),
+          optionalNumber(args, 'depth') || 1
+        )
+      },
+      {
+        name: 'impact_analysis',
+        description: 'Estimate what would be affected by changing a symbol',
+        inputSchema: {
+          type: 'object',
+          properties: {
+            symbol: { type: 'string', description: 'Symbol name' }
+          },
+          required: ['symbol']
+        },
+        handler: (args) => this.retriever.getImpactAnalysis(requireString(args, 'symbol'))
+      },
+      {
+        name: 'git_history',
+        description: 'Show recent commits touching a symbol',
+        inputSchema: {
+          type: 'object',
+          properties: {
+            symbol: { type: 'string', description: 'Symbol name' }
+          },
+          required: ['symbol']
+        },
+        handler: (args) => this.retriever.getGitHistory(requireString(args, 'symbol'))
+      }
+    ];
+  }
+
+  private defineResources(): ResourceDefinition[] {
+    return [
+      {
+        uri: 'primordyn://summary',
+        name: 'Project summary',
+        description: 'Overview of the indexed project',
+        mimeType: 'text/plain',
+        read: () => this.retriever.getContextSummary()
+      },
+      {
+        uri: 'primordyn://stats',
+        name: 'Index statistics',
+        description: 'File, symbol, token and language counts',
+        mimeType: 'application/json',
+        read: async () => JSON.stringify(await this.indexer.getIndexStats(), null, 2)
+      },
+      {
+        uri: 'primordyn://files',
+        name: 'Indexed files',
+        description: 'All indexed files with language and size; read one with primordyn://file/{path}',
+        mimeType: 'application/json',
+        read: async () => JSON.stringify(
+          this.db.getDatabase().prepare(`
+            SELECT relative_path as path, language, size
+            FROM files
+            ORDER BY relative_path
+          `).all(),
+          null,
+          2
+        )
+      }
+    ];
+  }
+}
+
+function requireString(args: Record<string, unknown>, key: string): string {
+  const value = args[key];
+  if (typeof value !== 'string' || value.trim().length === 0) {
+    throw new Error(`'${key}' must be a non-empty string`);
+  }
+  return value.trim();
+}
+
+function optionalNumber(args: Record<string, unknown>, key: string): number | undefined {
+  const value = args[key];
+  if (value === undefined) return undefined;
+  if (typeof value !== 'number' || !Number.isFinite(value) || value <= 0) {
+    throw new Error(`'${key}' must be a positive number`);
+  }
+  return Math.floor(value);
+}
+
+function optionalStrings(args: Record<string, unknown>, key: string): string[] | undefined {
+  const value = args[key];
+  if (value === undefined) return undefined;
+  if (!Array.isArray(value) || !value.every(item => typeof item === 'string')) {
+    throw new Error(`'${key}' must be an array of strings`);
+  }
+  return value.length > 0 ? value : undefined;
+}
@@ -0,0 +1,19 @@
+import { editDistance, FuzzyIndex } from '../fuzzy.js';
+
+describe('FuzzyIndex', () => {
+  test('should count swaps of adjacent characters as one edit', () => {
+    expect(editDistance('parser', 'parser')).toBe(0);
+    expect(editDistance('pasrer', 'parser')).toBe(1);
+    expect(editDistance('parsr', 'parser')).toBe(1);
+    expect(editDistance('', 'abc')).toBe(3);
+  });
+
+  test('should rank names by similarity to a misspelled pattern', () => {
+    const index = new FuzzyIndex(['Parser', 'parseConfig', 'Printer', 'getUserById', 'models.User']);
+
+    expect(index.search('Parsr').map(match => match.name)[0]).toBe('Parser');
+    expect(index.search('getUsr')[0]).toMatchObject({ name: 'getUserById' });
+    expect(index.search('models::User')[0]).toMatchObject({ name: 'models.User', similarity: 1, distance: 0 });
+    expect(index.search('zzzz')).toEqual([]);
+  });
+});
@@ -0,0 +1,72 @@
+import { ContextPacker, elideMiddle, skeleton } from '../packer.js';
+
+// One token per character keeps the arithmetic readable
+const packer = new ContextPacker(value => String(value).length);
+
+describe('ContextPacker', () => {
+  test('should shorten a large result instead of letting it take the whole budget', () => {
+    const { items, report } = packer.pack([
+      {
+        label: 'big.ts',
+        relevance: 1,
+        variants: [
+          { form: 'full', item: 'x'.repeat(100) },
+          { form: 'skeleton', item: 'x'.repeat(20) },
+          { form: 'signature', item: 'x'.repeat(5) }
+        ]
+      },
+      { label: 'small.ts', relevance: 0.8, variants: [{ form: 'full', item: 'y'.repeat(30) }] },
+      { label: 'other.ts', relevance: 0.5, variants: [{ form: 'full', item: 'z'.repeat(40) }] }
+    ], 60);
+
+    expect(items.map(item => item.form)).toEqual(['skeleton', 'full']);
+    expect(report).toEqual({
+      budget: 60,
+      used: 50,
+      reduced: [{ label: 'big.ts', form: 'skeleton', savedTokens: 80 }],
+      dropped: [{ label: 'other.ts', tokens: 40 }]
+    });
+  });
+
+  test('should keep everything whole when it fits', () => {
+    const { items, report } = packer.pack([
+      { label: 'a', relevance: 1, variants: [{ form: 'full', item: 'aaaa' }, { form: 'signature', item: 'a' }] },
+      { label: 'b', relevance: 0, variants: [{ form: 'full', item: 'bb' }] }
+    ], 10);
+
+    expect(items.map(item => item.item)).toEqual(['aaaa', 'bb']);
+    expect(report.reduced).toEqual([]);
+    expect(report.dropped).toEqual([]);
+  });
+});
+
+describe('packing forms', () => {
+  const lines = Array.from({ length: 100 }, (_, i) => `line ${i + 1}`).join('\n');
+
+  test('should skip results with no form to include', () => {
+    const { items, report } = packer.pack([
+      { label: 'empty', relevance: 1, variants: [] },
+      { label: 'b', relevance: 0.5, variants: [{ form: 'full', item: 'bb' }] }
+    ], 10);
+
+    expect(items.map(item => item.item)).toEqual(['bb']);
+    expect(report.dropped).toEqual([]);
+  });
+
+  test('should elide the middle of long text only', () => {
+    const elided = elideMiddle(lines, 10)!.split('\n');
+
+    expect(elided).toHaveLength(11);
+    expect(elided[0]).toBe('line 1');
+    expect(elided[6]).toBe('... 90 lines elided ...');
+    expect(elided[10]).toBe('line 100');
+    expect(elideMiddle('short\ntext', 10)).toBeNull();
+  });
+
+  test('should outline symbols by their first lines', () => {
+    const content = 'import x;\nclass A {\n  run() {\n    go();\n  }\n}\n';
+
+    expect(skeleton(content, [2, 3])).toBe('...\nclass A {\n  run() {\n...');
+    expect(skeleton(content, [])).toBeNull();
+  });
+});
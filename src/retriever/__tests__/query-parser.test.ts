@@ -0,0 +1,51 @@
+import { parseQuery, QueryParseError } from '../query-parser.js';
+
+describe('parseQuery', () => {
+  test('should quote every term so punctuation never reaches FTS5', () => {
+    expect(parseQuery('Foo::bar').match).toBe('"Foo::bar"');
+    expect(parseQuery('user.id').match).toBe('"user.id"');
+    expect(parseQuery('say "hi"').match).toBe('("say" AND "hi")');
+    expect(parseQuery('"exact phrase"').match).toBe('"exact phrase"');
+    expect(parseQuery('"a ""quoted"" word"').terms).toEqual(['a "quoted" word']);
+  });
+
+  test('should support prefixes and boolean operators', () => {
+    expect(parseQuery('pars*').match).toBe('"pars"*');
+    expect(parseQuery('parse OR lex').match).toBe('("parse" OR "lex")');
+    expect(parseQuery('parse AND (lex OR scan)').match).toBe('("parse" AND ("lex" OR "scan"))');
+    expect(parseQuery('parse NOT test -mock').match).toBe('("parse" NOT "test" NOT "mock")');
+    expect(parseQuery('parse -mock').terms).toEqual(['parse']);
+    expect(parseQuery('foo-bar').match).toBe('"foo-bar"');
+  });
+
+  test('should also match the words of compound identifiers', () => {
+    expect(parseQuery('getUser').match).toBe('("getUser" OR "get user")');
+    expect(parseQuery('get_user*').match).toBe('("get_user"* OR "get user"*)');
+    expect(parseQuery('user').match).toBe('"user"');
+  });
+
+  test('should extract filters wherever they appear', () => {
+    const parsed = parseQuery('type:function connect lang:rs path:src/db name:"open*"');
+
+    expect(parsed.match).toBe('"connect"');
+    expect(parsed.text).toBe('connect');
+    expect(parsed.filters).toEqual({ type: ['function'], lang: ['rust'], path: ['src/db'], name: ['open*'] });
+  });
+
+  test('should leave terms without searchable characters to the LIKE fallback', () => {
+    expect(parseQuery('->').match).toBeNull();
+    expect(parseQuery('->').terms).toEqual(['->']);
+    expect(parseQuery(':: parse').match).toBe('"parse"');
+    expect(parseQuery(':: OR parse').match).toBeNull();
+  });
+
+  test('should reject malformed queries', () => {
+    expect(() => parseQuery('(parse')).toThrow(QueryParseError);
+    expect(() => parseQuery('parse)')).toThrow(QueryParseError);
+    expect(() => parseQuery('"parse')).toThrow(QueryParseError);
+    expect(() => parseQuery('OR parse')).toThrow(QueryParseError);
+    expect(() => parseQuery('parse OR')).toThrow(QueryParseError);
+    expect(() => parseQuery('-parse')).toThrow(QueryParseError);
+    expect(() => parseQuery('type:')).toThrow(QueryParseError);
+  });
+});
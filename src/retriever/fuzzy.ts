@@ -0,0 +1,121 @@
+// Names sharing the most trigrams with the pattern are scored exactly
+const MAX_CANDIDATES = 500;
+
+export interface FuzzyMatch {
+  name: string;
+  similarity: number;  // 0-1; 1 when the name equals the pattern, ignoring case
+  distance: number;    // Edits between the pattern and the name
+}
+
+// Case and the `::` / `.` separator don't count as differences
+function normalize(name: string): string {
+  return name.toLowerCase().replace(/::/g, '.');
+}
+
+function trigrams(text: string): string[] {
+  const padded = `  ${text} `;
+  const grams = new Set<string>();
+  for (let i = 0; i + 3 <= padded.length; i++) {
+    grams.add(padded.substring(i, i + 3));
+  }
+  return [...grams];
+}
+
+/**
+ * Insertions, deletions, substitutions and swaps of adjacent characters
+ * needed to turn `a` into `b` (optimal string alignment distance)
+ */
+export function editDistance(a: string, b: string): number {
+  let previous2: number[] = [];
+  let previous = Array.from({ length: b.length + 1 }, (_, j) => j);
+
+  for (let i = 1; i <= a.length; i++) {
+    const current = [i];
+    for (let j = 1; j <= b.length; j++) {
+      const cost = a[i - 1] === b[j - 1] ? 0 : 1;
+      current[j] = Math.min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + cost);
+      if (i > 1 && j > 1 && a[i - 1] === b[j - 2] && a[i - 2] === b[j - 1]) {
+        current[j] = Math.min(current[j], previous2[j - 2] + 1);
+      }
+    }
+    previous2 = previous;
+    previous = current;
+  }
+
+  return previous[b.length];
+}
+
+/**
+ * How well `name` matches `pattern`: full edit similarity, with credit for
+ * names that start with or contain the pattern, or start with a misspelling
+ * of it (`getUsr` for `getUserById`)
+ */
+export function similarity(pattern: string, name: string): Omit<FuzzyMatch, 'name'> {
+  const p = normalize(pattern);
+  const n = normalize(name);
+  const distance = editDistance(p, n);
+  if (distance === 0) {
+    return { similarity: 1, distance };
+  }
+
+  let score = 1 - distance / Math.max(p.length, n.length);
+  if (n.startsWith(p)) {
+    score = Math.max(score, 0.8 + 0.15 * p.length / n.length);
+  } else if (n.includes(p)) {
+    score = Math.max(score, 0.7 + 0.15 * p.length / n.length);
+  } else if (p.length >= 4 && n.length > p.length) {
+    const prefixDistance = Math.min(
+      ...[p.length - 1, p.length, p.length + 1].map(length => editDistance(p, n.substring(0, length)))
+    );
+    score = Math.max(score, 0.75 * (1 - prefixDistance / p.length));
+  }
+
+  return { similarity: score, distance };
+}
+
+/**
+ * Typo-tolerant lookup over a set of names. A trigram index picks the
+ * candidates, which are then ranked by `similarity`.
+ */
+export class FuzzyIndex {
+  private names: string[];
+  private postings: Map<string, number[]>;
+
+  constructor(names: Iterable<string>) {
+    this.names = [...new Set(names)];
+    this.postings = new Map();
+
+    this.names.forEach((name, index) => {
+      for (const gram of trigrams(normalize(name))) {
+        const list = this.postings.get(gram) || [];
+        list.push(index);
+        this.postings.set(gram, list);
+      }
+    });
+  }
+
+  /**
+   * Names at least `minSimilarity` similar to `pattern`, best first
+   */
+  public search(pattern: string, limit: number = 20, minSimilarity: number = 0.5): FuzzyMatch[] {
+    const shared = new Map<number, number>();
+    for (const gram of trigrams(normalize(pattern))) {
+      for (const index of this.postings.get(gram) || []) {
+        shared.set(index, (shared.get(index) || 0) + 1);
+      }
+    }
+
+    return [...shared]
+      .sort((a, b) => b[1] - a[1])
+      .slice(0, MAX_CANDIDATES)
+      .map(([index]) => ({ name: this.names[index], ...similarity(pattern, this.names[index]) }))
+      .filter(match => match.similarity >= minSimilarity)
+      .sort((a, b) =>
+        b.similarity - a.similarity ||
+        Number(b.name === pattern) - Number(a.name === pattern) ||
+        a.name.length - b.name.length ||
+        a.name.localeCompare(b.name)
+      )
+      .slice(0, limit);
+  }
+}
@@ -1,24 +1,54 @@
 import { PrimordynDB } from '../database/index.js';
+import { sep } from 'path';
 import { encodingForModel, Tiktoken } from 'js-tiktoken';
 import { GitAnalyzer } from '../git/analyzer.js';
+import { TYPE_SYMBOLS } from '../indexer/linker.js';
+import { Ranker } from './ranking.js';
+import type { SemanticSimilarity } from './ranking.js';
+import { parseQuery } from './query-parser.js';
+import { FuzzyIndex } from './fuzzy.js';
+import { ContextPacker, elideMiddle, skeleton } from './packer.js';
+import type { PackCandidate } from './packer.js';
+import type { FuzzyMatch } from './fuzzy.js';
+import type { ParsedQuery } from './query-parser.js';
+import { EmbeddingIndex } from '../embeddings/index.js';
 import type { 
   QueryOptions, QueryResult, FileResult, SymbolResult, 
   DependencyGraph, CallGraphNode, CallGraphEdge, ImpactAnalysis, GitHistory, 
-  FileQueryRow, SymbolQueryRow, RecentFileChanges, FileReferenceRow,
-  MetadataResult, SymbolWithFileContent, CallGraphResult,
-  CallerResult, SymbolLookupResult, FilePathResult
+  FileQueryRow, SymbolQueryRow, RecentFileChanges, ReferenceRow,
+  SymbolWithFileContent, CallGraphResult,
+  CallerResult, SymbolLookupResult, FilePathResult, ModuleEdge,
+  TypeHierarchy, TypeHierarchyNode, TypeDefinition, TypeRelationRow,
+  FindOptions, FindResult
 } from '../types/index.js';
 
+// A prepared query and its named parameters
+interface SearchStatement {
+  sql: string;
+  params: Record<string, string>;
+}
+
+// Lines kept by the elided form of a file or symbol
+const ELIDED_LINES = 40;
+
+function escapeLike(value: string): string {
+  return value.replace(/[\\%_]/g, char => `\\${char}`);
+}
+
 export class ContextRetriever {
   private db: PrimordynDB;
   private tokenEncoder: Tiktoken;
   private gitAnalyzer: GitAnalyzer;
+  private ranker: Ranker;
+  private packer: ContextPacker;
 
   constructor(db: PrimordynDB) {
     this.db = db;
     // Use GPT-4 encoder as it's similar to Claude's tokenization
     this.tokenEncoder = encodingForModel('gpt-4');
     this.gitAnalyzer = new GitAnalyzer();
+    this.ranker = new Ranker(db, this.gitAnalyzer);
+    this.packer = new ContextPacker(value => this.estimateTokens(value));
   }
 
   public async query(searchTerm: string, options: QueryOptions = {}): Promise<QueryResult> {
@@ -32,66 +62,140 @@ export class ContextRetriever {
       truncated: false
     };
 
-    // Check if we can use FTS5 or need to fall back to LIKE
-    const escapedTerm = this.escapeFTS5(searchTerm);
-    const useFTS = escapedTerm.length > 0 && escapedTerm !== '';
-    
-    let files: FileQueryRow[];
-    let symbols: SymbolQueryRow[];
-    
-    if (useFTS) {
-      // Use FTS for better search
-      const fileQuery = this.buildFileQuery(escapedTerm, options);
-      files = database.prepare(fileQuery).all({ searchTerm: escapedTerm }) as FileQueryRow[];
-
-      // Search in symbols using FTS
-      const symbolQuery = this.buildSymbolQuery(escapedTerm, options);
-      symbols = database.prepare(symbolQuery).all({ searchTerm: escapedTerm }) as SymbolQueryRow[];
-    } else {
-      // Fall back to LIKE queries for special characters
-      const fileQuery = this.buildFileLikeQuery(searchTerm, options);
-      const params = [`%${searchTerm}%`];
-      if (options.fileTypes?.length) {
-        params.push(...options.fileTypes);
+    const parsed = parseQuery(searchTerm);
+    const searchText = parsed.text || searchTerm;
+    // type: and name: filters select symbols, so files are left out
+    const searchFiles = parsed.filters.type.length === 0 && parsed.filters.name.length === 0;
+    if (parsed.terms.length === 0 && Object.values(parsed.filters).every(values => values.length === 0)) {
+      return result;
+    }
+
+    // Terms made only of punctuation (`::`, `->`) can't be matched by FTS5
+    const fileStatement = parsed.match ? this.buildFileQuery(parsed, options) : this.buildFileLikeQuery(parsed, options);
+    const symbolStatement = parsed.match ? this.buildSymbolQuery(parsed, options) : this.buildSymbolLikeQuery(parsed, options);
+    const files = searchFiles
+      ? database.prepare(fileStatement.sql).all(fileStatement.params) as FileQueryRow[]
+      : [];
+    const symbols = database.prepare(symbolStatement.sql).all(symbolStatement.params) as SymbolQueryRow[];
+
+    // Semantic search adds the closest symbols and file chunks, whether or
+    // not they contain the words searched for
+    const similarity = options.semantic
+      ? await this.semanticCandidates(searchText, parsed, options, files, symbols, searchFiles)
+      : undefined;
+
+    // Pack the ranked results into the token budget, shortening or
+    // dropping the ones that don't fit whole
+    const contents = new Map<string, string | null>();
+    const candidates: PackCandidate<FileResult | SymbolResult>[] = [];
+    for (const ranked of this.ranker.rank(searchText, files, symbols, options.sortBy, similarity)) {
+      if (ranked.kind === 'file') {
+        const item = await this.processFileResult(ranked.row, options);
+        item.score = ranked.score;
+        candidates.push({
+          label: item.relativePath,
+          relevance: ranked.score.total,
+          variants: this.fileVariants(item, ranked.row.content)
+        });
+      } else {
+        const item = this.processSymbolResult(ranked.row);
+        item.score = ranked.score;
+        if (options.includeContent && !contents.has(item.filePath)) {
+          contents.set(item.filePath, this.fileContent(item.filePath));
+        }
+        candidates.push({
+          label: `${item.qualifiedName || item.name} (${item.filePath}:${item.lineStart})`,
+          relevance: ranked.score.total,
+          variants: this.symbolVariants(item, options.includeContent ? contents.get(item.filePath) ?? null : null)
+        });
       }
-      files = database.prepare(fileQuery).all(...params) as FileQueryRow[];
-      
-      const symbolQuery = this.buildSymbolLikeQuery(searchTerm, options);
-      const symbolParams = [`%${searchTerm}%`, `%${searchTerm}%`];
-      if (options.fileTypes?.length) {
-        symbolParams.push(...options.fileTypes);
+    }
+
+    const packed = this.packer.pack(candidates, maxTokens);
+    for (const { item } of packed.items) {
+      if ('relativePath' in item) {
+        result.files.push(item);
+      } else {
+        result.symbols.push(item);
       }
-      symbols = database.prepare(symbolQuery).all(...symbolParams) as SymbolQueryRow[];
     }
+    result.totalTokens = packed.report.used;
+    result.truncated = packed.report.dropped.length > 0 || packed.report.reduced.length > 0;
+    result.packing = packed.report;
 
-    // Process results with token limit
-    for (const file of files) {
-      const fileResult = await this.processFileResult(file, options);
-      const fileTokens = this.estimateTokens(fileResult);
+    return result;
+  }
 
-      if (result.totalTokens + fileTokens > maxTokens) {
-        result.truncated = true;
-        break;
+  /**
+   * Embedding similarity of the chunks closest to the search term. Symbols
+   * found this way, and files matched by chunks of their own text, are added
+   * to the candidates when full-text search missed them.
+   */
+  private async semanticCandidates(
+    searchText: string,
+    parsed: ParsedQuery,
+    options: QueryOptions,
+    files: FileQueryRow[],
+    symbols: SymbolQueryRow[],
+    searchFiles: boolean
+  ): Promise<SemanticSimilarity> {
+    const index = new EmbeddingIndex(this.db);
+    await index.update();
+    const matches = await index.search(searchText, 30);
+
+    const similarity: SemanticSimilarity = { files: new Map(), symbols: new Map() };
+    for (const match of matches) {
+      similarity.files.set(match.fileId, Math.max(similarity.files.get(match.fileId) ?? -1, match.similarity));
+      if (match.symbolId !== null) {
+        similarity.symbols.set(match.symbolId, match.similarity);
       }
-
-      result.files.push(fileResult);
-      result.totalTokens += fileTokens;
     }
 
-    for (const symbol of symbols) {
-      const symbolResult = this.processSymbolResult(symbol);
-      const symbolTokens = this.estimateTokens(symbolResult);
+    const database = this.db.getDatabase();
 
-      if (result.totalTokens + symbolTokens > maxTokens) {
-        result.truncated = true;
-        break;
-      }
+    const knownSymbols = new Set(symbols.map(symbol => symbol.id));
+    const missingSymbols = [...similarity.symbols.keys()].filter(id => !knownSymbols.has(id));
+    if (missingSymbols.length > 0) {
+      const params: Record<string, string> = {};
+      symbols.push(...database.prepare(`
+        SELECT 
+          s.id,
+          s.name,
+          s.qualified_name,
+          s.type,
+          s.line_start as lineStart,
+          s.line_end as lineEnd,
+          s.signature,
+          f.relative_path as filePath,
+          f.size as fileSize,
+          f.last_modified as fileModified
+        FROM symbols s
+        JOIN files f ON s.file_id = f.id
+        WHERE s.id IN (${missingSymbols.join(',')})${this.buildFilterClause(parsed, options, params, true)}
+      `).all(params) as SymbolQueryRow[]);
+    }
 
-      result.symbols.push(symbolResult);
-      result.totalTokens += symbolTokens;
+    const knownFiles = new Set(files.map(file => file.id));
+    const missingFiles = [...new Set(matches.filter(match => match.symbolId === null).map(match => match.fileId))]
+      .filter(id => !knownFiles.has(id));
+    if (searchFiles && missingFiles.length > 0) {
+      const params: Record<string, string> = {};
+      files.push(...database.prepare(`
+        SELECT 
+          f.id, 
+          f.path, 
+          f.relative_path as relativePath, 
+          f.content, 
+          f.language, 
+          f.metadata,
+          f.size,
+          f.last_modified as lastModified
+        FROM files f
+        WHERE f.id IN (${missingFiles.join(',')})${this.buildFilterClause(parsed, options, params, false)}
+      `).all(params) as FileQueryRow[]);
     }
 
-    return result;
+    return similarity;
   }
 
   public async getFileContext(filePath: string, options: QueryOptions = {}): Promise<FileResult | null> {
@@ -114,199 +218,268 @@ export class ContextRetriever {
     const maxTokens = options.maxTokens || 4000;
     const database = this.db.getDatabase();
 
-    // Get imports from the target file
-    const fileData = database.prepare(`
-      SELECT metadata FROM files WHERE path = ? OR relative_path = ?
-    `).get(filePath, filePath) as MetadataResult | undefined;
-
-    if (!fileData || !fileData.metadata) {
-      return [];
-    }
-
-    const metadata = JSON.parse(fileData.metadata);
-    const imports = metadata.imports || [];
-    const results: FileResult[] = [];
-    let totalTokens = 0;
-
-    // Find files that are imported
-    for (const importPath of imports) {
-      const relatedFiles = database.prepare(`
-        SELECT id, path, relative_path as relativePath, content, language, metadata, hash, size, last_modified, indexed_at
-        FROM files
-        WHERE relative_path LIKE ?
-        LIMIT 5
-      `).all(`%${importPath}%`) as FileQueryRow[];
-
-      for (const file of relatedFiles) {
-        const fileResult = await this.processFileResult(file, options);
-        const fileTokens = this.estimateTokens(fileResult);
-
-        if (totalTokens + fileTokens > maxTokens) {
-          break;
-        }
-
-        results.push(fileResult);
-        totalTokens += fileTokens;
-      }
+    // Files the target imports, via resolved import edges
+    const relatedFiles = database.prepare(`
+      SELECT f.id, f.path, f.relative_path as relativePath, f.content, f.language, f.metadata,
+             f.hash, f.size, f.last_modified, f.indexed_at
+      FROM imports i
+      JOIN files src ON i.file_id = src.id
+      JOIN files f ON i.target_file_id = f.id
+      WHERE (src.path = ? OR src.relative_path = ?) AND f.id != src.id
+      GROUP BY f.id
+      ORDER BY MIN(i.id)
+    `).all(filePath, filePath) as FileQueryRow[];
+
+    // Earlier imports count for more
+    const candidates: PackCandidate<FileResult>[] = [];
+    for (const [index, file] of relatedFiles.entries()) {
+      const fileResult = await this.processFileResult(file, options);
+      candidates.push({
+        label: fileResult.relativePath,
+        relevance: 1 / (index + 1),
+        variants: this.fileVariants(fileResult, file.content)
+      });
     }
 
-    return results;
+    return this.packer.pack(candidates, maxTokens).items.map(packed => packed.item);
   }
 
   public async findUsages(symbolName: string, options: QueryOptions = {}): Promise<FileResult[]> {
     const database = this.db.getDatabase();
     const maxTokens = options.maxTokens || 4000;
-    
-    // Search for files that contain references to this symbol
-    // This includes imports, function calls, class instantiations, etc.
-    const query = `
-      SELECT DISTINCT
-        f.id,
-        f.path,
-        f.relative_path as relativePath,
-        f.content,
-        f.language,
-        f.metadata,
-        f.size,
+
+    // Files defining the symbol are its definition, not its usages
+    const definingFiles = new Set((database.prepare(
+      'SELECT DISTINCT file_id as fileId FROM symbols WHERE name = ?'
+    ).all(symbolName) as { fileId: number }[]).map(row => row.fileId));
+
+    const byFile = new Map<number, ReferenceRow[]>();
+    for (const reference of this.findReferences(symbolName, options)) {
+      if (definingFiles.has(reference.fileId)) continue;
+      const list = byFile.get(reference.fileId) || [];
+      list.push(reference);
+      byFile.set(reference.fileId, list);
+    }
+
+    // Implementation files first, then tests
+    const fileIds = [...byFile.keys()]
+      .sort((a, b) => {
+        const pathA = byFile.get(a)![0].filePath;
+        const pathB = byFile.get(b)![0].filePath;
+        return Number(this.isTestFile(pathA)) - Number(this.isTestFile(pathB)) || pathA.localeCompare(pathB);
+      })
+      .slice(0, 20);
+
+    const fileQuery = database.prepare(`
+      SELECT f.id, f.path, f.relative_path as relativePath, f.content, f.language, f.metadata, f.size,
         f.last_modified as lastModified
       FROM files f
-      WHERE 
-        -- Check in file content (excluding the definition file)
-        f.content LIKE '%' || ? || '%'
-        AND f.id NOT IN (
-          SELECT DISTINCT file_id 
-          FROM symbols 
-          WHERE name = ?
-        )
-      ${options.fileTypes?.length ? `AND f.language IN (${options.fileTypes.map(() => '?').join(',')})` : ''}
-      ORDER BY 
-        CASE 
-          WHEN f.relative_path LIKE '%test%' THEN 1
-          WHEN f.relative_path LIKE '%spec%' THEN 1
-          ELSE 0
-        END,
-        f.relative_path
-      LIMIT 20
-    `;
-    
-    const params = [symbolName, symbolName];
-    if (options.fileTypes?.length) {
-      params.push(...options.fileTypes);
-    }
-    
-    const files = database.prepare(query).all(...params) as FileQueryRow[];
-    const results: FileResult[] = [];
-    let totalTokens = 0;
-    
-    for (const file of files) {
-      // Check if the file actually uses the symbol (not just mentions in comments)
-      const lines = file.content.split('\n');
-      const usageLines: number[] = [];
-      
-      lines.forEach((line: string, index: number) => {
-        // Look for actual usage patterns
-        if (
-          line.includes(`new ${symbolName}`) ||  // Class instantiation
-          line.includes(`${symbolName}(`) ||      // Function call
-          line.includes(`${symbolName}.`) ||      // Static method/property
-          line.includes(`<${symbolName}`) ||      // JSX/Type usage
-          line.includes(`: ${symbolName}`) ||     // Type annotation
-          line.includes(`extends ${symbolName}`) || // Inheritance
-          line.includes(`implements ${symbolName}`) || // Interface implementation
-          line.includes(`from '.*${symbolName}`) || // Import
-          line.includes(`import.*${symbolName}`)    // Import
-        ) {
-          usageLines.push(index + 1);
-        }
+      WHERE f.id = ?
+    `);
+    // Files with more references count for more; tests for less
+    const candidates: PackCandidate<FileResult>[] = [];
+
+    for (const fileId of fileIds) {
+      const file = fileQuery.get(fileId) as FileQueryRow | undefined;
+      if (!file) continue;
+
+      const references = byFile.get(fileId)!;
+      const fileResult = await this.processFileResult(file, options);
+      fileResult.metadata = {
+        usageLines: [...new Set(references.map(reference => reference.line))],
+        references: references.map(reference => ({
+          name: reference.name,
+          kind: reference.kind,
+          line: reference.line,
+          column: reference.column,
+          symbol: reference.symbolName
+        }))
+      };
+
+      candidates.push({
+        label: fileResult.relativePath,
+        relevance: Math.log1p(references.length) * (this.isTestFile(fileResult.relativePath) ? 0.5 : 1),
+        variants: this.fileVariants(fileResult, file.content)
       });
-      
-      if (usageLines.length > 0) {
-        const fileResult = await this.processFileResult(file, options);
-        fileResult.metadata = { usageLines };
-        
-        const fileTokens = this.estimateTokens(fileResult);
-        if (totalTokens + fileTokens > maxTokens) {
-          break;
-        }
-        
-        results.push(fileResult);
-        totalTokens += fileTokens;
-      }
     }
-    
-    return results;
+
+    return this.packer.pack(candidates, maxTokens).items.map(packed => packed.item);
+  }
+
+  /**
+   * References to a name from the reference index - calls, type names,
+   * reads, writes, imports and exports - matched on the last path segment,
+   * so `save` matches `repo.save` and `Repo::save`.
+   */
+  private findReferences(symbolName: string, options: QueryOptions = {}): ReferenceRow[] {
+    const shortName = symbolName.split(/::|\./).pop() || symbolName;
+    const params: Record<string, string> = { shortName };
+    const fileTypes = (options.fileTypes || []).map((fileType, i) => {
+      params[`fileType${i}`] = fileType;
+      return `:fileType${i}`;
+    });
+
+    return this.db.getDatabase().prepare(`
+      SELECT
+        r.file_id as fileId,
+        f.relative_path as filePath,
+        r.name,
+        r.kind,
+        r.line,
+        r.column_number as column,
+        s.name as symbolName
+      FROM symbol_references r
+      JOIN files f ON r.file_id = f.id
+      LEFT JOIN symbols s ON r.symbol_id = s.id
+      WHERE r.short_name = :shortName
+      ${fileTypes.length ? `AND f.language IN (${fileTypes.join(',')})` : ''}
+      ${this.buildCrateFilter(options, params)}
+      ORDER BY f.relative_path, r.line, r.column_number
+    `).all(params) as ReferenceRow[];
   }
   
   public async findSymbol(symbolName: string, options: QueryOptions = {}): Promise<SymbolResult[]> {
     const database = this.db.getDatabase();
+    const parsed = parseQuery(symbolName);
+    if (parsed.terms.length === 0 && Object.values(parsed.filters).every(values => values.length === 0)) {
+      return [];
+    }
 
-    // Check if we can use FTS5 or need to fall back to LIKE
-    const escapedName = this.escapeFTS5(symbolName);
-    const useFTS = escapedName.length > 0 && escapedName !== '';
-    
-    let symbols: SymbolQueryRow[];
-    
-    if (useFTS) {
-      // Use FTS for symbol search
-      const query = `
-        SELECT 
-          s.id,
-          s.name,
-          s.type,
-          s.line_start as lineStart,
-          s.line_end as lineEnd,
-          s.signature,
-          f.relative_path as filePath,
-          f.content as fileContent
-        FROM symbols_fts fts
-        JOIN symbols s ON fts.rowid = s.id
-        JOIN files f ON s.file_id = f.id
-        WHERE symbols_fts MATCH ?
-        ${options.fileTypes?.length ? `AND f.language IN (${options.fileTypes.map(() => '?').join(',')})` : ''}
-        ORDER BY 
-          bm25(symbols_fts),
-          CASE WHEN s.name = ? THEN 0 ELSE 1 END,
-          LENGTH(s.name)
-        LIMIT 20
-      `;
-
-      const params = [escapedName, escapedName];
-      if (options.fileTypes?.length) {
-        params.push(...options.fileTypes);
-      }
+    // Qualified names (`pkg.mod.func`, `Class.method`) match exactly or as a dotted suffix
+    const [name] = parsed.terms;
+    if (parsed.terms.length === 1 && /[.:]/.test(name)) {
+      const params: Record<string, string> = {
+        qualified: name,
+        suffix: `%.${escapeLike(name)}`
+      };
+      const filters = this.buildFilterClause(parsed, options, params, true);
 
-      symbols = database.prepare(query).all(...params) as SymbolQueryRow[];
-    } else {
-      // Fall back to LIKE query for special characters
-      const query = `
+      const qualified = database.prepare(`
         SELECT 
           s.id,
           s.name,
+          s.qualified_name,
           s.type,
           s.line_start as lineStart,
           s.line_end as lineEnd,
           s.signature,
-          f.relative_path as filePath,
-          f.content as fileContent
+          f.relative_path as filePath
         FROM symbols s
         JOIN files f ON s.file_id = f.id
-        WHERE s.name LIKE ?
-        ${options.fileTypes?.length ? `AND f.language IN (${options.fileTypes.map(() => '?').join(',')})` : ''}
-        ORDER BY 
-          CASE WHEN s.name = ? THEN 0 ELSE 1 END,
-          LENGTH(s.name)
+        WHERE (s.qualified_name = :qualified OR s.qualified_name LIKE :suffix ESCAPE '\\' OR s.name = :qualified)
+        ${filters}
+        ORDER BY CASE WHEN s.qualified_name = :qualified THEN 0 ELSE 1 END, LENGTH(COALESCE(s.qualified_name, s.name))
         LIMIT 20
-      `;
+      `).all(params) as SymbolQueryRow[];
 
-      const params = [`%${symbolName}%`, symbolName];
-      if (options.fileTypes?.length) {
-        params.push(...options.fileTypes);
+      if (qualified.length > 0) {
+        return qualified.map(symbol => this.processSymbolResult(symbol));
       }
+    }
+
+    const statement = parsed.match ? this.buildSymbolQuery(parsed, options) : this.buildSymbolLikeQuery(parsed, options);
+    const symbols = database.prepare(statement.sql).all(statement.params) as SymbolQueryRow[];
+
+    return this.ranker.rank(parsed.text || symbolName, [], symbols).slice(0, 20).map(ranked => ({
+      ...this.processSymbolResult(ranked.row as SymbolQueryRow),
+      score: ranked.score
+    }));
+  }
 
-      symbols = database.prepare(query).all(...params) as SymbolQueryRow[];
+  /**
+   * Typo-tolerant symbol search over names and qualified names, best match first
+   */
+  public findSymbolsFuzzy(pattern: string, options: FindOptions = {}): FindResult[] {
+    const database = this.db.getDatabase();
+    const limit = options.limit || 20;
+
+    const params: Record<string, string> = {};
+    const filters = this.buildFilterClause(
+      { match: null, terms: [], text: '', filters: { type: options.types || [], lang: [], path: [], name: [] } },
+      options,
+      params,
+      true
+    );
+    const names = (database.prepare(`
+      SELECT s.name, s.qualified_name
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      WHERE s.type NOT IN ('import', 'export')${filters}
+    `).all(params) as Array<{ name: string; qualified_name: string | null }>)
+      .flatMap(row => row.qualified_name ? [row.name, row.qualified_name] : [row.name]);
+
+    const matches = new Map(new FuzzyIndex(names).search(pattern, limit).map(match => [match.name, match]));
+    if (matches.size === 0) {
+      return [];
     }
 
-    return symbols.map(symbol => this.processSymbolResult(symbol));
+    const matchedNames = [...matches.keys()];
+    matchedNames.forEach((name, i) => {
+      params[`match${i}`] = name;
+    });
+    const placeholders = matchedNames.map((_, i) => `:match${i}`).join(',');
+    const rows = database.prepare(`
+      SELECT 
+        s.id,
+        s.name,
+        s.qualified_name,
+        s.type,
+        s.line_start as lineStart,
+        s.line_end as lineEnd,
+        s.signature,
+        f.relative_path as filePath,
+        ${options.includeContent ? 'f.content as fileContent' : 'NULL as fileContent'}
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      WHERE (s.name IN (${placeholders}) OR s.qualified_name IN (${placeholders}))
+        AND s.type NOT IN ('import', 'export')${filters}
+    `).all(params) as Array<SymbolQueryRow & { fileContent: string | null }>;
+
+    return rows
+      .map(row => {
+        // The better of the name and the qualified name
+        const match = [matches.get(row.name), row.qualified_name ? matches.get(row.qualified_name) : undefined]
+          .filter((candidate): candidate is FuzzyMatch => candidate !== undefined)
+          .sort((a, b) => b.similarity - a.similarity)[0];
+        const result: FindResult = {
+          ...this.processSymbolResult(row),
+          matchedName: match.name,
+          similarity: match.similarity,
+          distance: match.distance
+        };
+        if (row.fileContent !== null) {
+          result.content = row.fileContent.split('\n').slice(result.lineStart - 1, result.lineEnd).join('\n');
+        }
+        return result;
+      })
+      .sort((a, b) =>
+        b.similarity - a.similarity ||
+        Number(this.isTestFile(a.filePath)) - Number(this.isTestFile(b.filePath)) ||
+        a.filePath.localeCompare(b.filePath) ||
+        a.lineStart - b.lineStart
+      )
+      .slice(0, limit);
+  }
+
+  /**
+   * Symbol names a few edits away from the words of a query that found
+   * nothing, for "did you mean" hints
+   */
+  public suggestNames(searchTerm: string, options: FindOptions = {}, limit: number = 5): string[] {
+    let terms: string[];
+    try {
+      terms = parseQuery(searchTerm).terms;
+    } catch {
+      terms = [searchTerm];
+    }
+
+    const suggestions = terms
+      .filter(term => term.length >= 3)
+      .flatMap(term => this.findSymbolsFuzzy(term, { ...options, limit })
+        .filter(result => result.distance > 0 && result.distance <= Math.max(1, Math.floor(term.length / 4))))
+      .sort((a, b) => a.distance - b.distance || b.similarity - a.similarity);
+
+    return [...new Set(suggestions.map(result => result.matchedName))].slice(0, limit);
   }
 
   public async searchFullText(query: string, options: QueryOptions = {}): Promise<QueryResult> {
@@ -320,15 +493,17 @@ export class ContextRetriever {
       truncated: false
     };
 
-    // Check if we can use FTS5 or need to fall back to LIKE
-    const escapedQuery = this.escapeFTS5(query);
-    const useFTS = escapedQuery.length > 0 && escapedQuery !== '';
-    
+    const parsed = parseQuery(query);
+    if (parsed.terms.length === 0) {
+      return result;
+    }
+
     let files: FileQueryRow[];
     let symbols: SymbolQueryRow[];
     
-    if (useFTS) {
+    if (parsed.match) {
       // Search files using FTS
+      const fileParams: Record<string, string> = { match: parsed.match };
       const fileQuery = `
         SELECT 
           f.id, 
@@ -342,15 +517,16 @@ export class ContextRetriever {
           snippet(files_fts, 1, '<mark>', '</mark>', '...', 32) as snippet
         FROM files_fts fts
         JOIN files f ON fts.rowid = f.id
-        WHERE files_fts MATCH ?
-        ${options.fileTypes?.length ? `AND f.language IN (${options.fileTypes.map(t => `'${t}'`).join(',')})` : ''}
+        WHERE files_fts MATCH :match
+        ${this.buildFilterClause(parsed, options, fileParams, false)}
         ORDER BY bm25(files_fts)
         LIMIT 10
       `;
 
-      files = database.prepare(fileQuery).all(escapedQuery) as FileQueryRow[];
+      files = database.prepare(fileQuery).all(fileParams) as FileQueryRow[];
 
       // Search symbols using FTS
+      const symbolParams: Record<string, string> = { match: parsed.match };
       const symbolQuery = `
         SELECT 
           s.id,
@@ -365,54 +541,20 @@ export class ContextRetriever {
         FROM symbols_fts fts
         JOIN symbols s ON fts.rowid = s.id
         JOIN files f ON s.file_id = f.id
-        WHERE symbols_fts MATCH ?
-        ${options.fileTypes?.length ? `AND f.language IN (${options.fileTypes.map(t => `'${t}'`).join(',')})` : ''}
+        WHERE symbols_fts MATCH :match
+        ${this.buildFilterClause(parsed, options, symbolParams, true)}
         ORDER BY bm25(symbols_fts)
         LIMIT 20
       `;
 
-      symbols = database.prepare(symbolQuery).all(escapedQuery) as SymbolQueryRow[];
+      symbols = database.prepare(symbolQuery).all(symbolParams) as SymbolQueryRow[];
     } else {
-      // Fall back to LIKE queries for special characters
-      const fileQuery = `
-        SELECT 
-          f.id, 
-          f.path, 
-          f.relative_path as relativePath, 
-          f.content, 
-          f.language, 
-          f.metadata,
-          f.size,
-          f.last_modified as lastModified
-        FROM files f
-        WHERE f.content LIKE ?
-        ${options.fileTypes?.length ? `AND f.language IN (${options.fileTypes.map(t => `'${t}'`).join(',')})` : ''}
-        ORDER BY f.relative_path
-        LIMIT 10
-      `;
+      // Fall back to LIKE queries for terms FTS5 can't match
+      const fileStatement = this.buildFileLikeQuery(parsed, options);
+      files = (database.prepare(fileStatement.sql).all(fileStatement.params) as FileQueryRow[]).slice(0, 10);
 
-      files = database.prepare(fileQuery).all(`%${query}%`) as FileQueryRow[];
-
-      // Search symbols using LIKE
-      const symbolQuery = `
-        SELECT 
-          s.id,
-          s.name,
-          s.type,
-          s.line_start as lineStart,
-          s.line_end as lineEnd,
-          s.signature,
-          f.relative_path as filePath,
-          f.content as fileContent
-        FROM symbols s
-        JOIN files f ON s.file_id = f.id
-        WHERE (s.name LIKE ? OR s.signature LIKE ?)
-        ${options.fileTypes?.length ? `AND f.language IN (${options.fileTypes.map(t => `'${t}'`).join(',')})` : ''}
-        ORDER BY LENGTH(s.name)
-        LIMIT 20
-      `;
-
-      symbols = database.prepare(symbolQuery).all(`%${query}%`, `%${query}%`) as SymbolQueryRow[];
+      const symbolStatement = this.buildSymbolLikeQuery(parsed, options);
+      symbols = (database.prepare(symbolStatement.sql).all(symbolStatement.params) as SymbolQueryRow[]).slice(0, 20);
     }
 
     // Process results
@@ -445,8 +587,10 @@ export class ContextRetriever {
     return result;
   }
 
-  private buildFileQuery(_searchTerm: string, options: QueryOptions): string {
-    let query = `
+  private buildFileQuery(parsed: ParsedQuery, options: QueryOptions): SearchStatement {
+    const params: Record<string, string> = { match: parsed.match || '' };
+    // The best full-text matches are candidates; the ranker orders them
+    const sql = `
       SELECT 
         f.id, 
         f.path, 
@@ -455,57 +599,40 @@ export class ContextRetriever {
         f.language, 
         f.metadata,
         f.size,
-        f.last_modified as lastModified
+        f.last_modified as lastModified,
+        bm25(files_fts) as rank
       FROM files_fts fts
       JOIN files f ON fts.rowid = f.id
-      WHERE files_fts MATCH :searchTerm
+      WHERE files_fts MATCH :match
+      ${this.buildFilterClause(parsed, options, params, false)}
+      ORDER BY rank LIMIT 25
     `;
-
-    if (options.fileTypes?.length) {
-      query += ` AND f.language IN (${options.fileTypes.map(t => `'${t}'`).join(',')})`;
-    }
-
-    switch (options.sortBy) {
-      case 'path':
-        query += ' ORDER BY f.relative_path';
-        break;
-      case 'size':
-        query += ' ORDER BY f.size DESC';
-        break;
-      case 'modified':
-        query += ' ORDER BY f.last_modified DESC';
-        break;
-      default:
-        query += ' ORDER BY bm25(files_fts)';
-    }
-
-    query += ' LIMIT 10';
-    return query;
+    return { sql, params };
   }
 
-  private buildSymbolQuery(_searchTerm: string, options: QueryOptions): string {
-    let query = `
+  private buildSymbolQuery(parsed: ParsedQuery, options: QueryOptions): SearchStatement {
+    const params: Record<string, string> = { match: parsed.match || '' };
+    const sql = `
       SELECT 
         s.id,
         s.name,
+        s.qualified_name,
         s.type,
         s.line_start as lineStart,
         s.line_end as lineEnd,
         s.signature,
         f.relative_path as filePath,
-        f.content as fileContent
+        f.size as fileSize,
+        f.last_modified as fileModified,
+        bm25(symbols_fts) as rank
       FROM symbols_fts fts
       JOIN symbols s ON fts.rowid = s.id
       JOIN files f ON s.file_id = f.id
-      WHERE symbols_fts MATCH :searchTerm
+      WHERE symbols_fts MATCH :match
+      ${this.buildFilterClause(parsed, options, params, true)}
+      ORDER BY rank LIMIT 30
     `;
-
-    if (options.fileTypes?.length) {
-      query += ` AND f.language IN (${options.fileTypes.map(t => `'${t}'`).join(',')})`;
-    }
-
-    query += ' ORDER BY bm25(symbols_fts) LIMIT 15';
-    return query;
+    return { sql, params };
   }
 
   private async processFileResult(file: FileQueryRow, options: QueryOptions): Promise<FileResult> {
@@ -527,8 +654,13 @@ export class ContextRetriever {
     }
 
     if (options.includeImports) {
-      result.imports = metadata.imports || [];
-      result.exports = metadata.exports || [];
+      const database = this.db.getDatabase();
+      result.imports = (database.prepare(`
+        SELECT specifier FROM imports WHERE file_id = ? ORDER BY id
+      `).all(file.id) as Array<{ specifier: string }>).map(row => row.specifier);
+      result.exports = (database.prepare(`
+        SELECT name FROM exports WHERE file_id = ? ORDER BY id
+      `).all(file.id) as Array<{ name: string }>).map(row => row.name);
     }
 
     if (options.includeSymbols) {
@@ -554,10 +686,74 @@ export class ContextRetriever {
     return result;
   }
 
+  /**
+   * Forms a file can be packed in: as processed, with its middle elided, as
+   * the first lines of its symbols, or as its path, imports and exports
+   */
+  private fileVariants(result: FileResult, content: string): PackCandidate<FileResult>['variants'] {
+    const variants: PackCandidate<FileResult>['variants'] = [{ form: 'full', item: { ...result, form: 'full' } }];
+
+    if (result.content !== undefined) {
+      const elided = elideMiddle(content, ELIDED_LINES);
+      if (elided) {
+        variants.push({ form: 'elided', item: { ...result, content: elided, form: 'elided' } });
+      }
+
+      const lineStarts = (this.db.getDatabase().prepare('SELECT line_start FROM symbols WHERE file_id = ?')
+        .all(result.id) as Array<{ line_start: number }>).map(row => row.line_start);
+      const outline = skeleton(content, lineStarts);
+      if (outline) {
+        variants.push({ form: 'skeleton', item: { ...result, content: outline, symbols: undefined, form: 'skeleton' } });
+      }
+    }
+
+    variants.push({
+      form: 'signature',
+      item: { ...result, content: undefined, preview: undefined, symbols: undefined, form: 'signature' }
+    });
+    return variants;
+  }
+
+  /**
+   * Forms a symbol can be packed in: its body, the body with its middle
+   * elided, its first line and its members' first lines, or its signature.
+   * Without file content there is only the signature.
+   */
+  private symbolVariants(result: SymbolResult, fileContent: string | null): PackCandidate<SymbolResult>['variants'] {
+    if (fileContent === null) {
+      return [{ form: 'full', item: { ...result, form: 'full' } }];
+    }
+
+    const body = fileContent.split('\n').slice(result.lineStart - 1, result.lineEnd).join('\n');
+    const variants: PackCandidate<SymbolResult>['variants'] = [{ form: 'full', item: { ...result, content: body, form: 'full' } }];
+
+    const elided = elideMiddle(body, ELIDED_LINES);
+    if (elided) {
+      variants.push({ form: 'elided', item: { ...result, content: elided, form: 'elided' } });
+    }
+
+    if (result.members?.length) {
+      const outline = skeleton(body, [1, ...result.members.map(member => member.lineStart - result.lineStart + 1)]);
+      if (outline) {
+        variants.push({ form: 'skeleton', item: { ...result, content: outline, form: 'skeleton' } });
+      }
+    }
+
+    variants.push({ form: 'signature', item: { ...result, form: 'signature' } });
+    return variants;
+  }
+
+  private fileContent(relativePath: string): string | null {
+    const file = this.db.getDatabase().prepare('SELECT content FROM files WHERE relative_path = ?')
+      .get(relativePath) as { content: string } | undefined;
+    return file ? file.content : null;
+  }
+
   private processSymbolResult(symbol: SymbolQueryRow): SymbolResult {
     const result: SymbolResult = {
       id: symbol.id,
       name: symbol.name,
+      qualifiedName: symbol.qualified_name || undefined,
       type: symbol.type,
       filePath: symbol.filePath || '',
       lineStart: symbol.lineStart || symbol.line_start,
@@ -568,6 +764,33 @@ export class ContextRetriever {
     // Note: fileContent would need to be added to SymbolQueryRow if needed
     // For now, we don't extract content in processSymbolResult
 
+    const database = this.db.getDatabase();
+    const parent = database.prepare(`
+      SELECT COALESCE(p.qualified_name, p.name) as name
+      FROM symbols s
+      JOIN symbols p ON s.parent_symbol_id = p.id
+      WHERE s.id = ?
+    `).get(symbol.id) as { name: string } | undefined;
+    if (parent) {
+      result.parent = parent.name;
+    }
+
+    const members = database.prepare(`
+      SELECT id, name, qualified_name, type, line_start
+      FROM symbols
+      WHERE parent_symbol_id = ?
+      ORDER BY line_start
+    `).all(symbol.id) as Array<{ id: number; name: string; qualified_name: string | null; type: string; line_start: number }>;
+    if (members.length > 0) {
+      result.members = members.map(member => ({
+        id: member.id,
+        name: member.name,
+        qualifiedName: member.qualified_name || undefined,
+        type: member.type,
+        lineStart: member.line_start
+      }));
+    }
+
     return result;
   }
 
@@ -698,7 +921,8 @@ export class ContextRetriever {
     const result = {
       root,
       calls,
-      calledBy
+      calledBy,
+      importedBy: this.findImporters(symbol.fileId, symbol.name)
     };
     
     // Cache the result for 5 minutes
@@ -743,6 +967,104 @@ export class ContextRetriever {
     return graph;
   }
 
+  /**
+   * Supertypes, subtypes and implementors of a type, following linked type
+   * relations up to `depth` levels. Relations whose target could not be
+   * linked (external or ambiguous types) still match the root by name.
+   */
+  public async getTypeHierarchy(typeName: string, depth: number = 3): Promise<TypeHierarchy | null> {
+    const database = this.db.getDatabase();
+    const shortName = typeName.split(/::|\./).pop() || typeName;
+    const types = [...TYPE_SYMBOLS];
+
+    const definitions = (database.prepare(`
+      SELECT s.id as symbolId, s.name, s.qualified_name as qualifiedName, s.type,
+        f.relative_path as filePath, s.line_start as line
+      FROM symbols s
+      JOIN files f ON s.file_id = f.id
+      WHERE (s.name = ? OR s.qualified_name = ?) AND s.type IN (${types.map(() => '?').join(',')})
+      ORDER BY f.relative_path, s.line_start
+    `).all(typeName, typeName, ...types) as Array<Omit<TypeDefinition, 'qualifiedName'> & { qualifiedName: string | null }>)
+      .map(row => ({ ...row, qualifiedName: row.qualifiedName ?? undefined }));
+
+    const supertypesOf = database.prepare(`
+      SELECT tr.target_name as relatedName, tr.kind, tr.line as relationLine,
+        t.id as symbolId, t.name, t.type, t.line_start as line, f.relative_path as filePath
+      FROM type_relations tr
+      LEFT JOIN symbols t ON tr.target_symbol_id = t.id
+      LEFT JOIN files f ON t.file_id = f.id
+      WHERE tr.source_symbol_id = ?
+      ORDER BY tr.line
+    `);
+    const subtypesOf = database.prepare(`
+      SELECT tr.source_name as relatedName, tr.kind, tr.line as relationLine,
+        s.id as symbolId, s.name, s.type, s.line_start as line, f.relative_path as filePath
+      FROM type_relations tr
+      LEFT JOIN symbols s ON tr.source_symbol_id = s.id
+      JOIN files f ON tr.file_id = f.id
+      WHERE tr.target_symbol_id = ?
+      ORDER BY f.relative_path, tr.line
+    `);
+
+    const toNode = (row: TypeRelationRow): TypeHierarchyNode => ({
+      symbolId: row.symbolId ?? undefined,
+      name: row.name || row.relatedName,
+      type: row.type || (row.filePath ? 'unknown' : 'external'),
+      filePath: row.filePath ?? undefined,
+      line: (row.symbolId !== null ? row.line : row.filePath ? row.relationLine : null) ?? undefined,
+      kind: row.kind,
+      children: []
+    });
+
+    // Diamonds are shown in full; only cycles along one path are cut
+    const expand = (rows: TypeRelationRow[], next: typeof supertypesOf, level: number, ancestors: Set<number>): TypeHierarchyNode[] => {
+      const seen = new Set<string>();
+      const nodes: TypeHierarchyNode[] = [];
+      for (const row of rows) {
+        const key = `${row.symbolId ?? row.relatedName}:${row.kind}`;
+        if (seen.has(key)) continue;
+        seen.add(key);
+
+        const node = toNode(row);
+        if (row.symbolId !== null && level < depth && !ancestors.has(row.symbolId)) {
+          node.children = expand(
+            next.all(row.symbolId) as TypeRelationRow[],
+            next,
+            level + 1,
+            new Set([...ancestors, row.symbolId])
+          );
+        }
+        nodes.push(node);
+      }
+      return nodes;
+    };
+
+    const rootIds = new Set(definitions.map(definition => definition.symbolId));
+    const outgoing = definitions.flatMap(definition => supertypesOf.all(definition.symbolId) as TypeRelationRow[]);
+    const incoming = [
+      ...definitions.flatMap(definition => subtypesOf.all(definition.symbolId) as TypeRelationRow[]),
+      ...database.prepare(`
+        SELECT tr.source_name as relatedName, tr.kind, tr.line as relationLine,
+          s.id as symbolId, s.name, s.type, s.line_start as line, f.relative_path as filePath
+        FROM type_relations tr
+        LEFT JOIN symbols s ON tr.source_symbol_id = s.id
+        JOIN files f ON tr.file_id = f.id
+        WHERE tr.target_symbol_id IS NULL AND (tr.target_name = ? OR tr.target_short_name = ?)
+        ORDER BY f.relative_path, tr.line
+      `).all(typeName, shortName) as TypeRelationRow[]
+    ];
+
+    const supertypes = expand(outgoing, supertypesOf, 1, rootIds);
+    const subtypes = expand(incoming.filter(row => row.kind !== 'implements'), subtypesOf, 1, rootIds);
+    const implementors = expand(incoming.filter(row => row.kind === 'implements'), subtypesOf, 1, rootIds);
+
+    if (definitions.length === 0 && subtypes.length === 0 && implementors.length === 0) {
+      return null;
+    }
+
+    return { name: typeName, definitions, supertypes, subtypes, implementors };
+  }
+
   public async getImpactAnalysis(symbolName: string): Promise<ImpactAnalysis | null> {
     const database = this.db.getDatabase();
     
@@ -770,7 +1092,7 @@ export class ContextRetriever {
     
     if (!symbol) {
       // Try to find references even if symbol isn't in database
-      const references = this.findAllReferences(symbolName);
+      const references = this.findReferences(symbolName);
       if (references.length === 0) {
         return null;
       }
@@ -796,22 +1118,10 @@ export class ContextRetriever {
       ORDER BY f.relative_path, cg.line_number
     `).all(symbolName, symbol.symbolId) as CallerResult[];
     
-    // Get text-based references (catches things AST might miss)
-    const textReferences = database.prepare(`
-      SELECT 
-        f.id as fileId,
-        f.relative_path as filePath,
-        f.content,
-        f.language
-      FROM files f
-      WHERE f.content LIKE '%' || ? || '%'
-        AND f.id != ?
-    `).all(symbolName, symbol.fileId) as Array<{
-      fileId: number;
-      filePath: string;
-      content: string;
-      language: string | null;
-    }>;
+    // Every other reference - type names, reads, imports - from the reference index
+    const references = this.findReferences(symbolName).filter(ref =>
+      ref.fileId !== symbol.fileId || ref.line !== symbol.line
+    );
     
     // Analyze each file for actual references
     const affectedFiles = new Map<string, {
@@ -837,34 +1147,38 @@ export class ContextRetriever {
       file.lines.push(ref.callLine);
     });
     
-    // Process text references to find additional occurrences
-    textReferences.forEach(file => {
-      const lines = file.content.split('\n');
-      const matches: number[] = [];
-      
-      lines.forEach((line: string, index: number) => {
-        if (line.includes(symbolName)) {
-          matches.push(index + 1);
-        }
-      });
-      
-      if (matches.length > 0) {
-        const key = file.filePath || '';
-        if (!affectedFiles.has(key)) {
-          affectedFiles.set(key, {
-            path: file.filePath || '',
-            referenceCount: matches.length,
-            isTest: this.isTestFile(file.filePath || ''),
-            lines: matches
-          });
-        } else {
-          // Merge with existing data
-          const existing = affectedFiles.get(key)!;
-          existing.lines = [...new Set([...existing.lines, ...matches])].sort((a, b) => a - b);
-          existing.referenceCount = existing.lines.length;
-        }
+    // Files importing the symbol's module are affected even without a direct call
+    this.findImporters(symbol.fileId, symbol.name).forEach(edge => {
+      const existing = affectedFiles.get(edge.filePath);
+      if (!existing) {
+        affectedFiles.set(edge.filePath, {
+          path: edge.filePath,
+          referenceCount: 1,
+          isTest: this.isTestFile(edge.filePath),
+          lines: edge.line === null ? [] : [edge.line]
+        });
+      } else if (edge.line !== null && !existing.lines.includes(edge.line)) {
+        existing.lines.push(edge.line);
+        existing.referenceCount++;
       }
     });
+
+    // Merge in references the call graph doesn't cover
+    references.forEach(ref => {
+      const existing = affectedFiles.get(ref.filePath);
+      if (!existing) {
+        affectedFiles.set(ref.filePath, {
+          path: ref.filePath,
+          referenceCount: 1,
+          isTest: this.isTestFile(ref.filePath),
+          lines: [ref.line]
+        });
+      } else if (!existing.lines.includes(ref.line)) {
+        existing.lines.push(ref.line);
+        existing.referenceCount++;
+      }
+    });
+    affectedFiles.forEach(file => file.lines.sort((a, b) => a - b));
     
     // Calculate impact metrics
     const affectedFilesList = Array.from(affectedFiles.values());
@@ -875,7 +1189,10 @@ export class ContextRetriever {
     const totalReferences = affectedFilesList.reduce((sum, f) => sum + f.referenceCount, 0);
     
     // Get unique symbols that reference this one
-    const affectedSymbols = new Set(directReferences.map(r => r.callerName).filter(Boolean));
+    const affectedSymbols = new Set([
+      ...directReferences.map(r => r.callerName),
+      ...references.map(r => r.symbolName)
+    ].filter(Boolean));
     
     // Categorize files
     const impactByType = {
@@ -999,6 +1316,51 @@ export class ContextRetriever {
     return impact;
   }
   
+  /**
+   * Files importing `name` from a file, following re-exports (`export { x } from`,
+   * `export * from`) back to every module that re-exposes it.
+   */
+  private findImporters(fileId: number, name: string): ModuleEdge[] {
+    const importers = this.db.getDatabase().prepare(`
+      SELECT i.file_id as fileId, f.relative_path as filePath, i.specifier, i.line, i.reexport, i.names
+      FROM imports i
+      JOIN files f ON i.file_id = f.id
+      WHERE i.target_file_id = ? AND i.file_id != i.target_file_id
+      ORDER BY f.relative_path, i.line
+    `);
+    const edges: ModuleEdge[] = [];
+    const visited = new Set<number>([fileId]);
+    const queue = [fileId];
+
+    while (queue.length > 0) {
+      const rows = importers.all(queue.shift()!) as Array<{
+        fileId: number; filePath: string; specifier: string; line: number | null; reexport: number; names: string | null;
+      }>;
+
+      for (const row of rows) {
+        // Bare imports (`import './x'`, Python `import x`) bind the whole module
+        const names = row.names ? JSON.parse(row.names) as string[] : [];
+        if (names.length > 0 && !names.includes(name) && !names.includes('*')) {
+          continue;
+        }
+
+        edges.push({
+          fileId: row.fileId,
+          filePath: row.filePath,
+          specifier: row.specifier,
+          line: row.line,
+          reexport: row.reexport === 1
+        });
+        if (row.reexport === 1 && !visited.has(row.fileId)) {
+          visited.add(row.fileId);
+          queue.push(row.fileId);
+        }
+      }
+    }
+
+    return edges;
+  }
+
   private isTestFile(filePath: string): boolean {
     const testPatterns = [
       '.test.', '.spec.', '_test.', '_spec.',
@@ -1010,43 +1372,23 @@ export class ContextRetriever {
     return testPatterns.some(pattern => lowerPath.includes(pattern));
   }
   
-  private findAllReferences(symbolName: string): FileReferenceRow[] {
-    const database = this.db.getDatabase();
-    
-    // Find all files that mention this symbol
-    return database.prepare(`
-      SELECT 
-        f.id as fileId,
-        f.relative_path as filePath,
-        f.content,
-        f.language
-      FROM files f
-      WHERE f.content LIKE '%' || ? || '%'
-    `).all(symbolName) as FileReferenceRow[];
-  }
-  
-  private createImpactAnalysisFromReferences(symbolName: string, references: FileReferenceRow[]): ImpactAnalysis {
-    const affectedFiles: ImpactAnalysis['affectedFiles'] = [];
-    
-    references.forEach(file => {
-      const lines = file.content.split('\n');
-      const matches: number[] = [];
-      
-      lines.forEach((line: string, index: number) => {
-        if (line.includes(symbolName)) {
-          matches.push(index + 1);
-        }
-      });
-      
-      if (matches.length > 0) {
-        affectedFiles.push({
-          path: file.filePath || file.path || '',
-          referenceCount: matches.length,
-          isTest: this.isTestFile(file.filePath || file.path || ''),
-          lines: matches
-        });
+  private createImpactAnalysisFromReferences(symbolName: string, references: ReferenceRow[]): ImpactAnalysis {
+    const byPath = new Map<string, ImpactAnalysis['affectedFiles'][number]>();
+    
+    references.forEach(ref => {
+      const file = byPath.get(ref.filePath) || {
+        path: ref.filePath,
+        referenceCount: 0,
+        isTest: this.isTestFile(ref.filePath),
+        lines: []
+      };
+      if (!file.lines.includes(ref.line)) {
+        file.lines.push(ref.line);
+        file.referenceCount++;
       }
+      byPath.set(ref.filePath, file);
     });
+    const affectedFiles = [...byPath.values()];
     
     const testFiles = affectedFiles.filter(f => f.isTest);
     const totalReferences = affectedFiles.reduce((sum, f) => sum + f.referenceCount, 0);
@@ -1071,7 +1413,7 @@ export class ContextRetriever {
       },
       
       riskLevel: totalReferences > 10 ? 'HIGH' : totalReferences > 5 ? 'MEDIUM' : 'LOW',
-      riskFactors: [`Symbol not indexed but found ${totalReferences} text references`],
+      riskFactors: [`Symbol not indexed but found ${totalReferences} references`],
       
       affectedFiles: affectedFiles.sort((a, b) => b.referenceCount - a.referenceCount),
       
@@ -1197,52 +1539,98 @@ export class ContextRetriever {
     `).run(key, result, expirationMinutes);
   }
   
-  private escapeFTS5(term: string): string {
-    // FTS5 special characters that need escaping or removal
-    // If the term contains only special characters, return empty to trigger LIKE fallback
-    const cleaned = term.replace(/[^a-zA-Z0-9\s_-]/g, '');
-    
-    // If nothing remains after cleaning, we can't use FTS5
-    if (cleaned.trim().length === 0) {
+  /**
+   * Restrict results to files belonging to the given Cargo crates
+   */
+  private buildCrateFilter(options: QueryOptions, params: Record<string, string>): string {
+    if (!options.crates?.length) {
       return '';
     }
-    
-    // For FTS5, we need to wrap terms with special chars in quotes
-    // But since we're removing them, just return the cleaned version
-    return cleaned.trim();
+
+    const names = options.crates.map((crate, i) => {
+      params[`crate${i}`] = crate;
+      return `:crate${i}`;
+    }).join(',');
+    return ` AND f.crate_id IN (SELECT id FROM crates WHERE name IN (${names}) OR lib_name IN (${names}))`;
   }
-  
-  private buildFileLikeQuery(_searchTerm: string, options: QueryOptions): string {
-    let query = `
+
+  /**
+   * Conditions for the language and crate options and the query's filters,
+   * with their values added to `params`. `type:` and `name:` only apply
+   * when `symbols` is joined as `s`.
+   */
+  private buildFilterClause(
+    parsed: ParsedQuery,
+    options: QueryOptions,
+    params: Record<string, string>,
+    symbols: boolean
+  ): string {
+    const conditions: string[] = [];
+    const anyOf = (key: string, values: string[], condition: (param: string) => string) => {
+      if (values.length === 0) return;
+      const alternatives = values.map((value, i) => {
+        params[`${key}${i}`] = value;
+        return condition(`:${key}${i}`);
+      });
+      conditions.push(`(${alternatives.join(' OR ')})`);
+    };
+
+    anyOf('fileType', options.fileTypes || [], param => `f.language = ${param}`);
+    anyOf('lang', parsed.filters.lang, param => `f.language = ${param}`);
+    anyOf(
+      'path',
+      parsed.filters.path.map(path => `%${escapeLike(path.replace(/[\\/]/g, sep))}%`),
+      param => `f.relative_path LIKE ${param} ESCAPE '\\'`
+    );
+    if (symbols) {
+      anyOf('type', parsed.filters.type.map(type => type.toLowerCase()), param => `s.type = ${param}`);
+      anyOf(
+        'name',
+        parsed.filters.name.map(name => name.endsWith('*') ? `${escapeLike(name.slice(0, -1))}%` : escapeLike(name)),
+        param => `(s.name LIKE ${param} ESCAPE '\\' OR s.qualified_name LIKE ${param} ESCAPE '\\')`
+      );
+    }
+
+    return conditions.map(condition => ` AND ${condition}`).join('') + this.buildCrateFilter(options, params);
+  }
+
+  /**
+   * Fallback for queries FTS5 can't express: every term must appear as a substring
+   */
+  private buildFileLikeQuery(parsed: ParsedQuery, options: QueryOptions): SearchStatement {
+    const params: Record<string, string> = {};
+    const terms = parsed.terms.map((term, i) => {
+      params[`like${i}`] = `%${escapeLike(term)}%`;
+      return ` AND f.content LIKE :like${i} ESCAPE '\\'`;
+    });
+    const sql = `
       SELECT 
-        f.id, f.path, f.relative_path, f.content, 
-        f.language, f.metadata, f.size
+        f.id, f.path, f.relative_path as relativePath, f.content, 
+        f.language, f.metadata, f.size, f.last_modified as lastModified
       FROM files f
-      WHERE f.content LIKE ?
+      WHERE 1 = 1${terms.join('')}
+      ${this.buildFilterClause(parsed, options, params, false)}
+      ORDER BY f.relative_path LIMIT 100
     `;
-    
-    if (options.fileTypes && options.fileTypes.length > 0) {
-      query += ` AND f.language IN (${options.fileTypes.map(() => '?').join(',')})`;
-    }
-    
-    query += ' ORDER BY f.relative_path LIMIT 100';
-    return query;
+    return { sql, params };
   }
   
-  private buildSymbolLikeQuery(_searchTerm: string, options: QueryOptions): string {
-    let query = `
+  private buildSymbolLikeQuery(parsed: ParsedQuery, options: QueryOptions): SearchStatement {
+    const params: Record<string, string> = {};
+    const terms = parsed.terms.map((term, i) => {
+      params[`like${i}`] = `%${escapeLike(term)}%`;
+      return ` AND (s.name LIKE :like${i} ESCAPE '\\' OR s.signature LIKE :like${i} ESCAPE '\\')`;
+    });
+    const sql = `
       SELECT 
-        s.*, f.relative_path as filePath, f.language
+        s.*, f.relative_path as filePath, f.language,
+        f.size as fileSize, f.last_modified as fileModified
       FROM symbols s
       JOIN files f ON s.file_id = f.id
-      WHERE (s.name LIKE ? OR s.signature LIKE ?)
+      WHERE 1 = 1${terms.join('')}
+      ${this.buildFilterClause(parsed, options, params, true)}
+      ORDER BY s.name LIMIT 100
     `;
-    
-    if (options.fileTypes && options.fileTypes.length > 0) {
-      query += ` AND f.language IN (${options.fileTypes.map(() => '?').join(',')})`;
-    }
-    
-    query += ' ORDER BY s.name LIMIT 100';
-    return query;
+    return { sql, params };
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,146 @@
+import type { PackForm, PackingReport } from '../types/index.js';
+
+// Share of a result's relevance that each form keeps
+const FORM_VALUE: Record<PackForm, number> = {
+  full: 1,
+  elided: 0.8,
+  skeleton: 0.55,
+  signature: 0.3
+};
+
+// Results with no relevance score still beat leaving the budget unused
+const MIN_RELEVANCE = 0.01;
+
+export interface PackCandidate<T> {
+  label: string;      // Path or symbol name, for the report
+  relevance: number;  // Only relative values matter
+  variants: Array<{ form: PackForm; item: T }>;  // Most detailed first
+}
+
+export interface PackedItem<T> {
+  item: T;
+  form: PackForm;
+  tokens: number;
+}
+
+interface Variant<T> {
+  form: PackForm;
+  item: T;
+  tokens: number;
+  value: number;
+}
+
+/**
+ * Fits ranked results into a token budget. Each result can be included in
+ * full, with its middle elided, as a skeleton of signatures, or as its
+ * signature alone; the packer picks a form per result (or drops it) to keep
+ * as much relevance as fits. Greedy: starting from nothing, it repeatedly
+ * makes the inclusion or upgrade that adds the most value per token.
+ */
+export class ContextPacker {
+  private countTokens: (value: unknown) => number;
+
+  constructor(countTokens: (value: unknown) => number) {
+    this.countTokens = countTokens;
+  }
+
+  public pack<T>(allCandidates: PackCandidate<T>[], budget: number): { items: PackedItem<T>[]; report: PackingReport } {
+    // Nothing to include, so nothing to report as dropped either
+    const candidates = allCandidates.filter(candidate => candidate.variants.length > 0);
+    const variants: Variant<T>[][] = candidates.map(candidate => candidate.variants.map(variant => ({
+      ...variant,
+      tokens: this.countTokens(variant.item),
+      value: Math.max(candidate.relevance, MIN_RELEVANCE) * FORM_VALUE[variant.form]
+    })));
+    const chosen: Array<Variant<T> | null> = candidates.map(() => null);
+    let used = 0;
+
+    for (;;) {
+      let best: { index: number; variant: Variant<T>; ratio: number } | null = null;
+
+      for (let index = 0; index < variants.length; index++) {
+        const current = chosen[index];
+        for (const variant of variants[index]) {
+          const extraTokens = variant.tokens - (current?.tokens ?? 0);
+          const extraValue = variant.value - (current?.value ?? 0);
+          if (extraValue <= 0 || used + extraTokens > budget) continue;
+
+          const ratio = extraValue / Math.max(extraTokens, 1);
+          if (!best || ratio > best.ratio) {
+            best = { index, variant, ratio };
+          }
+        }
+      }
+
+      if (!best) break;
+      used += best.variant.tokens - (chosen[best.index]?.tokens ?? 0);
+      chosen[best.index] = best.variant;
+    }
+
+    const report: PackingReport = { budget, used, reduced: [], dropped: [] };
+    const items: PackedItem<T>[] = [];
+
+    candidates.forEach((candidate, index) => {
+      const variant = chosen[index];
+      if (!variant) {
+        report.dropped.push({
+          label: candidate.label,
+          tokens: Math.min(...variants[index].map(option => option.tokens))
+        });
+        return;
+      }
+
+      items.push({ item: variant.item, form: variant.form, tokens: variant.tokens });
+      const [detailed] = variants[index];
+      if (variant !== detailed) {
+        report.reduced.push({ label: candidate.label, form: variant.form, savedTokens: detailed.tokens - variant.tokens });
+      }
+    });
+
+    return { items, report };
+  }
+}
+
+/**
+ * The first and last lines of `text`, with a marker for the lines left out;
+ * null when the text is short enough to keep whole
+ */
+export function elideMiddle(text: string, keepLines: number): string | null {
+  const lines = text.split('\n');
+  if (lines.length <= keepLines + 5) {
+    return null;
+  }
+
+  const head = Math.ceil(keepLines * 0.6);
+  const tail = keepLines - head;
+  return [
+    ...lines.slice(0, head),
+    `... ${lines.length - head - tail} lines elided ...`,
+    ...lines.slice(lines.length - tail)
+  ].join('\n');
+}
+
+/**
+ * The first line of each symbol as written, with `...` for what lies between
+ */
+export function skeleton(content: string, lineStarts: number[]): string | null {
+  const lines = content.split('\n');
+  const starts = [...new Set(lineStarts)].filter(line => line >= 1 && line <= lines.length).sort((a, b) => a - b);
+  if (starts.length === 0) {
+    return null;
+  }
+
+  const output: string[] = [];
+  let previous = 0;
+  for (const line of starts) {
+    if (line > previous + 1) {
+      output.push('...');
+    }
+    output.push(lines[line - 1].trimEnd());
+    previous = line;
+  }
+  if (previous < lines.length) {
+    output.push('...');
+  }
+  return output.join('\n');
+}
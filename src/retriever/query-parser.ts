@@ -0,0 +1,242 @@
+import { splitIdentifier } from '../utils/identifiers.js';
+
+/**
+ * Search query grammar, compiled to FTS5 MATCH expressions.
+ *
+ *   query   := or
+ *   or      := and ('OR' and)*
+ *   and     := unary (['AND'] unary)*       adjacent terms are ANDed
+ *   unary   := ('NOT' | '-') primary | primary
+ *   primary := '(' or ')' | "phrase" | word | word* | filter
+ *   filter  := (type | lang | path | name) ':' (word | "phrase")
+ *
+ * Every term is emitted as a quoted FTS5 string, so characters such as `::`,
+ * `.` or `-` inside a word only separate tokens and never reach FTS5 as
+ * syntax. Filters apply to the whole query wherever they appear.
+ */
+
+export class QueryParseError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = 'QueryParseError';
+  }
+}
+
+export interface QueryFilters {
+  type: string[];  // Symbol types, e.g. function, class
+  lang: string[];  // Languages, aliases expanded (rs -> rust)
+  path: string[];  // Substrings of the relative path
+  name: string[];  // Symbol names; a trailing * matches a prefix
+}
+
+export interface ParsedQuery {
+  match: string | null;   // FTS5 expression; null when no term has searchable characters
+  terms: string[];        // Words and phrases that must appear, for LIKE fallback and ranking
+  filters: QueryFilters;
+  text: string;           // The terms joined back together, without operators and filters
+}
+
+type Token =
+  | { kind: 'word'; value: string; prefix: boolean }
+  | { kind: 'phrase'; value: string }
+  | { kind: 'filter'; key: keyof QueryFilters; value: string }
+  | { kind: 'and' | 'or' | 'not' | 'open' | 'close' };
+
+type Node =
+  | { kind: 'term'; value: string; prefix: boolean }
+  | { kind: 'and'; children: Node[]; negated: Node[] }
+  | { kind: 'or'; children: Node[] };
+
+const FILTER_KEYS = new Set(['type', 'lang', 'path', 'name']);
+
+const LANGUAGE_ALIASES: Record<string, string> = {
+  ts: 'typescript', tsx: 'typescript', js: 'javascript', jsx: 'javascript',
+  py: 'python', rs: 'rust', rb: 'ruby', 'c++': 'cpp', cs: 'csharp', kt: 'kotlin', md: 'markdown'
+};
+
+export function parseQuery(input: string): ParsedQuery {
+  const filters: QueryFilters = { type: [], lang: [], path: [], name: [] };
+  const tokens = tokenize(input).filter(token => {
+    if (token.kind !== 'filter') return true;
+    const value = token.key === 'lang' ? LANGUAGE_ALIASES[token.value.toLowerCase()] || token.value.toLowerCase() : token.value;
+    filters[token.key].push(value);
+    return false;
+  });
+
+  let position = 0;
+  const peek = () => tokens[position];
+
+  const parseOr = (): Node | null => {
+    const first = parseAnd();
+    const children: Node[] = first ? [first] : [];
+    while (peek()?.kind === 'or') {
+      position++;
+      const next = parseAnd();
+      if (!first || !next) throw new QueryParseError('OR needs a term on both sides');
+      children.push(next);
+    }
+    return children.length === 0 ? null : children.length === 1 ? children[0] : { kind: 'or', children };
+  };
+
+  const parseAnd = (): Node | null => {
+    const children: Node[] = [];
+    const negated: Node[] = [];
+    for (;;) {
+      const token = peek();
+      if (!token || token.kind === 'or' || token.kind === 'close') break;
+      if (token.kind === 'and') {
+        position++;
+        continue;
+      }
+      if (token.kind === 'not') {
+        position++;
+        const operand = parsePrimary();
+        if (!operand) throw new QueryParseError('NOT needs a term after it');
+        negated.push(operand);
+        continue;
+      }
+      const operand = parsePrimary();
+      if (operand) children.push(operand);
+    }
+
+    if (children.length === 0) {
+      if (negated.length > 0) throw new QueryParseError('A query needs at least one term that is not excluded');
+      return null;
+    }
+    return children.length === 1 && negated.length === 0 ? children[0] : { kind: 'and', children, negated };
+  };
+
+  const parsePrimary = (): Node | null => {
+    const token = tokens[position++];
+    if (!token) return null;
+    switch (token.kind) {
+      case 'open': {
+        const inner = parseOr();
+        if (tokens[position]?.kind !== 'close') throw new QueryParseError('Missing closing parenthesis');
+        position++;
+        return inner;
+      }
+      case 'close':
+        throw new QueryParseError('Unexpected closing parenthesis');
+      case 'word':
+        return { kind: 'term', value: token.value, prefix: token.prefix };
+      case 'phrase':
+        return { kind: 'term', value: token.value, prefix: false };
+      default:
+        throw new QueryParseError(`Unexpected ${token.kind.toUpperCase()}`);
+    }
+  };
+
+  const tree = parseOr();
+  if (position < tokens.length) {
+    throw new QueryParseError('Unexpected closing parenthesis');
+  }
+
+  const terms: string[] = [];
+  const collect = (node: Node | null) => {
+    if (!node) return;
+    if (node.kind === 'term') terms.push(node.value);
+    else node.children.forEach(collect);
+  };
+  collect(tree);
+
+  return {
+    match: tree ? compile(tree) : null,
+    terms,
+    filters,
+    text: terms.join(' ')
+  };
+}
+
+/**
+ * FTS5 expression for a node, or null when nothing in it can be matched
+ * (terms made only of punctuation). Such terms drop out of ANDs; an OR or a
+ * negation that loses an operand drops out entirely.
+ */
+function compile(node: Node): string | null {
+  switch (node.kind) {
+    case 'term': {
+      if (!/[\p{L}\p{N}_]/u.test(node.value)) return null;
+      const star = node.prefix ? '*' : '';
+      const term = `"${node.value.replace(/"/g, '""')}"${star}`;
+      // `getUser` and `get_user` are single tokens; their words also match
+      // the identifier_parts of longer names such as `getUserById`
+      const words = splitIdentifier(node.value).join(' ');
+      const tokens = node.value.toLowerCase().split(/[^\p{L}\p{N}_]+/u).filter(Boolean).join(' ');
+      return words && words !== tokens ? `(${term} OR "${words}"${star})` : term;
+    }
+    case 'or': {
+      const children = node.children.map(compile);
+      if (children.some(child => child === null)) return null;
+      return `(${children.join(' OR ')})`;
+    }
+    case 'and': {
+      const children = node.children.map(compile).filter((child): child is string => child !== null);
+      if (children.length === 0) return null;
+      const negated = node.negated.map(compile).filter((child): child is string => child !== null);
+      const positive = children.length === 1 ? children[0] : `(${children.join(' AND ')})`;
+      return negated.length === 0 ? positive : `(${negated.reduce((expression, child) => `${expression} NOT ${child}`, positive)})`;
+    }
+  }
+}
+
+function tokenize(input: string): Token[] {
+  const tokens: Token[] = [];
+  let i = 0;
+
+  const readQuoted = (): string => {
+    // Opening quote at input[i]; a doubled quote is a literal one
+    let value = '';
+    i++;
+    while (i < input.length) {
+      if (input[i] === '"') {
+        if (input[i + 1] === '"') {
+          value += '"';
+          i += 2;
+          continue;
+        }
+        i++;
+        return value;
+      }
+      value += input[i++];
+    }
+    throw new QueryParseError('Unterminated quoted phrase');
+  };
+
+  while (i < input.length) {
+    const char = input[i];
+    if (/\s/.test(char)) {
+      i++;
+    } else if (char === '(') {
+      tokens.push({ kind: 'open' });
+      i++;
+    } else if (char === ')') {
+      tokens.push({ kind: 'close' });
+      i++;
+    } else if (char === '"') {
+      tokens.push({ kind: 'phrase', value: readQuoted() });
+    } else if (char === '-' && i + 1 < input.length && /[^\s-]/.test(input[i + 1]) && (i === 0 || /[\s(]/.test(input[i - 1]))) {
+      tokens.push({ kind: 'not' });
+      i++;
+    } else {
+      const start = i;
+      while (i < input.length && !/[\s()"]/.test(input[i])) i++;
+      const word = input.substring(start, i);
+
+      const filter = word.match(/^(\w+):(.*)$/);
+      if (filter && FILTER_KEYS.has(filter[1].toLowerCase())) {
+        const value = filter[2] === '' && input[i] === '"' ? readQuoted() : filter[2];
+        if (!value) throw new QueryParseError(`${filter[1]}: needs a value`);
+        tokens.push({ kind: 'filter', key: filter[1].toLowerCase() as keyof QueryFilters, value });
+      } else if (word === 'AND' || word === 'OR' || word === 'NOT') {
+        tokens.push({ kind: word.toLowerCase() as 'and' | 'or' | 'not' });
+      } else if (word.length > 1 && word.endsWith('*')) {
+        tokens.push({ kind: 'word', value: word.replace(/\*+$/, ''), prefix: true });
+      } else if (word !== '*') {
+        tokens.push({ kind: 'word', value: word, prefix: false });
+      }
+    }
+  }
+
+  return tokens;
+}
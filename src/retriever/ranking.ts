@@ -0,0 +1,281 @@
+import { basename, extname } from 'path';
+import { PrimordynDB } from '../database/index.js';
+import { GitAnalyzer } from '../git/analyzer.js';
+import type { FileQueryRow, SymbolQueryRow, RelevanceScore, QueryOptions } from '../types/index.js';
+
+export type SortMode = NonNullable<QueryOptions['sortBy']>;
+
+// How much each signal contributes to the combined score
+export const RANKING_WEIGHTS: Omit<RelevanceScore, 'total'> = {
+  bm25: 3,
+  exactName: 3,
+  symbolType: 1,
+  definition: 1.5,
+  centrality: 1,
+  recency: 0.5,
+  semantic: 4
+};
+
+// Types people usually search for rank above the members and values inside them
+const SYMBOL_TYPE_WEIGHTS: Record<string, number> = {
+  class: 1,
+  interface: 1,
+  struct: 1,
+  trait: 1,
+  enum: 1,
+  function: 0.8,
+  method: 0.8,
+  type: 0.7,
+  module: 0.5,
+  impl: 0.5,
+  const: 0.4,
+  variable: 0.4,
+  property: 0.3
+};
+
+// Days after which git recency counts for half
+const RECENCY_HALF_LIFE_DAYS = 30;
+
+// Embedding similarity of files and symbols to the query, from `query --semantic`
+export interface SemanticSimilarity {
+  files: Map<number, number>;
+  symbols: Map<number, number>;
+}
+
+export type RankedResult =
+  | { kind: 'file'; row: FileQueryRow; score: RelevanceScore }
+  | { kind: 'symbol'; row: SymbolQueryRow; score: RelevanceScore };
+
+interface Candidate {
+  result: RankedResult;
+  path: string;
+  line: number;
+  size: number;
+  modified: number;
+}
+
+/**
+ * Orders full-text matches. Each file and symbol gets a score from its bm25
+ * rank (normalized within its table), how closely its name matches the
+ * search term, its symbol type, whether it defines the term or only mentions
+ * it, how many files call or import it, how recently it changed in git and,
+ * for semantic searches, how close its embedding is to the query's.
+ *
+ * Scores are always computed; `sortBy` only decides whether they or the
+ * path, size or modification time order the results.
+ */
+export class Ranker {
+  private db: PrimordynDB;
+  private gitAnalyzer: GitAnalyzer;
+
+  constructor(db: PrimordynDB, gitAnalyzer: GitAnalyzer) {
+    this.db = db;
+    this.gitAnalyzer = gitAnalyzer;
+  }
+
+  public rank(
+    searchTerm: string,
+    files: FileQueryRow[],
+    symbols: SymbolQueryRow[],
+    sortBy: SortMode = 'relevance',
+    similarity?: SemanticSimilarity
+  ): RankedResult[] {
+    const terms = this.terms(searchTerm);
+    const fileIds = files.map(file => file.id);
+    const symbolIds = symbols.map(symbol => symbol.id);
+
+    const fileBm25 = this.normalizeBm25(files.map(file => file.rank));
+    const symbolBm25 = this.normalizeBm25(symbols.map(symbol => symbol.rank));
+    const fileCentrality = this.normalizeCounts(this.fileInDegrees(fileIds), fileIds);
+    const symbolCentrality = this.normalizeCounts(this.symbolInDegrees(symbolIds), symbolIds);
+    const definingFiles = this.definingFiles(fileIds, terms);
+    const commitDates = this.gitAnalyzer.getLastCommitDates();
+
+    const recency = (path: string, lastModified?: string | null) => {
+      const date = commitDates.get(path.replace(/\\/g, '/')) ?? (lastModified ? new Date(lastModified) : undefined);
+      if (!date || isNaN(date.getTime())) return 0;
+      const ageDays = Math.max(0, (Date.now() - date.getTime()) / 86_400_000);
+      return Math.pow(0.5, ageDays / RECENCY_HALF_LIFE_DAYS);
+    };
+
+    const candidates: Candidate[] = [
+      ...files.map((file, index): Candidate => {
+        const path = file.relativePath || file.relative_path;
+        return {
+          result: {
+            kind: 'file',
+            row: file,
+            score: this.combine({
+              bm25: fileBm25[index],
+              exactName: this.nameMatch(basename(path, extname(path)), searchTerm, terms),
+              symbolType: 0,
+              definition: definingFiles.has(file.id) ? 1 : 0,
+              centrality: fileCentrality.get(file.id) || 0,
+              recency: recency(path, file.lastModified ?? file.last_modified),
+              semantic: Math.max(0, similarity?.files.get(file.id) || 0)
+            })
+          },
+          path,
+          line: 0,
+          size: file.size || 0,
+          modified: this.timestamp(file.lastModified ?? file.last_modified)
+        };
+      }),
+      ...symbols.map((symbol, index): Candidate => {
+        const path = symbol.filePath || symbol.relative_path || '';
+        return {
+          result: {
+            kind: 'symbol',
+            row: symbol,
+            score: this.combine({
+              bm25: symbolBm25[index],
+              exactName: Math.max(
+                this.nameMatch(symbol.name, searchTerm, terms),
+                symbol.qualified_name === searchTerm ? 1 : 0
+              ),
+              symbolType: SYMBOL_TYPE_WEIGHTS[symbol.type] ?? 0.3,
+              definition: 1,
+              centrality: symbolCentrality.get(symbol.id) || 0,
+              recency: recency(path, symbol.fileModified),
+              semantic: Math.max(0, similarity?.symbols.get(symbol.id) || 0)
+            })
+          },
+          path,
+          line: symbol.lineStart || symbol.line_start || 0,
+          size: symbol.fileSize || 0,
+          modified: this.timestamp(symbol.fileModified)
+        };
+      })
+    ];
+
+    const byPath = (a: Candidate, b: Candidate) => a.path.localeCompare(b.path) || a.line - b.line;
+    const byScore = (a: Candidate, b: Candidate) => b.result.score.total - a.result.score.total;
+    const compare: Record<SortMode, (a: Candidate, b: Candidate) => number> = {
+      relevance: (a, b) => byScore(a, b) || byPath(a, b),
+      path: byPath,
+      size: (a, b) => b.size - a.size || byScore(a, b),
+      modified: (a, b) => b.modified - a.modified || byScore(a, b)
+    };
+
+    return candidates.sort(compare[sortBy]).map(candidate => candidate.result);
+  }
+
+  /**
+   * Words of the search term, lower-cased; the whole term counts as one too
+   */
+  private terms(searchTerm: string): string[] {
+    const words = searchTerm.toLowerCase().split(/\s+/).filter(Boolean);
+    return [...new Set([searchTerm.toLowerCase(), ...words])];
+  }
+
+  private nameMatch(name: string, searchTerm: string, terms: string[]): number {
+    if (name === searchTerm) return 1;
+    const lower = name.toLowerCase();
+    if (terms.includes(lower)) return 0.8;
+    if (terms.some(term => lower.startsWith(term))) return 0.4;
+    if (terms.some(term => lower.includes(term))) return 0.2;
+    return 0;
+  }
+
+  private combine(signals: Omit<RelevanceScore, 'total'>): RelevanceScore {
+    const round = (value: number) => Math.round(value * 1000) / 1000;
+    const total = (Object.keys(RANKING_WEIGHTS) as Array<keyof typeof RANKING_WEIGHTS>)
+      .reduce((sum, key) => sum + RANKING_WEIGHTS[key] * signals[key], 0);
+    return {
+      total: round(total),
+      bm25: round(signals.bm25),
+      exactName: round(signals.exactName),
+      symbolType: round(signals.symbolType),
+      definition: round(signals.definition),
+      centrality: round(signals.centrality),
+      recency: round(signals.recency),
+      semantic: round(signals.semantic)
+    };
+  }
+
+  /**
+   * bm25() is negative, lower is better; scale so the best match is 1.
+   * LIKE fallback rows have no rank and score 0.
+   */
+  private normalizeBm25(ranks: Array<number | null | undefined>): number[] {
+    const best = Math.min(0, ...ranks.filter((rank): rank is number => typeof rank === 'number'));
+    return ranks.map(rank => typeof rank === 'number' && best < 0 ? rank / best : 0);
+  }
+
+  /**
+   * Log-scaled in-degree relative to the best connected result
+   */
+  private normalizeCounts(counts: Map<number, number>, ids: number[]): Map<number, number> {
+    const max = Math.max(0, ...ids.map(id => counts.get(id) || 0));
+    const normalized = new Map<number, number>();
+    if (max === 0) return normalized;
+    for (const id of ids) {
+      normalized.set(id, Math.log1p(counts.get(id) || 0) / Math.log1p(max));
+    }
+    return normalized;
+  }
+
+  /**
+   * Files calling into a symbol, plus types extending or implementing it
+   */
+  private symbolInDegrees(ids: number[]): Map<number, number> {
+    if (ids.length === 0) return new Map();
+    const placeholders = ids.map(() => '?').join(',');
+    const rows = this.db.getDatabase().prepare(`
+      SELECT id, SUM(n) as count FROM (
+        SELECT callee_symbol_id as id, COUNT(DISTINCT caller_file_id) as n
+        FROM call_graph
+        WHERE callee_symbol_id IN (${placeholders})
+        GROUP BY callee_symbol_id
+        UNION ALL
+        SELECT target_symbol_id as id, COUNT(*) as n
+        FROM type_relations
+        WHERE target_symbol_id IN (${placeholders})
+        GROUP BY target_symbol_id
+      )
+      GROUP BY id
+    `).all(...ids, ...ids) as Array<{ id: number; count: number }>;
+    return new Map(rows.map(row => [row.id, row.count]));
+  }
+
+  /**
+   * Files importing a file or calling into it
+   */
+  private fileInDegrees(ids: number[]): Map<number, number> {
+    if (ids.length === 0) return new Map();
+    const placeholders = ids.map(() => '?').join(',');
+    const rows = this.db.getDatabase().prepare(`
+      SELECT id, COUNT(DISTINCT source) as count FROM (
+        SELECT target_file_id as id, file_id as source
+        FROM imports
+        WHERE target_file_id IN (${placeholders})
+        UNION
+        SELECT callee_file_id as id, caller_file_id as source
+        FROM call_graph
+        WHERE callee_file_id IN (${placeholders}) AND caller_file_id != callee_file_id
+      )
+      GROUP BY id
+    `).all(...ids, ...ids) as Array<{ id: number; count: number }>;
+    return new Map(rows.map(row => [row.id, row.count]));
+  }
+
+  /**
+   * Files that define a symbol named like the search term, as opposed to
+   * files that only mention it
+   */
+  private definingFiles(ids: number[], terms: string[]): Set<number> {
+    if (ids.length === 0) return new Set();
+    const rows = this.db.getDatabase().prepare(`
+      SELECT DISTINCT file_id as id
+      FROM symbols
+      WHERE file_id IN (${ids.map(() => '?').join(',')})
+        AND LOWER(name) IN (${terms.map(() => '?').join(',')})
+    `).all(...ids, ...terms) as Array<{ id: number }>;
+    return new Set(rows.map(row => row.id));
+  }
+
+  private timestamp(value?: string | null): number {
+    const time = value ? new Date(value).getTime() : NaN;
+    return isNaN(time) ? 0 : time;
+  }
+}
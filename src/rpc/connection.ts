@@ -0,0 +1,179 @@
+import type { Readable, Writable } from 'stream';
+
+export type JsonRpcId = number | string | null;
+
+export interface JsonRpcMessage {
+  jsonrpc: '2.0';
+  id?: JsonRpcId;
+  method?: string;
+  params?: unknown;
+  result?: unknown;
+  error?: { code: number; message: string; data?: unknown };
+}
+
+export type RequestHandler = (params: unknown) => unknown | Promise<unknown>;
+export type NotificationHandler = (params: unknown) => void | Promise<void>;
+
+/**
+ * Message framing on the wire:
+ * - `header`: LSP-style `Content-Length` headers followed by the JSON body
+ * - `newline`: one JSON message per line (used by MCP stdio transports)
+ */
+export type JsonRpcFraming = 'header' | 'newline';
+
+export const ErrorCodes = {
+  ParseError: -32700,
+  InvalidRequest: -32600,
+  MethodNotFound: -32601,
+  InvalidParams: -32602,
+  InternalError: -32603
+} as const;
+
+/**
+ * Error thrown from a handler to send a specific JSON-RPC error code back
+ */
+export class JsonRpcError extends Error {
+  constructor(public code: number, message: string, public data?: unknown) {
+    super(message);
+    this.name = 'JsonRpcError';
+  }
+}
+
+/**
+ * Minimal JSON-RPC 2.0 connection over a pair of streams.
+ */
+export class JsonRpcConnection {
+  private requestHandlers: Map<string, RequestHandler> = new Map();
+  private notificationHandlers: Map<string, NotificationHandler> = new Map();
+  private buffer: Buffer = Buffer.alloc(0);
+  private closeHandlers: (() => void)[] = [];
+
+  constructor(
+    private input: Readable,
+    private output: Writable,
+    private framing: JsonRpcFraming = 'header'
+  ) {}
+
+  public onRequest(method: string, handler: RequestHandler): void {
+    this.requestHandlers.set(method, handler);
+  }
+
+  public onNotification(method: string, handler: NotificationHandler): void {
+    this.notificationHandlers.set(method, handler);
+  }
+
+  public onClose(handler: () => void): void {
+    this.closeHandlers.push(handler);
+  }
+
+  public listen(): void {
+    this.input.on('data', (chunk: Buffer | string) => {
+      this.buffer = Buffer.concat([this.buffer, typeof chunk === 'string' ? Buffer.from(chunk) : chunk]);
+      this.drain();
+    });
+    this.input.on('end', () => this.closeHandlers.forEach(handler => handler()));
+  }
+
+  public sendNotification(method: string, params?: unknown): void {
+    this.write({ jsonrpc: '2.0', method, params });
+  }
+
+  private drain(): void {
+    let body: string | null;
+    while ((body = this.nextMessage()) !== null) {
+      let message: JsonRpcMessage;
+      try {
+        message = JSON.parse(body);
+      } catch {
+        this.write({ jsonrpc: '2.0', id: null, error: { code: ErrorCodes.ParseError, message: 'Parse error' } });
+        continue;
+      }
+      void this.dispatch(message);
+    }
+  }
+
+  /**
+   * Pull the next complete message body off the buffer, if there is one
+   */
+  private nextMessage(): string | null {
+    if (this.framing === 'newline') {
+      const end = this.buffer.indexOf('\n');
+      if (end === -1) return null;
+      const line = this.buffer.subarray(0, end).toString('utf-8').trim();
+      this.buffer = this.buffer.subarray(end + 1);
+      return line.length > 0 ? line : this.nextMessage();
+    }
+
+    const headerEnd = this.buffer.indexOf('\r\n\r\n');
+    if (headerEnd === -1) return null;
+
+    const headers = this.buffer.subarray(0, headerEnd).toString('ascii');
+    const match = headers.match(/Content-Length:\s*(\d+)/i);
+    if (!match) {
+      // Skip the malformed header block
+      this.buffer = this.buffer.subarray(headerEnd + 4);
+      return this.nextMessage();
+    }
+
+    const length = parseInt(match[1], 10);
+    const start = headerEnd + 4;
+    if (this.buffer.length < start + length) return null;
+
+    const body = this.buffer.subarray(start, start + length).toString('utf-8');
+    this.buffer = this.buffer.subarray(start + length);
+    return body;
+  }
+
+  private async dispatch(message: JsonRpcMessage): Promise<void> {
+    if (!message.method) {
+      return; // Responses to server-initiated requests are not used
+    }
+
+    const isRequest = message.id !== undefined;
+
+    if (!isRequest) {
+      const handler = this.notificationHandlers.get(message.method);
+      try {
+        await handler?.(message.params);
+      } catch (error) {
+        process.stderr.write(`Error handling ${message.method}: ${error instanceof Error ? error.message : error}\n`);
+      }
+      return;
+    }
+
+    const handler = this.requestHandlers.get(message.method);
+    if (!handler) {
+      this.write({
+        jsonrpc: '2.0',
+        id: message.id,
+        error: { code: ErrorCodes.MethodNotFound, message: `Method not found: ${message.method}` }
+      });
+      return;
+    }
+
+    try {
+      const result = await handler(message.params);
+      this.write({ jsonrpc: '2.0', id: message.id, result: result === undefined ? null : result });
+    } catch (error) {
+      const code = error instanceof JsonRpcError ? error.code : ErrorCodes.InternalError;
+      this.write({
+        jsonrpc: '2.0',
+        id: message.id,
+        error: {
+          code,
+          message: error instanceof Error ? error.message : String(error),
+          data: error instanceof JsonRpcError ? error.data : undefined
+        }
+      });
+    }
+  }
+
+  private write(message: JsonRpcMessage): void {
+    const body = JSON.stringify(message);
+    if (this.framing === 'newline') {
+      this.output.write(body + '\n');
+    } else {
+      this.output.write(`Content-Length: ${Buffer.byteLength(body, 'utf-8')}\r\n\r\n${body}`);
+    }
+  }
+}
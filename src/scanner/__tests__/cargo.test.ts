@@ -0,0 +1,96 @@
+import { CrateGraph, parseToml } from '../cargo.js';
+import { mkdirSync, writeFileSync, rmSync, existsSync } from 'fs';
+import { join } from 'path';
+
+describe('CrateGraph', () => {
+  const testDir = join(process.cwd(), '.test-cargo');
+
+  beforeEach(() => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+    mkdirSync(join(testDir, 'crates', 'core', 'src'), { recursive: true });
+    mkdirSync(join(testDir, 'crates', 'cli', 'src'), { recursive: true });
+
+    writeFileSync(join(testDir, 'Cargo.toml'), [
+      '[workspace]',
+      'members = [',
+      '  "crates/*", # all crates',
+      ']',
+      '',
+      '[workspace.dependencies]',
+      'my-core = { path = "crates/core" }'
+    ].join('\n'));
+
+    writeFileSync(join(testDir, 'crates', 'core', 'Cargo.toml'), [
+      '[package]',
+      'name = "my-core"',
+      'version = "0.1.0"'
+    ].join('\n'));
+
+    writeFileSync(join(testDir, 'crates', 'cli', 'Cargo.toml'), [
+      '[package]',
+      'name = "my-cli"',
+      '',
+      '[dependencies]',
+      'my-core.workspace = true',
+      'engine = { package = "my-core", path = "../core" }',
+      'serde = { version = "1", features = ["derive"] }'
+    ].join('\n'));
+  });
+
+  afterEach(() => {
+    if (existsSync(testDir)) {
+      rmSync(testDir, { recursive: true, force: true });
+    }
+  });
+
+  test('should discover workspace members and their dependencies', () => {
+    const graph = CrateGraph.load(testDir);
+
+    expect(graph.workspaceRoot).toBe('Cargo.toml');
+    expect(graph.crates.map(c => c.name).sort()).toEqual(['my-cli', 'my-core']);
+
+    const cli = graph.crates.find(c => c.name === 'my-cli')!;
+    expect(cli.workspaceMember).toBe(true);
+    expect(cli.dependencies).toContainEqual(expect.objectContaining({
+      name: 'my-core', workspace: true, path: join('crates', 'core')
+    }));
+    expect(cli.dependencies.find(d => d.name === 'serde')?.path).toBeUndefined();
+  });
+
+  test('should map files and use paths to crates', () => {
+    const graph = CrateGraph.load(testDir);
+    const cli = graph.crateForPath(join('crates', 'cli', 'src', 'main.rs'));
+
+    expect(cli?.name).toBe('my-cli');
+    expect(graph.crateForPath('README.md')).toBeNull();
+
+    const expand = (path: string) => {
+      const expanded = graph.expandPath(path.split('::'), cli, 'crate::cmd::run');
+      return [expanded.crate?.name, expanded.segments.join('::'), expanded.minLength];
+    };
+    expect(expand('my_core::db::open')).toEqual(['my-core', 'crate::db::open', 1]);
+    expect(expand('engine::Engine')).toEqual(['my-core', 'crate::Engine', 1]);
+    expect(expand('crate::config::load')).toEqual(['my-cli', 'crate::config::load', 1]);
+    expect(expand('self::helper')).toEqual(['my-cli', 'crate::cmd::run::helper', 3]);
+    expect(expand('super::super::Args')).toEqual(['my-cli', 'crate::Args', 1]);
+    // Unknown roots are relative to the current module
+    expect(expand('serde::Serialize')).toEqual(['my-cli', 'crate::cmd::run::serde::Serialize', 4]);
+  });
+
+  test('should parse the TOML subset used by manifests', () => {
+    const manifest = parseToml([
+      '[package]',
+      'name = \'literal\'',
+      'edition = "2021"',
+      '[[bin]]',
+      'name = "a"',
+      '[[bin]]',
+      'name = "b"'
+    ].join('\n'));
+
+    expect(manifest.package).toEqual({ name: 'literal', edition: '2021' });
+    expect(manifest.bin).toEqual([{ name: 'a' }, { name: 'b' }]);
+  });
+});
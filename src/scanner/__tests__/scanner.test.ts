@@ -1,4 +1,5 @@
 import { FileScanner } from '../index.js';
+import type { FileInfo } from '../../types/index.js';
 import { mkdirSync, writeFileSync, rmSync, existsSync } from 'fs';
 import { join } from 'path';
 
@@ -109,4 +110,28 @@ describe('FileScanner', () => {
     const paths = files.map(f => f.relativePath);
     expect(paths).not.toContain('large.txt');
   });
-});
\ No newline at end of file
+
+  test('should stream files and skip reading unchanged ones', async () => {
+    const scanner = new FileScanner({ rootPath: testDir });
+    const first = await scanner.scan();
+    const main = first.find(f => f.relativePath.endsWith('main.ts'))!;
+
+    const known = new Map([[main.path, {
+      size: main.size,
+      lastModified: main.lastModified.toISOString(),
+      hash: main.hash
+    }]]);
+
+    const streamed: FileInfo[] = [];
+    for await (const file of scanner.scanStream(known)) {
+      streamed.push(file);
+    }
+
+    expect(streamed).toHaveLength(first.length);
+    const unchanged = streamed.find(f => f.path === main.path)!;
+    expect(unchanged.unchanged).toBe(true);
+    expect(unchanged.content).toBe('');
+    expect(unchanged.hash).toBe(main.hash);
+    expect(streamed.filter(f => f.unchanged)).toHaveLength(1);
+  });
+});
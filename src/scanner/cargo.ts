@@ -0,0 +1,514 @@
+import { existsSync, readFileSync, readdirSync, statSync } from 'fs';
+import { dirname, join, normalize, relative, sep } from 'path';
+import type Database from 'better-sqlite3';
+
+type TomlValue = string | number | boolean | TomlValue[] | TomlTable;
+
+export interface TomlTable {
+  [key: string]: TomlValue;
+}
+
+export interface CargoDependency {
+  name: string;          // Name used in code (the key under [dependencies])
+  package: string;       // Actual package name (differs when renamed with `package = ...`)
+  path?: string;         // Project-relative directory for path dependencies
+  workspace: boolean;    // Inherited from [workspace.dependencies]
+  kind: 'normal' | 'dev' | 'build';
+}
+
+export interface CargoCrate {
+  id?: number;           // Row in the `crates` table, for graphs read from the index
+  name: string;          // Package name as written in Cargo.toml
+  libName: string;       // Name used in `use` paths
+  manifestPath: string;  // Project-relative path to Cargo.toml
+  rootDir: string;       // Project-relative crate directory ('' for the project root)
+  version?: string;
+  workspaceMember: boolean;
+  dependencies: CargoDependency[];
+}
+
+export interface RustPath {
+  crate: CargoCrate | null;  // Crate the path points into
+  segments: string[];        // Absolute path within that crate, starting with `crate`
+  minLength: number;         // Shortest prefix that may name the defining module
+}
+
+/**
+ * Crates and workspaces described by the Cargo manifests in a project.
+ */
+export class CrateGraph {
+  public readonly crates: CargoCrate[];
+  public readonly workspaceRoot: string | null;
+
+  private constructor(crates: CargoCrate[], workspaceRoot: string | null) {
+    // Deepest crates first so nested crates win over their parents
+    this.crates = crates.sort((a, b) => b.rootDir.length - a.rootDir.length);
+    this.workspaceRoot = workspaceRoot;
+  }
+
+  /**
+   * Load the crate graph from the project's root manifest, its workspace
+   * members and any additional manifests found by the scanner.
+   */
+  public static load(projectRoot: string, manifestPaths: string[] = []): CrateGraph {
+    const manifests = new Map<string, TomlTable>();
+    const readManifest = (relativePath: string): TomlTable | null => {
+      const key = normalize(relativePath);
+      if (manifests.has(key)) return manifests.get(key)!;
+      const fullPath = join(projectRoot, key);
+      if (!existsSync(fullPath)) return null;
+      try {
+        const manifest = parseToml(readFileSync(fullPath, 'utf-8'));
+        manifests.set(key, manifest);
+        return manifest;
+      } catch {
+        // Skip manifests we can't parse
+        return null;
+      }
+    };
+
+    let workspaceRoot: string | null = null;
+    let workspaceDependencies: TomlTable = {};
+    const members = new Set<string>();
+
+    const rootManifest = readManifest('Cargo.toml');
+    const workspace = rootManifest ? asTable(rootManifest.workspace) : null;
+    if (rootManifest && workspace) {
+      workspaceRoot = 'Cargo.toml';
+      workspaceDependencies = asTable(workspace.dependencies) || {};
+      const excluded = asStringArray(workspace.exclude).map(pattern => normalize(pattern));
+      for (const pattern of asStringArray(workspace.members)) {
+        for (const dir of expandMemberGlob(projectRoot, pattern)) {
+          if (!excluded.includes(dir) && existsSync(join(projectRoot, dir, 'Cargo.toml'))) {
+            members.add(dir);
+            readManifest(join(dir, 'Cargo.toml'));
+          }
+        }
+      }
+    }
+
+    manifestPaths.forEach(path => readManifest(path));
+
+    const crates: CargoCrate[] = [];
+    for (const [manifestPath, manifest] of manifests) {
+      const pkg = asTable(manifest.package);
+      if (!pkg || typeof pkg.name !== 'string') {
+        continue; // Virtual workspace manifest
+      }
+
+      const rootDir = dirname(manifestPath) === '.' ? '' : dirname(manifestPath);
+      const lib = asTable(manifest.lib);
+      const dependencies: CargoDependency[] = [];
+      const sections: [string, CargoDependency['kind']][] = [
+        ['dependencies', 'normal'],
+        ['dev-dependencies', 'dev'],
+        ['build-dependencies', 'build']
+      ];
+
+      for (const [section, kind] of sections) {
+        const table = asTable(manifest[section]);
+        if (!table) continue;
+
+        for (const [name, spec] of Object.entries(table)) {
+          dependencies.push(resolveDependency(name, spec, kind, rootDir, workspaceDependencies));
+        }
+      }
+
+      crates.push({
+        name: pkg.name,
+        libName: typeof lib?.name === 'string' ? lib.name : pkg.name.replace(/-/g, '_'),
+        manifestPath,
+        rootDir,
+        version: typeof pkg.version === 'string' ? pkg.version : undefined,
+        workspaceMember: members.has(rootDir) || (workspaceRoot !== null && rootDir === ''),
+        dependencies
+      });
+    }
+
+    return new CrateGraph(crates, workspaceRoot);
+  }
+
+  /**
+   * Load the crate graph stored in the index by the last `index` run.
+   */
+  public static fromIndex(database: Database.Database): CrateGraph {
+    const crates = new Map<number, CargoCrate>();
+    const crateRows = database.prepare(
+      'SELECT id, name, lib_name, manifest_path, root_path, version, workspace_member FROM crates'
+    ).all() as {
+      id: number; name: string; lib_name: string; manifest_path: string; root_path: string;
+      version: string | null; workspace_member: number;
+    }[];
+    for (const row of crateRows) {
+      crates.set(row.id, {
+        id: row.id,
+        name: row.name,
+        libName: row.lib_name,
+        manifestPath: row.manifest_path,
+        rootDir: row.root_path,
+        version: row.version ?? undefined,
+        workspaceMember: row.workspace_member === 1,
+        dependencies: []
+      });
+    }
+
+    const dependencyRows = database.prepare(
+      'SELECT crate_id, name, package, kind, path, workspace FROM crate_dependencies'
+    ).all() as { crate_id: number; name: string; package: string; kind: CargoDependency['kind']; path: string | null; workspace: number }[];
+    for (const row of dependencyRows) {
+      crates.get(row.crate_id)?.dependencies.push({
+        name: row.name,
+        package: row.package,
+        path: row.path ?? undefined,
+        workspace: row.workspace === 1,
+        kind: row.kind
+      });
+    }
+
+    // Members are only recorded when the root manifest declares a workspace
+    const list = [...crates.values()];
+    return new CrateGraph(list, list.some(crate => crate.workspaceMember) ? 'Cargo.toml' : null);
+  }
+
+  /**
+   * Find the crate a project-relative file path belongs to.
+   */
+  public crateForPath(relativePath: string): CargoCrate | null {
+    const normalized = normalize(relativePath);
+    return this.crates.find(crate =>
+      crate.rootDir === '' || normalized.startsWith(crate.rootDir + sep)
+    ) || null;
+  }
+
+  /**
+   * Find the crate stored in the index under `id`.
+   */
+  public crateWithId(id: number | null): CargoCrate | null {
+    return id === null ? null : this.crates.find(crate => crate.id === id) || null;
+  }
+
+  /**
+   * Expand a Rust path written in module `fromModule` of `fromCrate` into the
+   * crate it points into and a `crate::...` path within it, following
+   * `crate`/`self`/`super`, dependency names (including renamed ones) and
+   * other crates in the project. `minLength` keeps a path into an unknown
+   * extern crate from falling back to the current module.
+   */
+  public expandPath(path: string[], fromCrate: CargoCrate | null, fromModule: string = 'crate'): RustPath {
+    const moduleSegments = fromModule.split('::');
+    const [root, ...rest] = path;
+
+    if (root === 'crate') {
+      return { crate: fromCrate, segments: path, minLength: 1 };
+    }
+    if (root === 'self') {
+      return { crate: fromCrate, segments: [...moduleSegments, ...rest], minLength: moduleSegments.length };
+    }
+    if (root === 'super') {
+      let module = moduleSegments;
+      let remaining = path;
+      while (remaining[0] === 'super') {
+        module = module.length > 1 ? module.slice(0, -1) : module;
+        remaining = remaining.slice(1);
+      }
+      return { crate: fromCrate, segments: [...module, ...remaining], minLength: module.length };
+    }
+
+    const target = this.crateNamed(root, fromCrate);
+    if (target) {
+      return { crate: target, segments: ['crate', ...rest], minLength: 1 };
+    }
+
+    // 2018-edition path relative to the current module
+    return { crate: fromCrate, segments: [...moduleSegments, ...path], minLength: moduleSegments.length + 1 };
+  }
+
+  /**
+   * Find the crate a dependency points at, if it lives in this project.
+   */
+  public resolveDependency(dependency: CargoDependency): CargoCrate | null {
+    return this.crates.find(crate =>
+      (dependency.path !== undefined && crate.rootDir === dependency.path) || crate.name === dependency.package
+    ) || null;
+  }
+
+  /**
+   * The project crate a leading path segment (`other_crate` in
+   * `other_crate::X`) names, as seen from `fromCrate`.
+   */
+  private crateNamed(rootSegment: string, fromCrate: CargoCrate | null): CargoCrate | null {
+    if (['std', 'core', 'alloc'].includes(rootSegment)) {
+      return null;
+    }
+
+    // Honour renamed dependencies first (`foo = { package = "bar" }`)
+    const dependency = fromCrate?.dependencies.find(dep => dep.name.replace(/-/g, '_') === rootSegment);
+    if (dependency) {
+      return this.resolveDependency(dependency);
+    }
+
+    return this.crates.find(crate => crate.libName === rootSegment) || null;
+  }
+}
+
+function resolveDependency(
+  name: string,
+  spec: TomlValue,
+  kind: CargoDependency['kind'],
+  crateDir: string,
+  workspaceDependencies: TomlTable
+): CargoDependency {
+  const dependency: CargoDependency = { name, package: name, workspace: false, kind };
+  let table = asTable(spec);
+
+  if (table?.workspace === true) {
+    // `foo.workspace = true` inherits from the workspace root; paths there are root-relative
+    dependency.workspace = true;
+    const inherited = asTable(workspaceDependencies[name]);
+    if (typeof inherited?.package === 'string') {
+      dependency.package = inherited.package;
+    }
+    if (typeof inherited?.path === 'string') {
+      dependency.path = normalizeDir(inherited.path);
+    }
+    table = null;
+  }
+
+  if (table) {
+    if (typeof table.package === 'string') {
+      dependency.package = table.package;
+    }
+    if (typeof table.path === 'string') {
+      dependency.path = normalizeDir(join(crateDir, table.path));
+    }
+  }
+
+  return dependency;
+}
+
+function normalizeDir(path: string): string {
+  const normalized = normalize(path).replace(/[\\/]+$/, '');
+  return normalized === '.' ? '' : normalized;
+}
+
+/**
+ * Expand a workspace member pattern such as `crates/*` into directories.
+ */
+function expandMemberGlob(projectRoot: string, pattern: string): string[] {
+  const segments = normalize(pattern).split(/[\\/]/).filter(Boolean);
+  let dirs = [''];
+
+  for (const segment of segments) {
+    const next: string[] = [];
+    for (const dir of dirs) {
+      if (!segment.includes('*') && !segment.includes('?')) {
+        next.push(dir ? join(dir, segment) : segment);
+        continue;
+      }
+
+      const matcher = new RegExp('^' + segment.replace(/[.+^${}()|[\]\\]/g, '\\$&').replace(/\*/g, '.*').replace(/\?/g, '.') + '$');
+      const fullDir = join(projectRoot, dir);
+      if (!existsSync(fullDir)) continue;
+      for (const entry of readdirSync(fullDir)) {
+        if (matcher.test(entry) && statSync(join(fullDir, entry)).isDirectory()) {
+          next.push(dir ? join(dir, entry) : entry);
+        }
+      }
+    }
+    dirs = next;
+  }
+
+  return dirs.map(dir => relative(projectRoot, join(projectRoot, dir)));
+}
+
+function asTable(value: TomlValue | undefined): TomlTable | null {
+  return value !== undefined && typeof value === 'object' && !Array.isArray(value) ? value : null;
+}
+
+function asStringArray(value: TomlValue | undefined): string[] {
+  return Array.isArray(value) ? value.filter((item): item is string => typeof item === 'string') : [];
+}
+
+/**
+ * Minimal TOML reader covering what Cargo manifests use: tables, arrays of
+ * tables, dotted keys, strings, arrays and inline tables.
+ */
+export function parseToml(content: string): TomlTable {
+  return new TomlReader(content).parse();
+}
+
+class TomlReader {
+  private pos = 0;
+
+  constructor(private text: string) {}
+
+  parse(): TomlTable {
+    const root: TomlTable = {};
+    let current = root;
+
+    while (true) {
+      this.skipBlank(true);
+      if (this.pos >= this.text.length) break;
+
+      if (this.text[this.pos] === '[') {
+        const isArray = this.text[this.pos + 1] === '[';
+        this.pos += isArray ? 2 : 1;
+        const keys = this.parseKey();
+        this.expect(isArray ? ']]' : ']');
+
+        if (isArray) {
+          const parent = this.tableAt(root, keys.slice(0, -1));
+          const last = keys[keys.length - 1];
+          const list = Array.isArray(parent[last]) ? parent[last] as TomlValue[] : [];
+          parent[last] = list;
+          current = {};
+          list.push(current);
+        } else {
+          current = this.tableAt(root, keys);
+        }
+      } else {
+        const keys = this.parseKey();
+        this.expect('=');
+        const value = this.parseValue();
+        const table = this.tableAt(current, keys.slice(0, -1));
+        table[keys[keys.length - 1]] = value;
+      }
+
+      this.skipBlank(false);
+    }
+
+    return root;
+  }
+
+  private tableAt(root: TomlTable, keys: string[]): TomlTable {
+    let table = root;
+    for (const key of keys) {
+      let next = table[key];
+      if (Array.isArray(next)) {
+        next = next[next.length - 1];
+      }
+      if (next === undefined || typeof next !== 'object' || Array.isArray(next)) {
+        next = {};
+        table[key] = next;
+      }
+      table = next as TomlTable;
+    }
+    return table;
+  }
+
+  private parseKey(): string[] {
+    const keys: string[] = [];
+    while (true) {
+      this.skipBlank(false);
+      const char = this.text[this.pos];
+      if (char === '"' || char === '\'') {
+        keys.push(this.parseString());
+      } else {
+        const match = this.text.substring(this.pos).match(/^[A-Za-z0-9_-]+/);
+        if (!match) throw new Error(`Invalid TOML key at offset ${this.pos}`);
+        keys.push(match[0]);
+        this.pos += match[0].length;
+      }
+      this.skipBlank(false);
+      if (this.text[this.pos] !== '.') break;
+      this.pos++;
+    }
+    return keys;
+  }
+
+  private parseValue(): TomlValue {
+    this.skipBlank(false);
+    const char = this.text[this.pos];
+
+    if (char === '"' || char === '\'') {
+      return this.parseString();
+    }
+
+    if (char === '[') {
+      this.pos++;
+      const values: TomlValue[] = [];
+      while (true) {
+        this.skipBlank(true);
+        if (this.text[this.pos] === ']') { this.pos++; break; }
+        values.push(this.parseValue());
+        this.skipBlank(true);
+        if (this.text[this.pos] === ',') { this.pos++; continue; }
+        this.expect(']');
+        break;
+      }
+      return values;
+    }
+
+    if (char === '{') {
+      this.pos++;
+      const table: TomlTable = {};
+      while (true) {
+        this.skipBlank(false);
+        if (this.text[this.pos] === '}') { this.pos++; break; }
+        const keys = this.parseKey();
+        this.expect('=');
+        this.tableAt(table, keys.slice(0, -1))[keys[keys.length - 1]] = this.parseValue();
+        this.skipBlank(false);
+        if (this.text[this.pos] === ',') { this.pos++; continue; }
+        this.expect('}');
+        break;
+      }
+      return table;
+    }
+
+    const match = this.text.substring(this.pos).match(/^[^\s,\]}#]+/);
+    if (!match) throw new Error(`Invalid TOML value at offset ${this.pos}`);
+    this.pos += match[0].length;
+
+    if (match[0] === 'true') return true;
+    if (match[0] === 'false') return false;
+    const num = Number(match[0].replace(/_/g, ''));
+    return isNaN(num) ? match[0] : num;
+  }
+
+  private parseString(): string {
+    const quote = this.text[this.pos];
+    const multiline = this.text.startsWith(quote.repeat(3), this.pos);
+    const delimiter = multiline ? quote.repeat(3) : quote;
+    this.pos += delimiter.length;
+    if (multiline && this.text[this.pos] === '\n') this.pos++;
+
+    let value = '';
+    while (this.pos < this.text.length && !this.text.startsWith(delimiter, this.pos)) {
+      const char = this.text[this.pos];
+      if (char === '\\' && quote === '"') {
+        const escaped = this.text[this.pos + 1];
+        const escapes: Record<string, string> = { n: '\n', t: '\t', r: '\r', '"': '"', '\\': '\\' };
+        value += escapes[escaped] ?? escaped;
+        this.pos += 2;
+      } else {
+        value += char;
+        this.pos++;
+      }
+    }
+    this.pos += delimiter.length;
+    return value;
+  }
+
+  private skipBlank(newlines: boolean): void {
+    while (this.pos < this.text.length) {
+      const char = this.text[this.pos];
+      if (char === '#') {
+        const end = this.text.indexOf('\n', this.pos);
+        this.pos = end === -1 ? this.text.length : end;
+      } else if (char === ' ' || char === '\t' || char === '\r' || (newlines && char === '\n')) {
+        this.pos++;
+      } else {
+        break;
+      }
+    }
+  }
+
+  private expect(token: string): void {
+    this.skipBlank(false);
+    if (!this.text.startsWith(token, this.pos)) {
+      throw new Error(`Expected '${token}' at offset ${this.pos}`);
+    }
+    this.pos += token.length;
+  }
+}
@@ -424,8 +424,7 @@ export class ContextExtractor {
         calleeName: className,
         callType: 'constructor',
         line: lineNumber,
-        column: column,
-        isExternal: false
+        column: column
       });
     }
 
@@ -447,8 +446,7 @@ export class ContextExtractor {
           calleeName: functionName,
           callType: 'function',
           line: lineNumber,
-          column: column,
-          isExternal: false
+          column: column
         });
       }
     }
@@ -465,8 +463,7 @@ export class ContextExtractor {
         calleeName: `${objectName}.${methodName}`,
         callType: 'method',
         line: lineNumber,
-        column: column,
-        isExternal: false
+        column: column
       });
     }
     
@@ -482,7 +479,7 @@ export class ContextExtractor {
         callType: 'import',
         line: lineNumber,
         column: column,
-        isExternal: true
+        scope: 'external'
       });
     }
     
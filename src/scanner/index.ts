@@ -1,10 +1,19 @@
 import { readdir, stat, readFile } from 'fs/promises';
-import { join, relative, extname, basename } from 'path';
+import { join, relative, extname, basename, isAbsolute } from 'path';
 import { createHash } from 'crypto';
 import ignore from 'ignore';
 import { existsSync, readFileSync } from 'fs';
 import type { FileInfo, ScanOptions } from '../types/index.js';
 
+/**
+ * State of a file as last indexed, used to skip reading unchanged files
+ */
+export interface KnownFile {
+  size: number;
+  lastModified: string; // ISO timestamp
+  hash: string;
+}
+
 export const LANGUAGE_MAP: Record<string, string> = {
   '.ts': 'typescript',
   '.tsx': 'typescript',
@@ -217,11 +226,40 @@ export class FileScanner {
 
   public async scan(): Promise<FileInfo[]> {
     const files: FileInfo[] = [];
-    await this.scanDirectory(this.options.rootPath, files);
+    for await (const file of this.scanStream()) {
+      files.push(file);
+    }
     return files;
   }
 
-  private async scanDirectory(dirPath: string, files: FileInfo[]): Promise<void> {
+  /**
+   * Yield files as they are discovered instead of collecting them first.
+   *
+   * Files listed in `known` whose size and mtime are unchanged are yielded
+   * without reading their content: they carry the known hash, an empty
+   * `content` and `unchanged: true`.
+   */
+  public async *scanStream(known?: Map<string, KnownFile>): AsyncGenerator<FileInfo> {
+    yield* this.scanDirectory(this.options.rootPath, known);
+  }
+
+  /**
+   * Scan a single file, applying the same ignore rules and limits as a full scan.
+   * Returns null if the file is ignored, outside the root or unreadable.
+   */
+  public async scanFile(fullPath: string): Promise<FileInfo | null> {
+    const relativePath = relative(this.options.rootPath, fullPath);
+    if (!relativePath || relativePath.startsWith('..') || isAbsolute(relativePath) || this.isIgnored(relativePath)) {
+      return null;
+    }
+    return this.processFile(fullPath, relativePath);
+  }
+
+  public isIgnored(relativePath: string): boolean {
+    return this.ignorer.ignores(relativePath);
+  }
+
+  private async *scanDirectory(dirPath: string, known?: Map<string, KnownFile>): AsyncGenerator<FileInfo> {
     const entries = await readdir(dirPath, { withFileTypes: true });
 
     for (const entry of entries) {
@@ -233,17 +271,17 @@ export class FileScanner {
       }
 
       if (entry.isDirectory()) {
-        await this.scanDirectory(fullPath, files);
+        yield* this.scanDirectory(fullPath, known);
       } else if (entry.isFile() || (entry.isSymbolicLink() && this.options.followSymlinks)) {
-        const fileInfo = await this.processFile(fullPath, relativePath);
+        const fileInfo = await this.processFile(fullPath, relativePath, known?.get(fullPath));
         if (fileInfo) {
-          files.push(fileInfo);
+          yield fileInfo;
         }
       }
     }
   }
 
-  private async processFile(fullPath: string, relativePath: string): Promise<FileInfo | null> {
+  private async processFile(fullPath: string, relativePath: string, previous?: KnownFile): Promise<FileInfo | null> {
     try {
       const stats = await stat(fullPath);
 
@@ -261,9 +299,24 @@ export class FileScanner {
         }
       }
 
+      const language = this.detectLanguage(fullPath);
+
+      // Cheap pre-check: same size and mtime as the indexed copy means unchanged
+      if (previous && previous.size === stats.size && previous.lastModified === stats.mtime.toISOString()) {
+        return {
+          path: fullPath,
+          relativePath,
+          content: '',
+          hash: previous.hash,
+          size: stats.size,
+          language,
+          lastModified: stats.mtime,
+          unchanged: true
+        };
+      }
+
       const content = await readFile(fullPath, 'utf-8');
       const hash = createHash('sha256').update(content).digest('hex');
-      const language = this.detectLanguage(fullPath);
 
       return {
         path: fullPath,
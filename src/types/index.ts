@@ -6,6 +6,7 @@ export interface FileInfo {
   size: number;
   language: string | null;
   lastModified: Date;
+  unchanged?: boolean; // Content not read: size and mtime match the index
 }
 
 export interface ScanOptions {
@@ -23,6 +24,8 @@ export interface Symbol {
   lineEnd: number;
   signature?: string;
   documentation?: string;
+  qualifiedName?: string;  // Name within its file's scope chain, e.g. `UserService.login`
+  parent?: string;         // qualifiedName of the enclosing symbol in the same file
   metadata?: Record<string, unknown>;
 }
 
@@ -31,16 +34,41 @@ export interface CallReference {
   callType: 'function' | 'method' | 'constructor' | 'import';
   line: number;
   column?: number;
-  isExternal?: boolean;
+  scope?: 'local' | 'project' | 'external' | 'unknown';  // What the callee's first name is bound to; unset means project
+  importSource?: string;  // Module that name is imported from; decides project vs external once imports resolve
+}
+
+export interface SymbolReference {
+  name: string;       // As written: `User`, `config.port`, `models::User`
+  kind: 'call' | 'type' | 'read' | 'write' | 'import' | 'export';
+  line: number;
+  column?: number;
+}
+
+export interface TypeRelationship {
+  source: string;
+  target: string;
+  kind: 'extends' | 'implements' | 'embeds';
+  line: number;
+}
+
+export interface ImportBinding {
+  source: string;     // Module specifier as written
+  names: string[];    // Imported names; '*' for namespace/glob imports, empty for whole-module imports
+  line?: number;
+  reexport?: boolean; // `export ... from` / `pub use`
 }
 
 export interface ExtractedContext {
   symbols: Symbol[];
   imports: string[];
+  importBindings?: ImportBinding[]; // Set by extractors that know which names each import brings in
   exports: string[];
   dependencies: string[];
   comments: string[];
   calls: CallReference[];
+  references?: SymbolReference[];  // Non-call references; calls and imports are added when stored
+  relationships?: TypeRelationship[];
   structure: CodeStructure;
 }
 
@@ -49,14 +77,22 @@ export interface IndexOptions extends Partial<ScanOptions> {
   verbose?: boolean;
   languages?: string[];
   updateExisting?: boolean;
+  jobs?: number;
+  embeddings?: boolean; // Also update the vector index used by semantic search
 }
 
 export interface IndexStats {
   filesIndexed: number;
+  filesRemoved?: number;
+  filesRenamed?: number;
   symbolsExtracted: number;
   totalTokens: number;
   timeElapsed: number;
   errors: number;
+  crates?: number;
+  callsResolved?: number;
+  callsAmbiguous?: number;
+  chunksEmbedded?: number;
 }
 
 export interface QueryOptions {
@@ -65,7 +101,17 @@ export interface QueryOptions {
   includeSymbols?: boolean;
   includeImports?: boolean;
   fileTypes?: string[];
+  crates?: string[];
   sortBy?: 'relevance' | 'path' | 'size' | 'modified';
+  semantic?: boolean; // Blend vector similarity into the ranking
+}
+
+export interface FindOptions {
+  types?: string[];     // Symbol types, e.g. function, class
+  fileTypes?: string[];
+  crates?: string[];
+  limit?: number;
+  includeContent?: boolean; // Source lines of each symbol
 }
 
 export interface QueryResult {
@@ -73,6 +119,7 @@ export interface QueryResult {
   symbols: SymbolResult[];
   totalTokens: number;
   truncated: boolean;
+  packing?: PackingReport;
   query_type?: 'search' | 'related';
   source_file?: string;
 }
@@ -89,17 +136,62 @@ export interface FileResult {
   imports?: string[];
   exports?: string[];
   metadata?: Record<string, unknown>;
+  score?: RelevanceScore;
+  form?: PackForm;
+}
+
+// How much of a result was kept to fit the token budget, most to least detailed
+export type PackForm = 'full' | 'elided' | 'skeleton' | 'signature';
+
+// What the token-budget packer kept, shortened and dropped
+export interface PackingReport {
+  budget: number;
+  used: number;
+  reduced: Array<{ label: string; form: PackForm; savedTokens: number }>;
+  dropped: Array<{ label: string; tokens: number }>; // Tokens of the smallest form
 }
 
 export interface SymbolResult {
   id: number;
   name: string;
+  qualifiedName?: string;
   type: string;
   filePath: string;
   lineStart: number;
   lineEnd: number;
   signature?: string;
   content?: string;
+  parent?: string;          // Qualified name of the enclosing symbol
+  members?: SymbolMember[]; // Direct children (methods, nested types, ...)
+  score?: RelevanceScore;
+  form?: PackForm;
+}
+
+// A symbol found by the fuzzy finder
+export interface FindResult extends SymbolResult {
+  matchedName: string;  // The name or qualified name that matched
+  similarity: number;   // 0-1; 1 for an exact match, ignoring case
+  distance: number;     // Edits between the pattern and matchedName
+}
+
+// Ranking signals of a search result, each normalized to 0-1
+export interface RelevanceScore {
+  total: number;      // Weighted sum of the signals below
+  bm25: number;       // Full-text rank relative to the best match
+  exactName: number;  // Name equals, starts with or contains the term
+  symbolType: number; // Types above functions above values; 0 for files
+  definition: number; // Defines the term rather than only mentioning it
+  centrality: number; // Files calling or importing it
+  recency: number;    // Halves every 30 days since the last commit
+  semantic: number;   // Embedding similarity to the query; 0 unless searching semantically
+}
+
+export interface SymbolMember {
+  id: number;
+  name: string;
+  qualifiedName?: string;
+  type: string;
+  lineStart: number;
 }
 
 export interface DatabaseInfo {
@@ -109,6 +201,14 @@ export interface DatabaseInfo {
   lastIndexed: Date | null;
 }
 
+export interface DatabaseHealth {
+  path: string;
+  status: 'ok' | 'missing' | 'outdated' | 'newer' | 'corrupt';
+  schemaVersion: number | null;
+  latestVersion: number;
+  problems: string[];
+}
+
 export interface CallGraphNode {
   symbolId?: number;
   fileId: number;
@@ -125,10 +225,46 @@ export interface CallGraphEdge {
   line: number;
 }
 
+export interface ModuleEdge {
+  fileId: number;
+  filePath: string;
+  specifier: string;
+  line: number | null;
+  reexport: boolean;
+}
+
 export interface DependencyGraph {
   root: CallGraphNode;
   calls: CallGraphEdge[];
   calledBy: CallGraphEdge[];
+  importedBy: ModuleEdge[];  // Files importing the root symbol, through re-exports
+}
+
+export interface TypeHierarchyNode {
+  symbolId?: number;         // Unset for types that are not indexed
+  name: string;
+  type: string;              // Symbol type, or 'external'
+  filePath?: string;
+  line?: number;
+  kind: TypeRelationship['kind'];  // How it relates to the node above it
+  children: TypeHierarchyNode[];
+}
+
+export interface TypeDefinition {
+  symbolId: number;
+  name: string;
+  qualifiedName?: string;
+  type: string;
+  filePath: string;
+  line: number;
+}
+
+export interface TypeHierarchy {
+  name: string;
+  definitions: TypeDefinition[];
+  supertypes: TypeHierarchyNode[];    // What the type extends, implements or embeds
+  subtypes: TypeHierarchyNode[];      // Types that extend or embed it
+  implementors: TypeHierarchyNode[];  // Types that implement it (interfaces, traits)
 }
 
 export interface ImpactAnalysis {
@@ -240,12 +376,22 @@ export interface QueryCommandOptions {
   recent?: string;
   blame?: boolean;
   languages?: string;
+  crates?: string;
+  sort?: string;
+  semantic?: boolean;
+}
+
+export interface HierarchyCommandOptions {
+  format: 'ai' | 'json' | 'human';
+  depth: string;
 }
 
 export interface FindCommandOptions {
   includeContent?: boolean;
   format: 'ai' | 'json' | 'human';
   type?: string;
+  languages?: string;
+  limit: string;
 }
 
 export interface RelatedCommandOptions {
@@ -269,13 +415,16 @@ export interface QueryCommandResult {
   primarySymbol: SymbolResult | null;
   allSymbols: SymbolResult[];
   files: FileResult[];
+  symbols: SymbolResult[]; // Ranked search matches
   usages: FileResult[];
   dependencyGraph: DependencyGraph | null;
   impactAnalysis: ImpactAnalysis | null;
   gitHistory: GitHistory | null;
   recentChanges: RecentFileChanges[] | null;
+  suggestions: string[]; // Similar symbol names, when nothing matched
   totalTokens: number;
   truncated: boolean;
+  packing: PackingReport | null;
 }
 
 // Database row types
@@ -296,6 +445,8 @@ export interface FileQueryRow extends FileRow {
   symbol_count?: number;
   tokens?: number;
   relativePath?: string; // Alias for relative_path used in some queries
+  lastModified?: string; // Alias for last_modified used in some queries
+  rank?: number | null;  // bm25() of the full-text match; NULL for LIKE matches
 }
 
 export interface SymbolRow {
@@ -308,6 +459,8 @@ export interface SymbolRow {
   signature: string | null;
   documentation: string | null;
   metadata: string | null;
+  qualified_name?: string | null;
+  parent_symbol_id?: number | null;
 }
 
 export interface SymbolQueryRow extends SymbolRow {
@@ -316,6 +469,9 @@ export interface SymbolQueryRow extends SymbolRow {
   language?: string | null;
   lineStart?: number; // Alias for line_start used in some queries
   lineEnd?: number; // Alias for line_end used in some queries
+  fileSize?: number;
+  fileModified?: string;
+  rank?: number | null; // bm25() of the full-text match; NULL for LIKE matches
 }
 
 export interface CallGraphRow {
@@ -328,6 +484,7 @@ export interface CallGraphRow {
   call_type: string;
   line_number: number;
   column_number: number | null;
+  resolution_confidence: number | null;
 }
 
 export interface DatabaseCountRow {
@@ -402,11 +559,14 @@ export interface SymbolLocationRow {
   line_end?: number;
 }
 
-export interface FileReferenceRow extends Partial<FileRow> {
+export interface ReferenceRow {
   fileId: number;
-  filePath?: string;
-  content: string;
-  language: string | null;
+  filePath: string;
+  name: string;
+  kind: SymbolReference['kind'];
+  line: number;
+  column: number | null;
+  symbolName: string | null;  // Innermost symbol containing the reference
 }
 
 // More specific query result types
@@ -451,6 +611,17 @@ export interface CallerResult {
   callLine: number;
 }
 
+export interface TypeRelationRow {
+  relatedName: string;       // Supertype or subtype name as written
+  kind: TypeRelationship['kind'];
+  relationLine: number | null;
+  symbolId: number | null;
+  name: string | null;
+  type: string | null;
+  line: number | null;
+  filePath: string | null;
+}
+
 export interface SymbolLookupResult {
   id: number;
   name: string;
@@ -0,0 +1,19 @@
+import { splitIdentifier, identifierParts } from '../identifiers.js';
+
+describe('identifiers', () => {
+  test('should split identifiers in every naming style', () => {
+    expect(splitIdentifier('getUserById')).toEqual(['get', 'user', 'by', 'id']);
+    expect(splitIdentifier('HTTPServer')).toEqual(['http', 'server']);
+    expect(splitIdentifier('get_user_by_id')).toEqual(['get', 'user', 'by', 'id']);
+    expect(splitIdentifier('get-user-by-id')).toEqual(['get', 'user', 'by', 'id']);
+    expect(splitIdentifier('models::User')).toEqual(['models', 'user']);
+    expect(splitIdentifier('self.user_id')).toEqual(['self', 'user', 'id']);
+    expect(splitIdentifier('utf8Decode')).toEqual(['utf8', 'decode']);
+  });
+
+  test('should list the words of compound identifiers once', () => {
+    const parts = identifierParts('fn get_user(id) { getUserById(id); getUserById(id); Parser::new() }\ndef __init__');
+
+    expect(parts.split('\n')).toEqual(['get user', 'get user by id', 'init']);
+  });
+});
@@ -0,0 +1,15 @@
+import { validateJobs, ValidationError } from '../validation.js';
+
+describe('validation', () => {
+  test('should accept whole numbers of jobs', () => {
+    expect(validateJobs('1')).toBe(1);
+    expect(validateJobs(' 8 ')).toBe(8);
+  });
+
+  test('should reject anything else as --jobs', () => {
+    for (const value of ['0', '-2', '2.5', '4x', 'all', '']) {
+      expect(() => validateJobs(value)).toThrow(ValidationError);
+    }
+    expect(() => validateJobs('2.5')).toThrow('Invalid value for --jobs: "2.5"');
+  });
+});
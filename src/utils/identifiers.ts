@@ -0,0 +1,39 @@
+// Candidate identifiers in source text, in any language
+const IDENTIFIER = /[\p{L}_$][\p{L}\p{N}_$]*/gu;
+
+/**
+ * Lower-case words of an identifier or path: `getUserById`,
+ * `get_user_by_id` and `get-user-by-id` all give `get user by id`;
+ * `HTTPServer` gives `http server` and `models::User` gives `models user`
+ */
+export function splitIdentifier(identifier: string): string[] {
+  return identifier
+    .replace(/([\p{Ll}\p{N}])(\p{Lu})/gu, '$1 $2')
+    .replace(/(\p{Lu}+)(\p{Lu}\p{Ll})/gu, '$1 $2')
+    .toLowerCase()
+    .split(/[^\p{L}\p{N}]+/u)
+    .filter(Boolean);
+}
+
+/**
+ * Words of the compound identifiers in `text`, one identifier per line, for
+ * the `identifier_parts` column of the full-text indexes. Identifiers that
+ * are already a single word are left out; the text itself keeps the
+ * original tokens.
+ */
+export function identifierParts(text: string): string {
+  const seen = new Set<string>();
+  const lines: string[] = [];
+
+  for (const [identifier] of text.matchAll(IDENTIFIER)) {
+    if (seen.has(identifier)) continue;
+    seen.add(identifier);
+
+    const words = splitIdentifier(identifier);
+    if (words.length > 1 || (words.length === 1 && words[0] !== identifier.toLowerCase())) {
+      lines.push(words.join(' '));
+    }
+  }
+
+  return lines.join('\n');
+}
@@ -0,0 +1,53 @@
+import { readFileSync } from 'fs';
+
+/**
+ * Parse JSON with comments and trailing commas (tsconfig.json style).
+ */
+export function parseJsonc(text: string): unknown {
+  let output = '';
+  let inString = false;
+
+  for (let i = 0; i < text.length; i++) {
+    const char = text[i];
+
+    if (inString) {
+      output += char;
+      if (char === '\\') {
+        output += text[++i] ?? '';
+      } else if (char === '"') {
+        inString = false;
+      }
+      continue;
+    }
+
+    if (char === '"') {
+      inString = true;
+      output += char;
+    } else if (char === ',' && /^\s*[}\]]/.test(text.substring(i + 1, i + 200))) {
+      // Trailing comma: `[1, 2,]` / `{"a": 1,}`
+      continue;
+    } else if (char === '/' && text[i + 1] === '/') {
+      while (i < text.length && text[i] !== '\n') i++;
+      output += '\n';
+    } else if (char === '/' && text[i + 1] === '*') {
+      i += 2;
+      while (i < text.length && !(text[i] === '*' && text[i + 1] === '/')) i++;
+      i++;
+    } else {
+      output += char;
+    }
+  }
+
+  return JSON.parse(output);
+}
+
+/**
+ * Read and parse a JSONC file, returning null if it is missing or invalid.
+ */
+export function readJsonc(path: string): unknown {
+  try {
+    return parseJsonc(readFileSync(path, 'utf-8'));
+  } catch {
+    return null;
+  }
+}
@@ -17,6 +17,16 @@ export function validatePositiveInteger(value: string, optionName: string): numb
   return num;
 }
 
+export function validateJobs(value: string): number {
+  // parseInt would accept "2.5" or "4x"
+  if (!/^\d+$/.test(value.trim())) {
+    throw new ValidationError(
+      `Invalid value for --jobs: "${value}". Must be a positive integer.`
+    );
+  }
+  return validatePositiveInteger(value, '--jobs');
+}
+
 export function validateTokenLimit(value: string): number {
   const num = validatePositiveInteger(value, '--tokens');
   if (num > 100000) {
@@ -35,6 +45,16 @@ export function validateFormat(value: string): 'ai' | 'json' | 'human' {
   return value as 'ai' | 'json' | 'human';
 }
 
+export function validateSortBy(value: string): 'relevance' | 'path' | 'size' | 'modified' {
+  const validModes = ['relevance', 'path', 'size', 'modified'] as const;
+  if (!validModes.includes(value as typeof validModes[number])) {
+    throw new ValidationError(
+      `Invalid sort mode: "${value}". Must be one of: ${validModes.join(', ')}`
+    );
+  }
+  return value as 'relevance' | 'path' | 'size' | 'modified';
+}
+
 export function validateLanguages(value: string): string[] {
   const languages = value.split(',').map(l => l.trim()).filter(Boolean);
   if (languages.length === 0) {
@@ -55,6 +75,14 @@ export function validateLanguages(value: string): string[] {
   return languages;
 }
 
+export function validateCrates(value: string): string[] {
+  const crates = value.split(',').map(c => c.trim()).filter(Boolean);
+  if (crates.length === 0) {
+    throw new ValidationError('No crates specified');
+  }
+  return crates;
+}
+
 export function validateDays(value: string): number {
   const num = validatePositiveInteger(value, '--recent');
   if (num > 365) {